@@ -0,0 +1,35 @@
+//! Measure the overhead of the coordination between the threads of a search, apart from the
+//! cryptographic work, to show whether the shared state or the channel to the main loop limit
+//! scaling to many threads.
+//!
+//! Run with `cargo bench --bench coordination`.
+//!
+//! Methodology: for each number of threads, a search runs without generating any accounts.
+//! Its workers still lock the shared state to clone the patterns and send a count message for
+//! every batch, and its main loop still locks the state to process each message, so the threads
+//! do nothing but coordinate. The overhead per batch is the thread time of the run divided by
+//! the batches the main loop counted, and is compared to the time of generating a batch, from
+//! the encoding benchmark. Threads beyond the number of cores mostly measure contention.
+
+use std::time::Duration;
+
+use algo_vanity_rs::{COUNT_PER_LOOP, benchmark};
+
+/// Thread counts to measure
+const THREADS: [usize; 6] = [1, 4, 16, 64, 128, 256];
+
+/// Patterns cloned by each worker for every batch
+const PATTERNS: usize = 100;
+
+/// Time to run each search for
+const DURATION: Duration = Duration::from_secs(3);
+
+fn main() {
+    let encoding = benchmark::encoding(10_000);
+    let batch = (encoding.derive + encoding.encode) * (COUNT_PER_LOOP * COUNT_PER_LOOP) as u32 / encoding.accounts as u32;
+    println!("Batch:   {} accounts in {:.1} ms per thread, {PATTERNS} patterns", COUNT_PER_LOOP * COUNT_PER_LOOP, batch.as_secs_f64() * 1e3);
+
+    for threads in THREADS {
+        print!("{}", benchmark::coordination(threads, PATTERNS, DURATION).report(batch));
+    }
+}
@@ -0,0 +1,27 @@
+//! Break down the time of generating an address into deriving the account from its seed and
+//! encoding its address, to show what optimizing either step could gain at most.
+//!
+//! Run with `cargo bench --bench encoding`, or `algo-vanity-rs --benchmark-encoding`.
+//!
+//! Methodology: accounts are generated exactly like in the search, from perturbed seeds,
+//! and derived into a buffer while timing only the derivation. The addresses of the
+//! buffered accounts are then encoded with `encode_string`, the base32 step including the
+//! checksum, while timing only the encoding. A warm-up round runs first, and the round with
+//! the lowest total time is reported, as it is the least disturbed by other processes.
+
+use algo_vanity_rs::benchmark;
+
+/// Accounts per round
+const ACCOUNTS: usize = 100_000;
+
+/// Measured rounds, after one warm-up round
+const ROUNDS: usize = 5;
+
+fn main() {
+    benchmark::encoding(ACCOUNTS / 10);
+    let best = (0..ROUNDS)
+        .map(|_| benchmark::encoding(ACCOUNTS))
+        .min_by_key(|b| b.derive + b.encode)
+        .unwrap();
+    print!("{}", best.report());
+}
@@ -0,0 +1,34 @@
+//! Record the versions of dependencies resolved in the lock file for `version_info`
+
+use std::{env, fs, path::Path};
+
+/// Dependencies whose resolved versions are reported
+const REPORTED: [(&str, &str); 2] = [("algo_rust_sdk", "SDK_VERSION"), ("rand", "RAND_VERSION")];
+
+fn main() {
+    let lock_path = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock_path.display());
+    let lock = fs::read_to_string(&lock_path).unwrap_or_default();
+
+    for (package, variable) in REPORTED {
+        let version = locked_version(&lock, package).unwrap_or("unknown");
+        println!("cargo:rustc-env={variable}={version}");
+    }
+}
+
+/// Version of a direct dependency in the lock file. When several versions of the package
+/// are locked, this crate's dependency list names the version, otherwise the package entry does.
+fn locked_version<'a>(lock: &'a str, package: &str) -> Option<&'a str> {
+    let dependency = lock.lines()
+        .skip_while(|line| *line != format!("name = \"{}\"", env!("CARGO_PKG_NAME")))
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.trim().strip_prefix('"')?.strip_suffix("\","))
+        .find(|dep| dep.split(' ').next() == Some(package))?;
+    if let Some((_, version)) = dependency.split_once(' ') {
+        return Some(version);
+    }
+
+    let name = format!("name = \"{package}\"");
+    let mut lines = lock.lines().skip_while(|line| *line != name).skip(1);
+    lines.next()?.strip_prefix("version = \"")?.strip_suffix('"')
+}
@@ -0,0 +1,36 @@
+//! Compare the search rate of the full match path against the address only path, using a
+//! pattern which matches often enough for the cost of handling matches to show.
+//!
+//! Run with `cargo run --release --example channel_throughput`
+
+use std::time::Duration;
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher};
+
+/// Time to search with each path
+const DURATION: Duration = Duration::from_secs(10);
+
+fn rate(address_only: bool) -> f64 {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-bench-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        save_path: save_path.to_string_lossy().into_owned(),
+        duration: Some(DURATION),
+        address_only,
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    s.total_count as f64 / s.run_time.as_secs_f64()
+}
+
+fn main() {
+    let full = rate(false);
+    let light = rate(true);
+    println!("Full match path:   {full:.0} addresses/s");
+    println!("Address only path: {light:.0} addresses/s ({:+.1}%)", (light / full - 1.0) * 100.0);
+}
@@ -0,0 +1,32 @@
+//! Compare the rate of generating accounts with seeds taken from a pool against seeds
+//! generated by each worker itself.
+//!
+//! Run with `cargo run --release --example seed_pool`
+
+use std::time::Instant;
+
+use rand::{SeedableRng, rngs::StdRng, thread_rng};
+use algo_vanity_rs::{Accounts, SeedPool, keys::KeyType};
+
+/// Accounts to generate with each source of seeds
+const ACCOUNTS: usize = 200_000;
+
+fn rate(pool: Option<SeedPool>) -> f64 {
+    let rng = StdRng::from_rng(thread_rng()).unwrap();
+    let accounts = match pool {
+        Some(pool) => Accounts::with_pool(rng, KeyType::default(), pool),
+        None => Accounts::new(rng, KeyType::default()),
+    };
+
+    let start = Instant::now();
+    let matches = accounts.take(ACCOUNTS).filter(|acc| acc.address().encode_string().starts_with('A')).count();
+    assert!(matches > 0);
+    ACCOUNTS as f64 / start.elapsed().as_secs_f64()
+}
+
+fn main() {
+    let own = rate(None);
+    let pooled = rate(Some(SeedPool::spawn(16)));
+    println!("Own seeds:    {own:.0} accounts/s");
+    println!("Pooled seeds: {pooled:.0} accounts/s ({:+.1}%)", (pooled / own - 1.0) * 100.0);
+}
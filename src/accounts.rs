@@ -0,0 +1,325 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+    sync::{Arc, Mutex, mpsc, atomic::{AtomicUsize, Ordering}},
+    thread,
+};
+
+use rand::{Rng,SeedableRng,rngs::StdRng,thread_rng};
+use rand_chacha::ChaCha12Rng;
+use serde::Serialize;
+use algo_rust_sdk::account::Account;
+
+use crate::{COUNT_PER_LOOP, keys::KeyType};
+
+/// Endless iterator of randomly generated accounts, for applying custom vanity criteria.
+///
+/// This hack allows for only generating orders of magnitudes fewer random numbers.
+/// After generating a random seed, two random indices of the seed are picked. These
+/// are counted up to change the seed ever so slightly for each account, which is much
+/// faster than generating 32 new random numbers every time. The same perturbed seed is
+/// used `COUNT_PER_LOOP`^2 times (10_000 by default) before a new seed is generated.
+///
+/// Iterators in different domains, such as the worker threads of a search, draw their seeds
+/// from separate streams of random numbers, even when given the same generator. Every byte
+/// of every seed stays random, so accounts carry no trace of the domain they came from.
+///
+/// **Security caveat:** accounts from the same batch share 30 of their 32 seed bytes.
+/// Anyone holding the mnemonic of one account can easily recover every other account
+/// of its batch, so never disclose the mnemonic of an account you did not keep if you
+/// keep another account generated close to it.
+///
+/// ```
+/// let vanity = algo_vanity_rs::accounts()
+///     .find(|acc| acc.address().encode_string().ends_with('A'))
+///     .unwrap();
+/// assert!(vanity.address().encode_string().ends_with('A'));
+/// println!("{}", vanity.mnemonic());
+/// ```
+pub struct Accounts {
+    rng: StdRng,
+    batches: Option<BatchCounter>,
+    key_type: KeyType,
+    seed: [u8; 32],
+    index0: usize,
+    index1: usize,
+    count: usize,
+    domain: u16,
+    pool: Option<SeedPool>,
+}
+
+/// Iterator of accounts from a securely seeded random number generator
+pub fn accounts() -> Accounts {
+    Accounts::new(StdRng::from_rng(thread_rng()).expect("Unable to seed random number generator"), KeyType::default())
+}
+
+impl Accounts {
+    /// Generate accounts of the given key type from a random number generator
+    pub fn new(rng: StdRng, key_type: KeyType) -> Self {
+        let mut accounts = Accounts { rng, batches: None, key_type, seed: [0; 32], index0: 0, index1: 0, count: 0, domain: 0, pool: None };
+        accounts.reseed();
+        accounts
+    }
+
+    /// Generate accounts from seeds of the pool, falling back to the random number generator
+    /// should the pool ever stop
+    pub fn with_pool(rng: StdRng, key_type: KeyType, pool: SeedPool) -> Self {
+        let mut accounts = Accounts { rng, batches: None, key_type, seed: [0; 32], index0: 0, index1: 0, count: 0, domain: 0, pool: Some(pool) };
+        accounts.reseed();
+        accounts
+    }
+
+    /// Generate the accounts of the batches of a deterministic seed space within a partition,
+    /// see [`Partition`]. The seed of every batch is derived from `seed` and the global index of
+    /// the batch alone, so the same batch is generated the same way on every machine.
+    ///
+    /// ```
+    /// use algo_vanity_rs::{Accounts, Partition, keys::KeyType};
+    ///
+    /// let first = |partition| Accounts::partitioned(0, partition, KeyType::default())
+    ///     .next().unwrap().address().encode_string();
+    /// assert_eq!(first(Partition::new(1, 2).unwrap()), first(Partition::new(1, 3).unwrap()));
+    /// assert_ne!(first(Partition::new(0, 2).unwrap()), first(Partition::new(1, 2).unwrap()));
+    /// ```
+    pub fn partitioned(seed: u64, partition: Partition, key_type: KeyType) -> Self {
+        let batches = BatchCounter { seed, next: partition.offset, stride: partition.stride };
+        let mut accounts = Accounts { rng: StdRng::seed_from_u64(seed), batches: Some(batches), key_type, seed: [0; 32], index0: 0, index1: 0, count: 0, domain: 0, pool: None };
+        accounts.reseed();
+        accounts
+    }
+
+    /// Generate accounts in the given domain, from a stream of random numbers separate from
+    /// that of every other domain. The stream is keyed by the generator, so iterators given the
+    /// same generator still generate different accounts in different domains. Batches of a
+    /// partition are derived with the domain as well.
+    ///
+    /// ```
+    /// use rand::{SeedableRng, rngs::StdRng};
+    /// use algo_vanity_rs::{Accounts, keys::KeyType};
+    ///
+    /// let first = |domain| Accounts::new(StdRng::seed_from_u64(0), KeyType::default())
+    ///     .with_domain(domain).next().unwrap().address().encode_string();
+    /// assert_eq!(first(1), first(1));
+    /// assert_ne!(first(0), first(1));
+    /// ```
+    pub fn with_domain(mut self, domain: u16) -> Self {
+        self.domain = domain;
+        match self.batches.as_mut() {
+            // Derive the current batch again, now within the domain
+            Some(batches) => batches.next = batches.next.wrapping_sub(batches.stride),
+            None => {
+                let mut rng = ChaCha12Rng::from_seed(self.rng.gen());
+                rng.set_stream(domain.into());
+                self.rng = StdRng::from_rng(rng).expect("Unable to seed random number generator");
+            },
+        }
+        self.reseed();
+        self
+    }
+
+    /// Take a fresh seed and two different indices to perturb
+    fn reseed(&mut self) {
+        let batch = if let Some(batches) = self.batches.as_mut() {
+            batches.take(self.domain)
+        } else {
+            match self.pool.as_ref().and_then(SeedPool::take) {
+                Some(batch) => batch,
+                None => BatchSeed::generate(&mut self.rng),
+            }
+        };
+        (self.seed, self.index0, self.index1) = (batch.seed, batch.index0, batch.index1);
+        self.count = 0;
+    }
+}
+
+/// Seed of a batch of accounts along with the two indices perturbed within the batch
+struct BatchSeed {
+    seed: [u8; 32],
+    index0: usize,
+    index1: usize,
+}
+
+impl BatchSeed {
+    /// Generate a random seed and two different indices
+    fn generate<R: Rng>(rng: &mut R) -> Self {
+        let seed = rng.gen();
+        let index0 = rng.gen_range(0..32);
+        let mut index1 = rng.gen_range(0..32);
+        while index0 == index1 {
+            index1 = rng.gen_range(0..32);
+        }
+        BatchSeed { seed, index0, index1 }
+    }
+}
+
+/// Share of the deterministic seed space of a search, for splitting a search across machines
+/// without any coordination between them.
+///
+/// The seed space is a sequence of batches of `COUNT_PER_LOOP`^2 accounts each, with the seed
+/// of batch `i` derived from the search seed and `i` alone. A partition covers the batches
+/// `offset`, `offset + stride`, `offset + 2 * stride` and so on, so machine `k` of `N` searching
+/// with offset `k` and stride `N` never generates a batch of another machine. The worker threads
+/// of each machine share its batches, but generate them in domains of their own, see
+/// [`Accounts::with_domain`].
+///
+/// An offset past the stride continues a partition part way, such that machine `k` can resume
+/// with offset `k + j * N` after each of its threads searched `j` batches. Partitions only stay
+/// disjoint while their offsets differ modulo their common stride.
+///
+/// ```
+/// use algo_vanity_rs::Partition;
+///
+/// let batches = |offset, stride| Partition::new(offset, stride).unwrap().batches().take(3).collect::<Vec<_>>();
+/// assert_eq!(batches(0, 1), [0, 1, 2]);
+/// assert_eq!(batches(1, 3), [1, 4, 7]);
+/// assert_eq!(batches(7, 3), [7, 10, 13]);
+/// assert!(Partition::new(0, 0).is_err());
+/// ```
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Serialize)]
+pub struct Partition {
+    /// Index of the first batch of the partition
+    pub offset: u64,
+    /// Number of batches from one batch of the partition to the next
+    pub stride: u64,
+}
+
+impl Default for Partition {
+    fn default() -> Self {
+        Partition { offset: 0, stride: 1 }
+    }
+}
+
+impl Partition {
+    /// Partition of every `stride`th batch, starting at batch `offset`
+    pub fn new(offset: u64, stride: u64) -> Result<Self, String> {
+        if stride == 0 {
+            return Err(String::from("stride must be at least 1"))
+        }
+        Ok(Partition { offset, stride })
+    }
+
+    /// Global indices of the batches of the partition, in the order they are generated
+    pub fn batches(self) -> impl Iterator<Item = u64> {
+        (0..).map_while(move |i: u64| i.checked_mul(self.stride)?.checked_add(self.offset))
+    }
+}
+
+/// Next batch of a partition of the deterministic seed space
+struct BatchCounter {
+    seed: u64,
+    next: u64,
+    stride: u64,
+}
+
+impl BatchCounter {
+    /// Derive the seed of the next batch from the search seed, the batch index and the domain
+    fn take(&mut self, domain: u16) -> BatchSeed {
+        let mut key = [0; 32];
+        key[..8].copy_from_slice(&self.seed.to_le_bytes());
+        key[8..16].copy_from_slice(&self.next.to_le_bytes());
+        key[16..18].copy_from_slice(&domain.to_le_bytes());
+        self.next = self.next.wrapping_add(self.stride);
+        BatchSeed::generate(&mut StdRng::from_seed(key))
+    }
+}
+
+/// Bytes of an entropy file seeding the random number generator of each worker thread
+pub const ENTROPY_PER_THREAD: usize = 32;
+
+/// Read the seeds of the random number generators of `threads` worker threads from the first
+/// [`ENTROPY_PER_THREAD`] bytes per thread of an entropy file, such as one written from a
+/// hardware random number generator. Only those bytes are read, so the path may also be a
+/// device streaming random bytes.
+///
+/// The bytes only seed the generators, which then stretch them over the whole search, so the
+/// file is never exhausted. A file holding fewer bytes than the threads need is rejected rather
+/// than padded. Seeding from the same bytes generates the same accounts, thus an entropy file
+/// must be as secret as the mnemonics, and never be reused for another search.
+///
+/// ```
+/// let path = std::env::temp_dir().join(format!("algo-vanity-doc-entropy-{}", std::process::id()));
+/// std::fs::write(&path, [7u8; 80]).unwrap();
+/// assert_eq!(algo_vanity_rs::read_entropy(&path, 2).unwrap(), [[7u8; 32]; 2]);
+/// assert!(algo_vanity_rs::read_entropy(&path, 3).is_err());
+/// # std::fs::remove_file(path).unwrap();
+/// ```
+pub fn read_entropy(path: impl AsRef<Path>, threads: usize) -> io::Result<Vec<[u8; ENTROPY_PER_THREAD]>> {
+    let required = threads * ENTROPY_PER_THREAD;
+    let mut bytes = Vec::with_capacity(required);
+    File::open(path)?.take(required as u64).read_to_end(&mut bytes)?;
+    if bytes.len() < required {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!(
+            "{threads} threads need {required} bytes of entropy, the file holds only {}", bytes.len(),
+        )))
+    }
+    Ok(bytes.chunks_exact(ENTROPY_PER_THREAD).map(|chunk| chunk.try_into().expect("Chunks are exact")).collect())
+}
+
+/// Fresh random seeds generated ahead of time by a dedicated thread, which keeps up to
+/// `capacity` of them ready. Worker threads then only take a seed when reseeding, instead
+/// of generating it on their critical path. The thread stops once every clone of the pool
+/// has been dropped.
+///
+/// Accounts are reseeded only once per `COUNT_PER_LOOP`^2 accounts, so the pool saves
+/// little time per account, see `cargo run --release --example seed_pool`.
+///
+/// ```
+/// use rand::{SeedableRng, rngs::StdRng};
+/// use algo_vanity_rs::{Accounts, SeedPool, keys::KeyType};
+///
+/// let pool = SeedPool::spawn(4);
+/// let mut accounts = Accounts::with_pool(StdRng::seed_from_u64(0), KeyType::default(), pool.clone());
+/// assert_eq!(pool.taken(), 1);
+///
+/// accounts.nth(10_000).unwrap();
+/// assert_eq!(pool.taken(), 2);
+/// ```
+#[derive(Clone,Debug)]
+pub struct SeedPool {
+    rx: Arc<Mutex<mpsc::Receiver<BatchSeed>>>,
+    taken: Arc<AtomicUsize>,
+}
+
+impl SeedPool {
+    /// Bytes each seed kept ready takes up
+    pub const SEED_SIZE: usize = std::mem::size_of::<BatchSeed>();
+
+    /// Start a thread keeping up to `capacity` seeds ready
+    pub fn spawn(capacity: usize) -> Self {
+        let (tx, rx) = mpsc::sync_channel(capacity);
+        thread::spawn(move || {
+            let mut rng = thread_rng();
+            while tx.send(BatchSeed::generate(&mut rng)).is_ok() {}
+        });
+        SeedPool { rx: Arc::new(Mutex::new(rx)), taken: Arc::new(AtomicUsize::new(0)) }
+    }
+
+    /// Number of seeds taken from the pool so far
+    pub fn taken(&self) -> usize {
+        self.taken.load(Ordering::Relaxed)
+    }
+
+    /// Wait for the next seed, unless the pool has stopped
+    fn take(&self) -> Option<BatchSeed> {
+        let batch = self.rx.lock().ok()?.recv().ok()?;
+        self.taken.fetch_add(1, Ordering::Relaxed);
+        Some(batch)
+    }
+}
+
+impl Iterator for Accounts {
+    type Item = Account;
+
+    fn next(&mut self) -> Option<Account> {
+        if self.count == COUNT_PER_LOOP * COUNT_PER_LOOP {
+            self.reseed();
+        }
+        if self.count.is_multiple_of(COUNT_PER_LOOP) {
+            self.seed[self.index0] = self.seed[self.index0].wrapping_add(1);
+        }
+        self.seed[self.index1] = self.seed[self.index1].wrapping_add(1);
+        self.count += 1;
+        Some(self.key_type.account_from_seed(self.seed))
+    }
+}
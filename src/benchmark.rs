@@ -0,0 +1,251 @@
+//! Micro-benchmarks of the steps of generating an address, and of the coordination around them
+
+use std::{hint::black_box, time::{Duration, Instant}};
+
+use algo_rust_sdk::account::Account;
+
+use crate::{Accounts, COUNT_PER_LOOP, SearchConfig, VanitySearcher, accounts, locale::NumberLocale};
+
+/// Pattern searched for while measuring search rates, long enough to never be found
+const TUNE_PATTERN: &str = "TUNETHREADS";
+
+/// Rates within this fraction of the best rate are considered as good, as the difference is
+/// within the noise of a short measurement
+const TUNE_TOLERANCE: f64 = 0.02;
+
+/// Time spent on each step of generating the addresses of some accounts
+#[derive(Clone,Copy,Debug)]
+pub struct EncodingBreakdown {
+    /// Number of accounts measured
+    pub accounts: usize,
+    /// Time spent deriving the accounts (key pairs) from their seeds
+    pub derive: Duration,
+    /// Time spent encoding the addresses of the derived accounts, including their checksum
+    pub encode: Duration,
+}
+
+impl EncodingBreakdown {
+    /// Fraction of the total time spent encoding addresses
+    pub fn encode_share(&self) -> f64 {
+        self.encode.as_secs_f64() / (self.derive + self.encode).as_secs_f64()
+    }
+
+    /// Nanoseconds per account of a step
+    pub fn per_account(&self, step: Duration) -> f64 {
+        step.as_nanos() as f64 / self.accounts.max(1) as f64
+    }
+
+    /// Human readable report of the breakdown
+    pub fn report(&self) -> String {
+        let total = self.derive + self.encode;
+        format!(
+            "Accounts:  {}\nDerive:    {:8.0} ns/account ({:.1}%)\nEncode:    {:8.0} ns/account ({:.1}%)\nTotal:     {:8.0} ns/account ({:.0} accounts/s)\n",
+            self.accounts,
+            self.per_account(self.derive), (1.0 - self.encode_share()) * 100.0,
+            self.per_account(self.encode), self.encode_share() * 100.0,
+            self.per_account(total), self.accounts as f64 / total.as_secs_f64(),
+        )
+    }
+}
+
+/// Measure how the time of generating addresses splits between deriving accounts from their
+/// seeds and encoding their addresses as base32 strings with `encode_string`.
+///
+/// Accounts are first derived into a buffer, timing only the derivation. The addresses of
+/// the buffered accounts are then encoded, timing only the encoding. Separating the steps
+/// this way keeps either from sharing caches or branch predictions with the other, which
+/// slightly favors both compared to the interleaved search loop.
+///
+/// ```
+/// let breakdown = algo_vanity_rs::benchmark::encoding(100);
+/// assert_eq!(breakdown.accounts, 100);
+/// assert!((0.0..=1.0).contains(&breakdown.encode_share()));
+/// ```
+pub fn encoding(count: usize) -> EncodingBreakdown {
+    encoding_with(accounts(), count)
+}
+
+/// Measure the breakdown of [`encoding`] with the given accounts
+pub fn encoding_with(accounts: Accounts, count: usize) -> EncodingBreakdown {
+    let start = Instant::now();
+    let derived: Vec<Account> = accounts.take(count).collect();
+    let derive = start.elapsed();
+
+    let start = Instant::now();
+    for acc in &derived {
+        black_box(acc.address().encode_string());
+    }
+    let encode = start.elapsed();
+
+    EncodingBreakdown { accounts: derived.len(), derive, encode }
+}
+
+/// Cost of the coordination between the threads of a search, apart from generating accounts
+#[derive(Clone,Copy,Debug)]
+pub struct CoordinationOverhead {
+    /// Number of worker threads
+    pub threads: usize,
+    /// Number of batches the main loop counted
+    pub batches: usize,
+    /// Time the threads ran for
+    pub elapsed: Duration,
+}
+
+impl CoordinationOverhead {
+    /// Thread time spent on the coordination around each batch
+    pub fn per_batch(&self) -> Duration {
+        (self.elapsed * self.threads as u32).checked_div(self.batches as u32).unwrap_or(self.elapsed)
+    }
+
+    /// Fraction of the time of a batch spent on coordination, given the time it takes a thread
+    /// to generate and check the accounts of a batch
+    pub fn share_of(&self, batch: Duration) -> f64 {
+        let overhead = self.per_batch().as_secs_f64();
+        overhead / (overhead + batch.as_secs_f64())
+    }
+
+    /// Human readable report of the overhead, relative to the time of generating a batch
+    pub fn report(&self, batch: Duration) -> String {
+        format!(
+            "Threads: {:4}  Batches: {:9}  Overhead: {:9.2} us/batch ({:.4}% of a batch)\n",
+            self.threads, self.batches, self.per_batch().as_secs_f64() * 1e6, self.share_of(batch) * 100.0,
+        )
+    }
+}
+
+/// Measure the cost of the coordination between the threads of a search, by running a search
+/// which generates no accounts. Its workers still lock the shared state to clone the patterns,
+/// and report a count message for every batch, which the main loop locks the state to process,
+/// just like in a real search. The overhead per batch is the thread time of the run divided by
+/// the batches the main loop counted. When the main loop can not keep up with the messages,
+/// fewer batches are counted and the overhead grows, showing the main loop as the bottleneck.
+///
+/// With a real search, each batch is `COUNT_PER_LOOP`^2 accounts, so the overhead is only
+/// significant if it approaches the time of generating that many accounts, see [`encoding`].
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let overhead = algo_vanity_rs::benchmark::coordination(2, 10, Duration::from_millis(200));
+/// assert_eq!(overhead.threads, 2);
+/// assert!(overhead.batches > 0);
+/// assert!((0.0..=1.0).contains(&overhead.share_of(Duration::from_millis(100))));
+/// ```
+pub fn coordination(threads: usize, patterns: usize, duration: Duration) -> CoordinationOverhead {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-coordination-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: (0..patterns).map(|i| format!("P{i}")).collect(),
+        threads,
+        duration: Some(duration),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).dry_run().start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().expect("Unable to lock mutex");
+    CoordinationOverhead { threads, batches: s.total_count / (COUNT_PER_LOOP * COUNT_PER_LOOP), elapsed: s.run_time }
+}
+
+/// Search rate measured with a number of threads
+#[derive(Clone,Copy,Debug,PartialEq)]
+pub struct ThreadMeasurement {
+    pub threads: usize,
+    /// Addresses per second
+    pub rate: f64,
+}
+
+/// Search rates measured at several thread counts, for choosing the number of threads
+#[derive(Clone,Debug,PartialEq)]
+pub struct ThreadTuning {
+    pub measurements: Vec<ThreadMeasurement>,
+}
+
+impl ThreadTuning {
+    /// Measurement with the fewest threads whose rate is within [`TUNE_TOLERANCE`] of the best,
+    /// as more threads are not worth it for gains within the noise of the measurement
+    pub fn recommended(&self) -> Option<ThreadMeasurement> {
+        let best = self.measurements.iter().map(|m| m.rate).fold(0.0, f64::max);
+        self.measurements.iter()
+            .filter(|m| m.rate >= best * (1.0 - TUNE_TOLERANCE))
+            .min_by_key(|m| m.threads)
+            .copied()
+    }
+
+    /// Table of the rate and speedup over the first measurement of each thread count, marking
+    /// the recommended one
+    ///
+    /// ```
+    /// use algo_vanity_rs::benchmark::{ThreadMeasurement, ThreadTuning};
+    ///
+    /// let tuning = ThreadTuning { measurements: vec![
+    ///     ThreadMeasurement { threads: 1, rate: 1000.0 },
+    ///     ThreadMeasurement { threads: 2, rate: 1900.0 },
+    /// ] };
+    /// assert_eq!(tuning.table(), "\
+    /// Threads      Rate (a/s)  Speedup
+    ///       1           1,000    1.00x
+    ///       2           1,900    1.90x  <- recommended
+    /// ");
+    /// ```
+    pub fn table(&self) -> String {
+        let base = self.measurements.first().map_or(0.0, |m| m.rate);
+        let recommended = self.recommended().map(|m| m.threads);
+
+        let mut table = format!("{:>7}  {:>14}  {:>7}\n", "Threads", "Rate (a/s)", "Speedup");
+        for m in &self.measurements {
+            let marker = if Some(m.threads) == recommended { "  <- recommended" } else { "" };
+            table += &format!("{:>7}  {:>14}  {:>6.2}x{marker}\n", m.threads, NumberLocale::default().group(m.rate.round() as u64), m.rate / base);
+        }
+        table
+    }
+}
+
+/// Thread counts worth measuring on a machine with `logical` threads, which are the powers of
+/// two below it and the number of logical threads itself
+///
+/// ```
+/// use algo_vanity_rs::benchmark::tune_thread_counts;
+///
+/// assert_eq!(tune_thread_counts(1), [1]);
+/// assert_eq!(tune_thread_counts(8), [1, 2, 4, 8]);
+/// assert_eq!(tune_thread_counts(12), [1, 2, 4, 8, 12]);
+/// ```
+pub fn tune_thread_counts(logical: usize) -> Vec<usize> {
+    let mut counts: Vec<usize> = std::iter::successors(Some(1usize), |n| n.checked_mul(2)).take_while(|n| *n < logical).collect();
+    counts.push(logical.max(1));
+    counts
+}
+
+/// Measure the rate at each thread count with `measure`, such as [`search_rate`]
+pub fn tune_with(counts: &[usize], mut measure: impl FnMut(usize) -> f64) -> ThreadTuning {
+    ThreadTuning { measurements: counts.iter().map(|&threads| ThreadMeasurement { threads, rate: measure(threads) }).collect() }
+}
+
+/// Measure the search rate with `threads` by searching for a pattern which is never found for
+/// `duration`. Workers count their attempts in whole batches of `COUNT_PER_LOOP`^2 accounts, so
+/// the duration should cover several batches per thread for the rate to be meaningful.
+pub fn search_rate(threads: usize, duration: Duration) -> f64 {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-tune-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from(TUNE_PATTERN)],
+        threads,
+        duration: Some(duration),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().expect("Unable to lock mutex");
+    s.total_count as f64 / s.run_time.as_secs_f64()
+}
+
+/// Measure the search rate for `duration` at each of the [`tune_thread_counts`] of the machine
+pub fn tune(logical: usize, duration: Duration) -> ThreadTuning {
+    tune_with(&tune_thread_counts(logical), |threads| search_rate(threads, duration))
+}
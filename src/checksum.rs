@@ -0,0 +1,76 @@
+//! Constraints on the characters of an address derived from its checksum
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::{ADDRESS_CHARS, PlacementMode, difficulty::{self, CHECKSUM_LENGTH, DifficultyModel, LAST_CHARS}};
+
+/// Requires the checksum region of an address, see [`difficulty::checksum_region`], to end
+/// with a suffix, in addition to the address matching a pattern.
+///
+/// The checksum is a hash of the public key, so the suffix is independent of any pattern
+/// within the public key, and every match is rarer by the [`ChecksumConstraint::factor`].
+///
+/// ```
+/// use algo_vanity_rs::checksum::ChecksumConstraint;
+///
+/// let suffix: ChecksumConstraint = "ze".parse().unwrap();
+/// assert!(suffix.satisfied("ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZE"));
+/// assert!(!suffix.satisfied("ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZA"));
+///
+/// assert!("".parse::<ChecksumConstraint>().is_err());
+/// assert!("AB".parse::<ChecksumConstraint>().is_err());
+/// assert!("ABCDEFGA".parse::<ChecksumConstraint>().is_err());
+/// assert!("A1".parse::<ChecksumConstraint>().is_err());
+/// ```
+#[derive(Clone,Debug,PartialEq,Serialize)]
+pub struct ChecksumConstraint {
+    /// Characters the checksum region has to end with
+    pub suffix: String,
+}
+
+impl ChecksumConstraint {
+    /// Whether the checksum region of the address ends with the suffix
+    pub fn satisfied(&self, address: &str) -> bool {
+        difficulty::checksum_region(address).ends_with(&self.suffix)
+    }
+
+    /// Probability that a random address satisfies the constraint
+    pub fn probability(&self, model: DifficultyModel) -> f64 {
+        model.probability(&self.suffix, &PlacementMode::End.into())
+    }
+
+    /// Factor by which the constraint multiplies the attempts to find any pattern
+    ///
+    /// ```
+    /// use algo_vanity_rs::{checksum::ChecksumConstraint, difficulty::DifficultyModel};
+    ///
+    /// // The final character only carries 3 bits, the others 5 bits each
+    /// let suffix: ChecksumConstraint = "ZE".parse().unwrap();
+    /// assert_eq!(suffix.factor(DifficultyModel::Uniform), 256.0);
+    /// ```
+    pub fn factor(&self, model: DifficultyModel) -> f64 {
+        self.probability(model).recip()
+    }
+}
+
+impl FromStr for ChecksumConstraint {
+    type Err = String;
+
+    /// Parse the suffix, which has to fit within the checksum region and end with a character
+    /// which can end an address
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let suffix = s.to_uppercase();
+        if suffix.is_empty() || suffix.len() > CHECKSUM_LENGTH {
+            return Err(format!("checksum suffix must be between 1 and {CHECKSUM_LENGTH} characters"));
+        }
+        if let Some(c) = suffix.chars().find(|c| !ADDRESS_CHARS.contains(*c)) {
+            return Err(format!("checksum character '{c}' can not exist in an Algorand Address"));
+        }
+        if difficulty::impossible_at_end(&suffix) {
+            return Err(format!("checksum suffix must end with one of {LAST_CHARS}, as the last character only encodes 3 bits"));
+        }
+        Ok(ChecksumConstraint { suffix })
+    }
+}
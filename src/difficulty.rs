@@ -0,0 +1,511 @@
+use serde::Serialize;
+
+use crate::{AddressMatch, Placement, SearchPlacement, locale::NumberLocale, window::PatternWindow};
+
+/// Number of characters in an encoded Algorand address
+pub const ADDRESS_LENGTH: usize = 58;
+
+/// Number of distinct characters per position in a base32 address
+const ALPHABET_SIZE: f64 = 32.0;
+
+/// The 36 address bytes (public key and checksum) are 288 bits, which leaves only 3 bits
+/// for the final character, so it can only be one of these 8 characters
+pub(crate) const LAST_CHARS: &str = "AEIMQUY4";
+
+/// Expected attempts beyond which a pattern is considered effectively impossible to find
+pub const IMPRACTICAL_ATTEMPTS: f64 = 1e15;
+
+/// Expected time for a single pattern beyond which the empty state suggests a shorter one
+pub const SLOW_PATTERN_SECS: f64 = 3600.0;
+
+/// Expected time to find a pattern beyond which its estimate is shown as ∞, 100 years
+pub const MAX_ETA_SECS: f64 = 100.0 * 365.25 * 24.0 * 3600.0;
+
+/// Number of leading address characters encoded only from bits of the public key. The next
+/// character holds the last bit of the public key and the first 4 bits of the checksum.
+pub const PAYLOAD_LENGTH: usize = 51;
+
+/// Whether the character at `index` of an address is (partly) derived from the checksum
+///
+/// ```
+/// use algo_vanity_rs::difficulty::{ADDRESS_LENGTH, is_checksum_derived};
+///
+/// assert!(!is_checksum_derived(0));
+/// assert!(!is_checksum_derived(50));
+/// assert!(is_checksum_derived(51));
+/// assert!(is_checksum_derived(ADDRESS_LENGTH - 1));
+/// ```
+pub fn is_checksum_derived(index: usize) -> bool {
+    index >= PAYLOAD_LENGTH
+}
+
+/// Number of trailing address characters (partly) derived from the checksum
+pub const CHECKSUM_LENGTH: usize = ADDRESS_LENGTH - PAYLOAD_LENGTH;
+
+/// Trailing characters of an address which are (partly) derived from the checksum
+///
+/// ```
+/// use algo_vanity_rs::difficulty::checksum_region;
+///
+/// assert_eq!(checksum_region("ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZE"), "FIGZAZE");
+/// assert_eq!(checksum_region("ALGO"), "");
+/// ```
+pub fn checksum_region(address: &str) -> &str {
+    address.get(PAYLOAD_LENGTH..).unwrap_or("")
+}
+
+/// Models of how likely a random address is to contain a pattern
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DifficultyModel {
+    /// Every character is equally likely everywhere, except at the end of the address
+    #[default]
+    Uniform,
+    /// Score each character by the bits its position carries, for all placements. The checksum
+    /// is a hash of the public key, so its characters are as random as those of the key,
+    /// except for the final character which only carries 3 bits.
+    ChecksumAware,
+}
+
+impl DifficultyModel {
+    /// Probability that a single random address matches the pattern with the given placement
+    ///
+    /// ```
+    /// use algo_vanity_rs::{PlacementMode, difficulty::DifficultyModel};
+    ///
+    /// let (start, anywhere) = (PlacementMode::Start.into(), PlacementMode::Anywhere.into());
+    /// let (uniform, aware) = (DifficultyModel::Uniform, DifficultyModel::ChecksumAware);
+    ///
+    /// // Patterns which can not reach the final character are estimated the same way
+    /// assert_eq!(aware.probability("ALGO", &start), uniform.probability("ALGO", &start));
+    /// // Ending an address with 'B' is impossible, so only the other positions count
+    /// assert!(aware.probability("AB", &anywhere) < uniform.probability("AB", &anywhere));
+    /// // Ending an address with 'A' is 4 times as likely as any one character elsewhere
+    /// assert!(aware.probability("AA", &anywhere) > uniform.probability("AA", &anywhere));
+    /// ```
+    pub fn probability(self, pattern: &str, placement: &SearchPlacement) -> f64 {
+        let len = pattern.len();
+        if len == 0 { return 1.0 }
+        if len > ADDRESS_LENGTH { return 0.0 }
+
+        let last = ADDRESS_LENGTH - len;
+        let fixed = ALPHABET_SIZE.powi(len as i32).recip();
+        let (start, anywhere, end) = match self {
+            // Anywhere counts the end like any other position, as it always has
+            DifficultyModel::Uniform => (fixed, (last + 1) as f64 * fixed, end_probability(pattern)),
+            DifficultyModel::ChecksumAware => (
+                offset_probability(pattern, 0),
+                (0..=last).map(|offset| offset_probability(pattern, offset)).sum(),
+                offset_probability(pattern, last),
+            ),
+        };
+
+        // Anywhere covers start and end as well, so it takes precedence
+        if placement.anywhere { return anywhere.min(1.0) }
+
+        let mut probability = 0.0;
+        if placement.start { probability += start }
+        if placement.end { probability += end }
+        probability
+    }
+
+    /// Expected number of addresses to generate before finding a match
+    pub fn expected_attempts(self, pattern: &str, placement: &SearchPlacement) -> f64 {
+        self.probability(pattern, placement).recip()
+    }
+
+    /// Number of addresses to generate for a match to have been found with probability `confidence`
+    pub fn attempts_for_confidence(self, pattern: &str, placement: &SearchPlacement, confidence: f64) -> f64 {
+        confidence_attempts(self.probability(pattern, placement), confidence)
+    }
+
+    /// Whether finding the pattern would require more than `IMPRACTICAL_ATTEMPTS` attempts
+    pub fn is_impractical(self, pattern: &str, placement: &SearchPlacement) -> bool {
+        self.expected_attempts(pattern, placement) > IMPRACTICAL_ATTEMPTS
+    }
+
+    /// Probability that a single random address holds the pattern of the window within it
+    ///
+    /// ```
+    /// use algo_vanity_rs::{PlacementMode, difficulty::DifficultyModel, window::PatternWindow};
+    ///
+    /// let window: PatternWindow = "10:20:ALGO".parse().unwrap();
+    /// assert_eq!(DifficultyModel::Uniform.window_probability(&window), 7.0 / 32f64.powi(4));
+    /// assert_eq!(DifficultyModel::ChecksumAware.window_probability(&window), 7.0 / 32f64.powi(4));
+    /// ```
+    pub fn window_probability(self, window: &PatternWindow) -> f64 {
+        let len = window.pattern.len();
+        let offsets = window.start..=window.end.saturating_sub(len);
+        match self {
+            DifficultyModel::Uniform => offsets.count() as f64 * ALPHABET_SIZE.powi(len as i32).recip(),
+            DifficultyModel::ChecksumAware => offsets.map(|offset| offset_probability(&window.pattern, offset)).sum(),
+        }
+    }
+
+    /// Expected attempts to find each pattern with the placement, or within its windows for the
+    /// windowed patterns, when every match is rarer by `factor`, such as due to a checksum suffix.
+    /// Windowed patterns missing from `vanities` are estimated after them.
+    ///
+    /// ```
+    /// use algo_vanity_rs::{PlacementMode, difficulty::DifficultyModel, window::PatternWindow};
+    ///
+    /// let vanities = ["ALGO".to_string()];
+    /// let windows: Vec<PatternWindow> = vec!["0:2:XY".parse().unwrap(), "56:58:XY".parse().unwrap()];
+    /// let estimates = DifficultyModel::Uniform.pattern_estimates(&vanities, &PlacementMode::Start.into(), &windows, 2.0);
+    /// assert_eq!(estimates, [(String::from("ALGO"), 2.0 * 32f64.powi(4)), (String::from("XY"), 1024.0)]);
+    /// ```
+    pub fn pattern_estimates(self, vanities: &[String], placement: &SearchPlacement, windows: &[PatternWindow], factor: f64) -> Vec<(String, f64)> {
+        let windowed = windows.iter().map(|w| &w.pattern).filter(|p| !vanities.contains(p));
+        let mut patterns: Vec<&String> = vanities.iter().collect();
+        for pattern in windowed {
+            if !patterns.contains(&pattern) { patterns.push(pattern) }
+        }
+
+        patterns.into_iter().map(|vanity| {
+            let mut windows = windows.iter().filter(|w| &w.pattern == vanity).peekable();
+            let probability = match windows.peek() {
+                Some(_) => windows.map(|w| self.window_probability(w)).sum(),
+                None => self.probability(vanity, placement),
+            };
+            (vanity.clone(), factor / probability)
+        }).collect()
+    }
+
+    /// Attempts made as a fraction of the expected attempts to find the pattern
+    pub fn progress(self, pattern: &str, placement: &SearchPlacement, attempts: usize) -> f64 {
+        attempts as f64 / self.expected_attempts(pattern, placement)
+    }
+}
+
+/// Number of addresses to generate for a match with probability `p` per address to have been
+/// found with probability `confidence`
+pub fn confidence_attempts(p: f64, confidence: f64) -> f64 {
+    if p >= 1.0 { return 1.0 }
+    (-confidence).ln_1p() / (-p).ln_1p()
+}
+
+/// Probability that a random address contains the pattern starting at character `offset`
+fn offset_probability(pattern: &str, offset: usize) -> f64 {
+    pattern.chars().enumerate().map(|(i, c)| match offset + i {
+        index if index == ADDRESS_LENGTH - 1 && LAST_CHARS.contains(c) => (LAST_CHARS.len() as f64).recip(),
+        index if index == ADDRESS_LENGTH - 1 => 0.0,
+        _ => ALPHABET_SIZE.recip(),
+    }).product()
+}
+
+/// Probability that a single random address matches the pattern with the given placement
+pub fn match_probability(pattern: &str, placement: &SearchPlacement) -> f64 {
+    DifficultyModel::default().probability(pattern, placement)
+}
+
+/// Probability that a random address ends with the pattern, taking into account that the
+/// final character only carries the last 3 bits of the checksum
+fn end_probability(pattern: &str) -> f64 {
+    match pattern.chars().last() {
+        Some(c) if LAST_CHARS.contains(c) => {
+            ALPHABET_SIZE.powi(pattern.len() as i32 - 1).recip() / LAST_CHARS.len() as f64
+        },
+        Some(_) => 0.0,
+        None => 1.0,
+    }
+}
+
+/// Whether the last character of the pattern can never end an address
+pub fn impossible_at_end(pattern: &str) -> bool {
+    pattern.chars().last().is_some_and(|c| !LAST_CHARS.contains(c))
+}
+
+/// Whether finding the pattern would require more than `IMPRACTICAL_ATTEMPTS` attempts
+pub fn is_impractical(pattern: &str, placement: &SearchPlacement) -> bool {
+    DifficultyModel::default().is_impractical(pattern, placement)
+}
+
+/// Expected number of addresses to generate before finding a match
+pub fn expected_attempts(pattern: &str, placement: &SearchPlacement) -> f64 {
+    DifficultyModel::default().expected_attempts(pattern, placement)
+}
+
+/// Number of addresses to generate for a match to have been found with probability `confidence`
+pub fn attempts_for_confidence(pattern: &str, placement: &SearchPlacement, confidence: f64) -> f64 {
+    DifficultyModel::default().attempts_for_confidence(pattern, placement, confidence)
+}
+
+/// Rarity score of a match in bits, like the leading zero bits of a proof of work. Finding a
+/// match with a score of `n` bits took `2^n` attempts on average, so every additional bit
+/// makes a match twice as rare. This is the [`quality`] of the match in bits rather than
+/// base32 characters.
+///
+/// ```
+/// use algo_vanity_rs::{AddressMatch, Placement, difficulty::rarity_bits};
+///
+/// let address = "ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZE";
+/// let bits_of = |target: &str, placement| rarity_bits(&AddressMatch::new(target, address, "", placement));
+///
+/// assert_eq!(bits_of("ALG", Placement::Start), 15.0);
+/// assert_eq!(bits_of("ALGO", Placement::Start), 30.0);
+/// assert!(bits_of("VUDU", Placement::Anywhere(8)) < 15.0);
+/// ```
+pub fn rarity_bits(address_match: &AddressMatch) -> f64 {
+    quality(address_match) * ALPHABET_SIZE.log2()
+}
+
+/// Character encoding zero bits in a base32 address
+const ZERO_CHAR: char = 'A';
+
+/// Score of how impressive a match is, roughly the number of address characters it fixes.
+///
+/// The score is the length of the pattern, reduced by how much easier its placement made
+/// it to find (in base32 digits), plus one for each zero character (`A`) extending the
+/// match further into the address.
+///
+/// ```
+/// use algo_vanity_rs::{AddressMatch, Placement, difficulty::quality};
+///
+/// let address = "ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZE";
+/// let quality_of = |target: &str, placement| quality(&AddressMatch::new(target, address, "", placement));
+///
+/// assert_eq!(quality_of("ALGO", Placement::Start), 6.0);
+/// assert_eq!(quality_of("ALG", Placement::Start), 3.0);
+/// assert!(quality_of("ZE", Placement::End) < 2.0);
+/// assert!(quality_of("VUDU", Placement::Anywhere(8)) < 3.0);
+/// assert!(quality_of("ALSO", Placement::Near(1)) < quality_of("ALGO", Placement::Start) - 1.0);
+/// ```
+pub fn quality(address_match: &AddressMatch) -> f64 {
+    let len = address_match.matched_text().len();
+    let address = &address_match.public;
+
+    let (easing, following) = match address_match.placement {
+        Placement::Start => (1.0, address.get(len..)),
+        Placement::End => (ALPHABET_SIZE / LAST_CHARS.len() as f64, None),
+        Placement::Anywhere(index) => {
+            let positions = ADDRESS_LENGTH.saturating_sub(len) + 1;
+            (positions as f64, address.get(index + len..))
+        },
+        Placement::Near(distance) => (near_count(len, distance), address.get(len..)),
+        Placement::Window { start, end, index } => {
+            let positions = (end - start).saturating_sub(len) + 1;
+            (positions as f64, address.get(index + len..))
+        },
+    };
+    let zeros = following.map_or(0, |rest| rest.chars().take_while(|c| *c == ZERO_CHAR).count());
+
+    len as f64 - easing.log(ALPHABET_SIZE) + zeros as f64
+}
+
+/// Number of strings of length `len` within Hamming distance `distance` of a given string
+fn near_count(len: usize, distance: usize) -> f64 {
+    let mut choices = 1.0;
+    let mut count = 1.0;
+    for k in 1..=distance.min(len) {
+        choices *= (len + 1 - k) as f64 / k as f64;
+        count += choices * (ALPHABET_SIZE - 1.0).powi(k as i32);
+    }
+    count
+}
+
+/// Attempts made as a fraction of the expected attempts to find the pattern. Fractions
+/// above 1 mean the pattern is taking longer than expected.
+///
+/// ```
+/// use algo_vanity_rs::{SearchPlacement, difficulty::progress};
+///
+/// let start = SearchPlacement { start: true, anywhere: false, end: false };
+/// assert_eq!(progress("AB", &start, 0), 0.0);
+/// assert_eq!(progress("AB", &start, 512), 0.5);
+/// assert_eq!(progress("AB", &start, 2048), 2.0);
+/// ```
+pub fn progress(pattern: &str, placement: &SearchPlacement, attempts: usize) -> f64 {
+    DifficultyModel::default().progress(pattern, placement, attempts)
+}
+
+/// Number of filled cells of a progress bar `width` cells wide, full from a fraction of 1
+///
+/// ```
+/// use algo_vanity_rs::difficulty::bar_fill;
+///
+/// assert_eq!(bar_fill(0.0, 10), 0);
+/// assert_eq!(bar_fill(0.04, 10), 0);
+/// assert_eq!(bar_fill(0.45, 10), 5);
+/// assert_eq!(bar_fill(1.0, 10), 10);
+/// assert_eq!(bar_fill(3.7, 10), 10);
+/// assert_eq!(bar_fill(f64::NAN, 10), 0);
+/// ```
+pub fn bar_fill(fraction: f64, width: usize) -> usize {
+    if fraction.is_nan() { return 0 }
+    (fraction.clamp(0.0, 1.0) * width as f64).round() as usize
+}
+
+/// Lines reassuring that the search is working before its first match, with the expected time
+/// to the first match of any of the patterns at the search rate, and a tip when a pattern alone
+/// is expected to take longer than [`SLOW_PATTERN_SECS`]. Without a measured rate yet, only
+/// the expected attempts are given.
+///
+/// ```
+/// use algo_vanity_rs::{PlacementMode, difficulty::{DifficultyModel, empty_state}, locale::NumberLocale};
+///
+/// let lines = empty_state(DifficultyModel::Uniform, &["ALGO".into()], &PlacementMode::Start.into(), 0.0, NumberLocale::En);
+/// assert_eq!(lines, ["No matches yet, the search is running", "Expected first match after about 1,048,576 attempts"]);
+/// ```
+pub fn empty_state(model: DifficultyModel, vanities: &[String], placement: &SearchPlacement, rate: f64, locale: NumberLocale) -> Vec<String> {
+    if vanities.is_empty() {
+        return vec![String::from("No patterns left to search for")]
+    }
+
+    // The first match is of whichever pattern comes first, so their probabilities add up
+    let attempts = vanities.iter().map(|v| model.probability(v, placement)).sum::<f64>().recip();
+    let mut lines = vec![String::from("No matches yet, the search is running")];
+    if rate <= 0.0 {
+        lines.push(format!("Expected first match after about {} attempts", locale.attempts(attempts)));
+        return lines
+    }
+    lines.push(format!("Expected first match in about {} ({} attempts)", format_duration(attempts / rate), locale.attempts(attempts)));
+
+    // Point out the slowest pattern if it alone takes long
+    let hardest = vanities.iter().max_by(|a, b| model.expected_attempts(a, placement).total_cmp(&model.expected_attempts(b, placement)));
+    if let Some(hardest) = hardest {
+        let secs = model.expected_attempts(hardest, placement) / rate;
+        if secs > SLOW_PATTERN_SECS {
+            let kind = match (placement.start, placement.anywhere, placement.end) {
+                (true, false, false) => "prefix",
+                (false, false, true) => "suffix",
+                _ => "pattern",
+            };
+            lines.push(format!(
+                "Tip: the {}-character {kind} {hardest} alone may take {}, every character less makes it {ALPHABET_SIZE} times faster",
+                hardest.len(), format_duration(secs),
+            ));
+        }
+    }
+    lines
+}
+
+/// Table of the expected attempts to find each pattern, see [`DifficultyModel::pattern_estimates`]
+///
+/// ```
+/// use algo_vanity_rs::{difficulty::estimates_table, locale::NumberLocale};
+///
+/// let estimates = [(String::from("ALGO"), 1048576.0), (String::from("ALGORAND"), 32f64.powi(8))];
+/// assert_eq!(estimates_table(&estimates, NumberLocale::En), "\
+/// Pattern   Expected attempts
+/// ALGO              1,048,576
+/// ALGORAND  1,099,511,627,776
+/// ");
+/// ```
+pub fn estimates_table(estimates: &[(String, f64)], locale: NumberLocale) -> String {
+    let attempts: Vec<String> = estimates.iter().map(|(_, attempts)| locale.attempts(*attempts)).collect();
+    let width = estimates.iter().map(|(pattern, _)| pattern.len()).max().unwrap_or(0).max("Pattern".len());
+    let attempts_width = attempts.iter().map(|a| a.chars().count()).max().unwrap_or(0).max("Expected attempts".len());
+
+    let mut table = format!("{:width$}  {:>attempts_width$}\n", "Pattern", "Expected attempts");
+    for ((pattern, _), attempts) in estimates.iter().zip(attempts) {
+        table += &format!("{pattern:width$}  {attempts:>attempts_width$}\n");
+    }
+    table
+}
+
+/// Estimated time to find a pattern with the expected attempts at the search rate. Every
+/// address is as likely to match as the ones before it, so this is the expected time from now
+/// on, which only changes with the rate. Without a measured rate yet, it is still estimating.
+///
+/// ```
+/// use algo_vanity_rs::difficulty::eta;
+///
+/// assert_eq!(eta(1048576.0, 0.0), "estimating…");
+/// assert_eq!(eta(32f64.powi(6), 100_000.0), "~2h 58m");
+/// assert_eq!(eta(32f64.powi(12), 100_000.0), "∞");
+/// assert_eq!(eta(f64::INFINITY, 100_000.0), "∞");
+/// ```
+pub fn eta(expected_attempts: f64, rate: f64) -> String {
+    if rate <= 0.0 { return String::from("estimating…") }
+    match expected_attempts / rate {
+        secs if secs <= MAX_ETA_SECS => format!("~{}", format_duration(secs)),
+        _ => String::from("∞"),
+    }
+}
+
+/// Format a number of attempts with thousands separators
+pub fn format_attempts(attempts: f64) -> String {
+    NumberLocale::default().attempts(attempts)
+}
+
+/// Format a count with a unit suited to its magnitude, keeping three significant digits
+///
+/// ```
+/// use algo_vanity_rs::difficulty::format_count;
+///
+/// assert_eq!(format_count(0), "0");
+/// assert_eq!(format_count(999), "999");
+/// assert_eq!(format_count(1_500), "1.50 thousand");
+/// assert_eq!(format_count(25_300_000), "25.3 million");
+/// assert_eq!(format_count(999_999_999), "1.00 billion");
+/// assert_eq!(format_count(4_200_000_000), "4.20 billion");
+/// assert_eq!(format_count(123_000_000_000_000), "123 trillion");
+/// assert_eq!(format_count(5_000_000_000_000_000), "5000 trillion");
+/// ```
+pub fn format_count(n: usize) -> String {
+    NumberLocale::default().count(n)
+}
+
+/// Format a number of seconds as a short human readable duration
+pub fn format_duration(secs: f64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const YEAR: f64 = 365.25 * DAY as f64;
+
+    if !secs.is_finite() { return String::from("∞") }
+    if secs >= YEAR { return format!("{:.1} years", secs / YEAR) }
+
+    let s = secs.max(0.0).round() as u64;
+    match s {
+        s if s < MINUTE => format!("{s}s"),
+        s if s < HOUR => format!("{}m {}s", s / MINUTE, s % MINUTE),
+        s if s < DAY => format!("{}h {}m", s / HOUR, (s % HOUR) / MINUTE),
+        s => format!("{}d {}h", s / DAY, (s % DAY) / HOUR),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start() -> SearchPlacement { SearchPlacement { start: true, anywhere: false, end: false } }
+
+    #[test]
+    fn one_character_start_pattern() {
+        // Each address starts with the character with probability 1/32
+        let half = attempts_for_confidence("A", &start(), 0.5);
+        let likely = attempts_for_confidence("A", &start(), 0.95);
+        assert!((half - 0.5f64.ln() / (31.0f64 / 32.0).ln()).abs() < 1e-9, "{half}");
+        assert!((likely - 0.05f64.ln() / (31.0f64 / 32.0).ln()).abs() < 1e-9, "{likely}");
+        assert!(half < expected_attempts("A", &start()));
+        assert!(likely > expected_attempts("A", &start()));
+    }
+
+    #[test]
+    fn rarer_patterns_and_higher_confidence_take_more_attempts() {
+        let (short, long) = (attempts_for_confidence("AL", &start(), 0.5), attempts_for_confidence("ALG", &start(), 0.5));
+        assert!((long / short - 32.0).abs() < 0.05, "{short} {long}");
+        assert!(attempts_for_confidence("AL", &start(), 0.95) > short);
+
+        // Half of the expected attempts is too few for even odds, as the median is ln(2) of the mean
+        let p = match_probability("ALGO", &start());
+        assert!((attempts_for_confidence("ALGO", &start(), 0.5) * p - 2f64.ln()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn impossible_last_characters() {
+        // The last character encodes 3 bits, so only every fourth letter or digit can end an address
+        assert!(!impossible_at_end("ALGOA"));
+        assert!(!impossible_at_end("ALGO4"));
+        assert!(impossible_at_end("ALGOB"));
+        assert!(impossible_at_end("ALGO7"));
+        assert!(!impossible_at_end(""));
+    }
+
+    #[test]
+    fn certain_matches_take_one_attempt() {
+        assert_eq!(attempts_for_confidence("", &start(), 0.5), 1.0);
+        assert_eq!(attempts_for_confidence("", &start(), 0.95), 1.0);
+    }
+}
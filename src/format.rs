@@ -0,0 +1,332 @@
+//! Formats of results files, detected from the file extension or contents
+
+use std::{fs, io::{self, Write}, path::Path};
+
+use algo_rust_sdk::account::Account;
+use data_encoding::BASE64;
+use serde::{Serialize, Deserialize};
+
+use crate::AddressMatch;
+
+/// Gzip compressed files start with these bytes
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Field of a match which can be selected for the results file
+#[derive(Clone,Copy,Debug,PartialEq,Eq,clap::ValueEnum,Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MatchField {
+    Target,
+    Public,
+    Mnemonic,
+    Placement,
+    Seed,
+    Label,
+    Rarity,
+    Reversed,
+    FoundAfterAttempts,
+    FoundAfterSecs,
+}
+
+impl MatchField {
+    /// Name of the field in the results file
+    pub fn name(self) -> &'static str {
+        match self {
+            MatchField::Target => "target",
+            MatchField::Public => "public",
+            MatchField::Mnemonic => "mnemonic",
+            MatchField::Placement => "placement",
+            MatchField::Seed => "seed",
+            MatchField::Label => "label",
+            MatchField::Rarity => "rarity",
+            MatchField::Reversed => "reversed",
+            MatchField::FoundAfterAttempts => "found_after_attempts",
+            MatchField::FoundAfterSecs => "found_after_secs",
+        }
+    }
+
+    /// Json encoded value of the field of a match
+    fn value(self, m: &AddressMatch) -> serde_json::Result<String> {
+        match self {
+            MatchField::Target => serde_json::to_string(&m.target),
+            MatchField::Public => serde_json::to_string(&m.public),
+            MatchField::Mnemonic => serde_json::to_string(&m.mnemonic),
+            MatchField::Placement => serde_json::to_string(&m.placement),
+            MatchField::Seed => serde_json::to_string(&m.seed),
+            MatchField::Label => serde_json::to_string(&m.label),
+            MatchField::Rarity => serde_json::to_string(&m.rarity),
+            MatchField::Reversed => serde_json::to_string(&m.reversed),
+            MatchField::FoundAfterAttempts => serde_json::to_string(&m.found_after_attempts),
+            MatchField::FoundAfterSecs => serde_json::to_string(&m.found_after_secs),
+        }
+    }
+}
+
+/// Encoding of the matches in a results file
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Serialize,clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResultsFormat {
+    /// A pretty-printed json array of matches
+    Json,
+    /// One json object per match and line
+    JsonLines,
+    /// Only the address of each match, one per line, such as for watch-only monitoring.
+    /// Address lists can not be loaded as matches again.
+    Addresses,
+    /// Accounts to import into a wallet, see [`WalletFile`]. Wallet files can not be loaded as
+    /// matches again.
+    Wallet,
+}
+
+impl ResultsFormat {
+    /// Format implied by the extension of the path, if it is a known one
+    ///
+    /// ```
+    /// use algo_vanity_rs::format::ResultsFormat;
+    ///
+    /// assert_eq!(ResultsFormat::from_extension("vanities.json"), Some(ResultsFormat::Json));
+    /// assert_eq!(ResultsFormat::from_extension("vanities.jsonl"), Some(ResultsFormat::JsonLines));
+    /// assert_eq!(ResultsFormat::from_extension("vanities.ndjson"), Some(ResultsFormat::JsonLines));
+    /// assert_eq!(ResultsFormat::from_extension("vanities"), None);
+    /// ```
+    pub fn from_extension(path: &str) -> Option<Self> {
+        match Path::new(path).extension()?.to_str()? {
+            "json" => Some(ResultsFormat::Json),
+            "jsonl" | "ndjson" => Some(ResultsFormat::JsonLines),
+            _ => None,
+        }
+    }
+
+    /// Format recognized from the first bytes of a file. Empty files have no format.
+    ///
+    /// ```
+    /// use algo_vanity_rs::format::ResultsFormat;
+    ///
+    /// assert_eq!(ResultsFormat::from_contents(b"  [\n  {\"target\": \"A\"}]").unwrap(), Some(ResultsFormat::Json));
+    /// assert_eq!(ResultsFormat::from_contents(b"{\"target\": \"A\"}\n").unwrap(), Some(ResultsFormat::JsonLines));
+    /// assert_eq!(ResultsFormat::from_contents(b"\n").unwrap(), None);
+    /// assert!(ResultsFormat::from_contents(&[0x1f, 0x8b, 0x08]).is_err());
+    /// assert!(ResultsFormat::from_contents(b"target,public").is_err());
+    /// ```
+    pub fn from_contents(contents: &[u8]) -> io::Result<Option<Self>> {
+        if contents.starts_with(&GZIP_MAGIC) {
+            return Err(unsupported("gzip compressed results files are not supported"));
+        }
+        match contents.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'[') => Ok(Some(ResultsFormat::Json)),
+            Some(b'{') => Ok(Some(ResultsFormat::JsonLines)),
+            Some(_) => Err(unsupported("unrecognized results file format")),
+            None => Ok(None),
+        }
+    }
+
+    /// Format of an existing file, from its contents or else its extension, defaulting to json
+    pub fn detect(path: &str, contents: &[u8]) -> io::Result<Self> {
+        Ok(Self::from_contents(contents)?
+            .or_else(|| Self::from_extension(path))
+            .unwrap_or(ResultsFormat::Json))
+    }
+
+    /// Decode the matches of a file in this format
+    pub fn parse(self, contents: &[u8]) -> io::Result<Vec<AddressMatch>> {
+        match self {
+            ResultsFormat::Addresses => Err(unsupported("address lists can not be loaded as matches")),
+            ResultsFormat::Wallet => Err(unsupported("wallet files can not be loaded as matches")),
+            ResultsFormat::Json if contents.trim_ascii().is_empty() => Ok(Vec::new()),
+            ResultsFormat::Json => Ok(serde_json::from_slice(contents)?),
+            ResultsFormat::JsonLines => serde_json::Deserializer::from_slice(contents)
+                .into_iter()
+                .map(|m| m.map_err(io::Error::from))
+                .collect(),
+        }
+    }
+
+    /// Encode only the given fields of the matches in this format, in the given order. Without
+    /// fields every field is encoded. Records missing fields can not be decoded again.
+    ///
+    /// ```
+    /// use algo_vanity_rs::{AddressMatch, Placement, format::{MatchField, ResultsFormat}};
+    ///
+    /// let m = AddressMatch::new("ALGO", "ALGOXYZ", "words", Placement::Start);
+    /// let fields = [MatchField::Public, MatchField::Placement, MatchField::Target];
+    /// assert_eq!(
+    ///     ResultsFormat::JsonLines.serialize_fields(&[m.clone()], &fields).unwrap(),
+    ///     "{\"public\":\"ALGOXYZ\",\"placement\":\"Start\",\"target\":\"ALGO\"}\n",
+    /// );
+    /// assert_eq!(
+    ///     ResultsFormat::Json.serialize_fields(&[m], &[MatchField::Label]).unwrap(),
+    ///     "[\n  {\"label\":null}\n]",
+    /// );
+    /// ```
+    pub fn serialize_fields(self, matches: &[AddressMatch], fields: &[MatchField]) -> io::Result<String> {
+        if fields.is_empty() || matches!(self, ResultsFormat::Addresses | ResultsFormat::Wallet) { return self.serialize(matches) }
+
+        let records = matches.iter()
+            .map(|m| {
+                let pairs = fields.iter()
+                    .map(|field| Ok(format!("{}:{}", serde_json::to_string(field.name())?, field.value(m)?)))
+                    .collect::<serde_json::Result<Vec<_>>>()?;
+                Ok(format!("{{{}}}", pairs.join(",")))
+            })
+            .collect::<serde_json::Result<Vec<_>>>()?;
+
+        Ok(match self {
+            ResultsFormat::Json if records.is_empty() => String::from("[]"),
+            ResultsFormat::Json => format!("[\n  {}\n]", records.join(",\n  ")),
+            ResultsFormat::JsonLines | ResultsFormat::Addresses | ResultsFormat::Wallet => records.iter().map(|r| format!("{r}\n")).collect(),
+        })
+    }
+
+    /// Encode the matches in this format
+    ///
+    /// ```
+    /// use algo_vanity_rs::{AddressMatch, Placement, format::ResultsFormat};
+    ///
+    /// let matches = [
+    ///     AddressMatch::new("ALGO", "ALGOXYZ", "words", Placement::Start),
+    ///     AddressMatch::new("XYZ", "RANDXYZ", "words", Placement::End),
+    /// ];
+    /// assert_eq!(ResultsFormat::Addresses.serialize(&matches).unwrap(), "ALGOXYZ\nRANDXYZ\n");
+    /// ```
+    pub fn serialize(self, matches: &[AddressMatch]) -> io::Result<String> {
+        match self {
+            ResultsFormat::Addresses => Ok(matches.iter().map(|m| format!("{}\n", m.public)).collect()),
+            ResultsFormat::Wallet => Ok(serde_json::to_string_pretty(&WalletFile::from_matches(matches)?)?),
+            ResultsFormat::Json => Ok(serde_json::to_string_pretty(matches)?),
+            ResultsFormat::JsonLines => matches.iter()
+                .map(|m| serde_json::to_string(m).map(|line| line + "\n").map_err(io::Error::from))
+                .collect(),
+        }
+    }
+}
+
+/// Version of the wallet import schema written by [`ResultsFormat::Wallet`]
+pub const WALLET_VERSION: u32 = 1;
+
+/// Wallet import file, listing an account per match under `accounts`
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "accounts": [
+///     {
+///       "name": "ALGO",
+///       "address": "ALGO...",
+///       "mnemonic": "25 words ...",
+///       "private_key": "base64 ..."
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+pub struct WalletFile {
+    pub version: u32,
+    pub accounts: Vec<WalletAccount>,
+}
+
+/// Account of a wallet import file, carrying both forms of the secret which wallets and the
+/// Algorand SDKs import accounts from
+#[derive(Clone,Debug,PartialEq,Serialize,Deserialize)]
+pub struct WalletAccount {
+    /// Name to show for the account, the label of its pattern or else the pattern itself
+    pub name: String,
+    pub address: String,
+    /// 25 word mnemonic, the backup phrase wallets import accounts from
+    pub mnemonic: String,
+    /// Base64 encoded 64 byte ed25519 secret key, which is the 32 byte seed followed by the
+    /// 32 byte public key, as the Algorand SDKs encode private keys
+    pub private_key: String,
+}
+
+impl WalletFile {
+    /// Wallet import file of the matches, which all need their mnemonic
+    pub fn from_matches(matches: &[AddressMatch]) -> io::Result<Self> {
+        let accounts = matches.iter().map(WalletAccount::from_match).collect::<io::Result<_>>()?;
+        Ok(WalletFile { version: WALLET_VERSION, accounts })
+    }
+}
+
+impl WalletAccount {
+    /// Wallet account of a match, deriving its private key from its mnemonic
+    ///
+    /// ```
+    /// use algo_vanity_rs::{AddressMatch, Placement, format::WalletAccount};
+    ///
+    /// let acc = algo_vanity_rs::accounts().next().unwrap();
+    /// let public = acc.address().encode_string();
+    /// let m = AddressMatch::new(&public[..2], public.clone(), acc.mnemonic(), Placement::Start);
+    ///
+    /// let wallet = WalletAccount::from_match(&m).unwrap();
+    /// assert_eq!(wallet.name, &public[..2]);
+    /// assert_eq!(wallet.account().unwrap().address().encode_string(), public);
+    /// assert!(WalletAccount::from_match(&AddressMatch::new("A", public, "", Placement::Start)).is_err());
+    /// ```
+    pub fn from_match(m: &AddressMatch) -> io::Result<Self> {
+        if m.mnemonic.is_empty() {
+            return Err(unsupported("wallet files need the mnemonic of every match"));
+        }
+        let acc = Account::from_mnemonic(&m.mnemonic)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("invalid mnemonic of {}: {e}", m.public)))?;
+        let mut private_key = acc.seed().to_vec();
+        private_key.extend_from_slice(&acc.address().0);
+
+        Ok(WalletAccount {
+            name: m.label.clone().unwrap_or_else(|| m.target.clone()),
+            address: m.public.clone(),
+            mnemonic: m.mnemonic.clone(),
+            private_key: BASE64.encode(&private_key),
+        })
+    }
+
+    /// Account imported from this entry, as a wallet would, checking that its mnemonic, private
+    /// key and address all belong to the same account
+    pub fn account(&self) -> io::Result<Account> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, format!("{message} of {}", self.address));
+
+        let acc = Account::from_mnemonic(&self.mnemonic).map_err(|_| invalid("invalid mnemonic"))?;
+        let private_key = BASE64.decode(self.private_key.as_bytes()).map_err(|_| invalid("invalid private key"))?;
+        let (seed, public_key) = private_key.split_at_checked(32).filter(|(_, pk)| pk.len() == 32).ok_or_else(|| invalid("invalid private key length"))?;
+
+        if seed != acc.seed() || public_key != acc.address().0 {
+            return Err(invalid("private key does not match mnemonic"));
+        }
+        if acc.address().encode_string() != self.address {
+            return Err(invalid("mnemonic derives another address"));
+        }
+        Ok(acc)
+    }
+}
+
+/// Write matches to a new results file in the format implied by its extension, defaulting
+/// to json. Existing files are never overwritten.
+///
+/// ```
+/// use algo_vanity_rs::{AddressMatch, Placement, format::{read_matches, write_new_matches}};
+///
+/// let m = AddressMatch::new("ALGO", "ALGOXYZ", "words", Placement::Start);
+/// let path = std::env::temp_dir().join(format!("algo-vanity-export-{}.json", std::process::id()));
+/// let path = path.to_str().unwrap();
+///
+/// write_new_matches(path, &[m]).unwrap();
+/// assert!(write_new_matches(path, &[]).is_err());
+/// let (read, _) = read_matches(path).unwrap();
+/// std::fs::remove_file(path).unwrap();
+///
+/// assert_eq!(read.len(), 1);
+/// assert_eq!((read[0].public.as_str(), read[0].mnemonic.as_str()), ("ALGOXYZ", "words"));
+/// ```
+pub fn write_new_matches(path: &str, matches: &[AddressMatch]) -> io::Result<()> {
+    let format = ResultsFormat::from_extension(path).unwrap_or(ResultsFormat::Json);
+    let mut file = fs::OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(format.serialize(matches)?.as_bytes())
+}
+
+/// Read the matches of a results file in whichever format it was written
+pub fn read_matches(path: &str) -> io::Result<(Vec<AddressMatch>, ResultsFormat)> {
+    let contents = fs::read(path)?;
+    let format = ResultsFormat::detect(path, &contents)?;
+    Ok((format.parse(&contents)?, format))
+}
+
+fn unsupported(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
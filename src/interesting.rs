@@ -0,0 +1,79 @@
+//! Rules which mark especially interesting matches
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::{AddressMatch, difficulty};
+
+/// A simple rule for spotting bonus vanity in a match.
+///
+/// ```
+/// use algo_vanity_rs::{AddressMatch, Placement, interesting::InterestingRule};
+///
+/// let m = AddressMatch::new("ALGO", "ALGOXQQQQZRAND", "", Placement::Start);
+///
+/// let rule = |s: &str| s.parse::<InterestingRule>().unwrap();
+/// assert!(rule("repeat:4").matches(&m));
+/// assert!(!rule("repeat:5").matches(&m));
+/// assert!(rule("contains:rand").matches(&m));
+/// assert!(!rule("contains:algo").matches(&m));
+/// assert!(rule("quality:4").matches(&m));
+/// assert!("sparkles:3".parse::<InterestingRule>().is_err());
+/// ```
+#[derive(Clone,Debug,Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterestingRule {
+    /// At least this many consecutive identical characters outside the matched pattern
+    Repeat(usize),
+    /// The address contains this string outside the matched pattern
+    Contains(String),
+    /// The match has at least this [`difficulty::quality`]
+    Quality(f64),
+}
+
+impl InterestingRule {
+    /// Whether the match satisfies the rule
+    pub fn matches(&self, m: &AddressMatch) -> bool {
+        let (before, after) = outside_match(m);
+        match self {
+            InterestingRule::Repeat(count) => longest_run(before) >= *count || longest_run(after) >= *count,
+            InterestingRule::Contains(text) => before.contains(text.as_str()) || after.contains(text.as_str()),
+            InterestingRule::Quality(min) => difficulty::quality(m) >= *min,
+        }
+    }
+}
+
+impl FromStr for InterestingRule {
+    type Err = String;
+
+    /// Parse `repeat:N`, `contains:TEXT` or `quality:Q`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = s.split_once(':').ok_or(format!("expected RULE:VALUE, got '{s}'"))?;
+        match kind {
+            "repeat" => value.parse().map(InterestingRule::Repeat).map_err(|_| format!("invalid repeat count '{value}'")),
+            "contains" => Ok(InterestingRule::Contains(value.to_uppercase())),
+            "quality" => value.parse().map(InterestingRule::Quality).map_err(|_| format!("invalid quality '{value}'")),
+            _ => Err(format!("unknown rule '{kind}', expected repeat, contains or quality")),
+        }
+    }
+}
+
+/// Parts of the address before and after the matched pattern
+fn outside_match(m: &AddressMatch) -> (&str, &str) {
+    let matched = m.matched_range();
+    (m.public.get(..matched.start).unwrap_or(""), m.public.get(matched.end..).unwrap_or(""))
+}
+
+/// Length of the longest run of identical characters
+fn longest_run(s: &str) -> usize {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous = None;
+    for c in s.chars() {
+        run = if previous == Some(c) { run + 1 } else { 1 };
+        longest = longest.max(run);
+        previous = Some(c);
+    }
+    longest
+}
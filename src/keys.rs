@@ -0,0 +1,53 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use algo_rust_sdk::account::Account;
+
+/// A scheme for deriving an account (and thereby its address) from a 32 byte seed
+pub trait KeyScheme {
+    fn account_from_seed(seed: [u8; 32]) -> Account;
+}
+
+/// Standard Algorand ed25519 single-signature accounts
+pub struct Ed25519;
+
+impl KeyScheme for Ed25519 {
+    fn account_from_seed(seed: [u8; 32]) -> Account {
+        Account::from_seed(seed)
+    }
+}
+
+/// Key types which can be selected from the command line
+#[derive(Clone,Copy,Debug,Default,ValueEnum,Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyType {
+    #[default]
+    Ed25519,
+}
+
+impl KeyType {
+    /// Generate an account from the seed using the scheme of this key type
+    pub fn account_from_seed(self, seed: [u8; 32]) -> Account {
+        match self {
+            KeyType::Ed25519 => Ed25519::account_from_seed(seed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use algo_rust_sdk::Address;
+    use super::*;
+
+    #[test]
+    fn every_key_type_generates_valid_addresses() {
+        for key_type in KeyType::value_variants() {
+            for seed in [[0; 32], [7; 32], [255; 32]] {
+                let account = key_type.account_from_seed(seed);
+                let address = account.address().encode_string();
+                let parsed = Address::from_string(&address).unwrap_or_else(|e| panic!("{key_type:?}: {address}: {e}"));
+                assert_eq!(parsed.encode_string(), address, "{key_type:?}");
+                assert_eq!(account.seed(), seed, "{key_type:?}");
+            }
+        }
+    }
+}
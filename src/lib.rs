@@ -0,0 +1,1846 @@
+//! Multi-threaded search for Algorand vanity addresses
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::{File, OpenOptions},
+    io::{Write, self},
+    fmt::Display,
+    time::{Instant, Duration},
+    sync::{Arc,mpsc,atomic::{AtomicBool, AtomicUsize, Ordering}, Mutex},
+};
+
+use rand::{Rng,SeedableRng,rngs::StdRng,thread_rng};
+use serde::{Serialize,Deserialize};
+use algo_rust_sdk::account::Account;
+use zeroize::Zeroize;
+
+pub mod keys;
+pub mod benchmark;
+pub mod difficulty;
+pub mod format;
+pub mod interesting;
+pub mod locale;
+pub mod patterns;
+pub mod region;
+pub mod regex_pattern;
+pub mod checksum;
+pub mod ordering;
+pub mod window;
+pub mod scaling;
+pub mod stats;
+pub mod throttle;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "script")]
+pub mod script;
+mod accounts;
+mod searcher;
+#[cfg(unix)]
+mod socket;
+
+pub use accounts::{accounts, read_entropy, Accounts, Partition, SeedPool, ENTROPY_PER_THREAD};
+pub use searcher::{SearchConfig, SearchHandle, VanitySearcher, find_one_vanity};
+
+use format::{MatchField, ResultsFormat};
+use patterns::{PatternEntry, PatternFormat};
+use region::RegionConstraint;
+use checksum::ChecksumConstraint;
+use ordering::OrderConstraint;
+use regex_pattern::RegexPattern;
+use window::PatternWindow;
+use scaling::ThreadScaler;
+use stats::RateAggregator;
+use keys::KeyType;
+
+/// Number of worker threads active at first when scaling them automatically
+pub const AUTO_THREADS_START: usize = 2;
+
+/// Minimum relative rate gain for automatic scaling to keep adding threads
+pub const AUTO_THREADS_MIN_GAIN: f32 = 0.05;
+
+/// Time to measure the rate over before deciding whether to add another thread
+const AUTO_THREADS_WINDOW: Duration = Duration::from_secs(3);
+
+/// Default time the search rate may stay below `--min-rate` before the search aborts
+pub const DEFAULT_MIN_RATE_GRACE: Duration = Duration::from_secs(60);
+
+/// Default interval between updates of the `--status-file`
+pub const DEFAULT_STATUS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Idle worker threads check this often whether they have been activated
+const IDLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time an active worker thread may go without reporting a batch before it is considered stuck
+/// and replaced by a fresh one
+pub(crate) const WORKER_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Number of per-thread account checks between notifying main thread
+pub const COUNT_PER_LOOP: usize = 100;
+
+/// Default file path to save vanity addresses to
+pub const DEFAULT_PATH: &str = "vanities.json";
+
+/// Default number of threads if auto detect fails
+pub const DEFAULT_THREADS: usize = 4;
+
+/// Characters of the base32 alphabet addresses are encoded with
+pub const ADDRESS_CHARS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Message types worker threads send back to the main thread loop
+pub(crate) enum WorkerMsg {
+    AddressMatch(AddressMatch),
+    /// Lighter match of only the target, address, placement and text a regex matched, without any secrets
+    Address((String,String,Placement,Option<String>)),
+    /// Address of an account matching several different patterns, after its matches
+    DoubleVanity(String),
+    /// Thread id, number of accounts in the batch and how long it took
+    Count((usize,usize,Duration))
+}
+
+/// Struct for when an address has matched a vanity string. New fields may be added in
+/// future versions, so construct it with [`AddressMatch::new`] outside of this crate.
+///
+/// ```
+/// use algo_vanity_rs::{AddressMatch, Placement};
+///
+/// let m = AddressMatch::new("ALGO", "ALGOXYZ", "secret words", Placement::Start).with_label("algo");
+/// assert_eq!(m.target(), "ALGO");
+/// assert_eq!(m.public(), "ALGOXYZ");
+/// assert_eq!(m.mnemonic(), Some("secret words"));
+/// assert_eq!(m.placement(), Placement::Start);
+/// assert_eq!(m.seed(), None);
+/// assert_eq!(m.label(), Some("algo"));
+/// ```
+#[derive(Serialize,Deserialize,Clone)]
+#[non_exhaustive]
+pub struct AddressMatch {
+    pub target : String,
+    pub public : String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub mnemonic : String,
+    pub placement : Placement,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed : Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label : Option<String>,
+    /// Rarity score in bits, see [`difficulty::rarity_bits`]. Missing from matches saved by
+    /// earlier versions, for which it is computed when needed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rarity : Option<f64>,
+    /// Whether the target was matched against the reversed address, such that the address
+    /// holds the target reversed at `placement`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reversed : bool,
+    /// Attempts of the run when the match was found, if recorded. Workers report their attempts
+    /// in whole batches, so this falls short by up to a batch per thread.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub found_after_attempts : Option<usize>,
+    /// Time from the start of the run until the match was found, if recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub found_after_secs : Option<f64>,
+    /// Characters of the address a regex target matched, as these differ from the target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched : Option<String>,
+}
+
+impl AddressMatch {
+    /// Match of `target` in the `public` address, with an empty `mnemonic` if it was not kept
+    pub fn new(target: impl Into<String>, public: impl Into<String>, mnemonic: impl Into<String>, placement: Placement) -> Self {
+        AddressMatch { target: target.into(), public: public.into(), mnemonic: mnemonic.into(), placement, seed: None, label: None, rarity: None, reversed: false, found_after_attempts: None, found_after_secs: None, matched: None }
+    }
+
+    /// Mark the target as matched against the reversed address
+    pub fn with_reversed(mut self) -> Self {
+        self.reversed = true;
+        self
+    }
+
+    /// Record the characters of the address a regex target matched
+    pub fn with_matched(mut self, matched: impl Into<String>) -> Self {
+        self.matched = Some(matched.into());
+        self
+    }
+
+    /// Record the hex encoded seed of the account
+    pub fn with_seed(mut self, seed: impl Into<String>) -> Self {
+        self.seed = Some(seed.into());
+        self
+    }
+
+    /// Record the label of the pattern
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Pattern which was matched
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Encoded address of the account
+    pub fn public(&self) -> &str {
+        &self.public
+    }
+
+    /// Mnemonic of the account, unless it was not kept
+    pub fn mnemonic(&self) -> Option<&str> {
+        Some(self.mnemonic.as_str()).filter(|m| !m.is_empty())
+    }
+
+    /// Where in the address the pattern was matched
+    pub fn placement(&self) -> Placement {
+        self.placement
+    }
+
+    /// Hex encoded seed of the account, if recorded
+    pub fn seed(&self) -> Option<&str> {
+        self.seed.as_deref()
+    }
+
+    /// Label of the pattern, if any
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Rarity score of the match in bits, as recorded or else computed from the match
+    ///
+    /// ```
+    /// use algo_vanity_rs::{AddressMatch, Placement};
+    ///
+    /// let m = AddressMatch::new("ALGO", "ALGOXYZ", "", Placement::Start);
+    /// assert_eq!(m.rarity(), 20.0);
+    /// ```
+    pub fn rarity(&self) -> f64 {
+        self.rarity.unwrap_or_else(|| difficulty::rarity_bits(self))
+    }
+
+    /// Characters of the address holding the match, which is the target itself unless it was
+    /// matched against the reversed address, or is a regex
+    ///
+    /// ```
+    /// use algo_vanity_rs::{AddressMatch, Placement};
+    ///
+    /// assert_eq!(AddressMatch::new("ALGO", "XYZALGO", "", Placement::End).matched_text(), "ALGO");
+    /// assert_eq!(AddressMatch::new("ALGO", "XYZOGLA", "", Placement::End).with_reversed().matched_text(), "OGLA");
+    /// assert_eq!(AddressMatch::new("Z.G", "XYZAG", "", Placement::End).with_matched("ZAG").matched_text(), "ZAG");
+    /// ```
+    pub fn matched_text(&self) -> String {
+        if let Some(matched) = &self.matched {
+            return matched.clone();
+        }
+        match self.reversed {
+            true => self.target.chars().rev().collect(),
+            false => self.target.clone(),
+        }
+    }
+
+    /// Range of the characters of the address holding the match, such as for highlighting it.
+    /// Placements are always recorded for the address as is, also for reversed matches.
+    ///
+    /// ```
+    /// use algo_vanity_rs::{AddressMatch, Placement};
+    ///
+    /// assert_eq!(AddressMatch::new("ALGO", "ALGOXYZ", "", Placement::Start).matched_range(), 0..4);
+    /// assert_eq!(AddressMatch::new("ALGO", "XALGOYZ", "", Placement::Anywhere(1)).matched_range(), 1..5);
+    /// assert_eq!(AddressMatch::new("ALGO", "XYZOGLA", "", Placement::End).with_reversed().matched_range(), 3..7);
+    /// ```
+    pub fn matched_range(&self) -> std::ops::Range<usize> {
+        let len = self.matched_text().len();
+        let start = match self.placement {
+            Placement::Start | Placement::Near(_) => 0,
+            Placement::Anywhere(index) | Placement::Window { index, .. } => index,
+            Placement::End => self.public.len().saturating_sub(len),
+        };
+        start..(start + len).min(self.public.len())
+    }
+
+    /// Overwrite the mnemonic and seed with zeros in place before dropping them, such that they
+    /// do not linger in freed memory
+    ///
+    /// ```
+    /// use algo_vanity_rs::{AddressMatch, Placement};
+    ///
+    /// let mut m = AddressMatch::new("ALGO", "ALGOXYZ", "abandon ability", Placement::Start).with_seed("00ff");
+    /// m.wipe_secrets();
+    /// assert!(m.mnemonic.is_empty() && m.seed.is_none());
+    /// assert_eq!(m.public, "ALGOXYZ");
+    /// ```
+    pub fn wipe_secrets(&mut self) {
+        self.mnemonic.zeroize();
+        self.seed.zeroize();
+    }
+
+    /// One-line description of the match which does not reveal the mnemonic
+    pub fn summary(&self) -> String {
+        let reversed = if self.reversed { ", reversed" } else { "" };
+        match &self.label {
+            Some(label) => format!("{} \"{label}\" {} ({}{reversed})", self.target, self.public, self.placement),
+            None => format!("{} {} ({}{reversed})", self.target, self.public, self.placement),
+        }
+    }
+}
+
+/// Placement of matched string pattern
+#[derive(Serialize,Deserialize,Clone,Copy,Debug,PartialEq,Eq)]
+pub enum Placement {
+    Start,
+    Anywhere(usize),
+    End,
+    /// Start of the address within this Hamming distance of the target
+    Near(usize),
+    /// At `index`, within the window of characters `start..end` the target was searched in
+    Window { start: usize, end: usize, index: usize },
+}
+
+impl Placement {
+    /// Whether the address contains the target at this placement
+    pub fn matches(&self, address: &str, target: &str) -> bool {
+        match self {
+            Placement::Start => address.starts_with(target),
+            Placement::Anywhere(index) => address.get(*index..).is_some_and(|rest| rest.starts_with(target)),
+            Placement::End => address.ends_with(target),
+            Placement::Near(distance) => address.get(..target.len())
+                .and_then(|prefix| hamming_distance(prefix, target)) == Some(*distance),
+            Placement::Window { start, end, index } => index + target.len() <= *end && index >= start
+                && address.get(*index..).is_some_and(|rest| rest.starts_with(target)),
+        }
+    }
+}
+
+impl Display for Placement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Placement::Start => write!(f,"start"),
+            Placement::Anywhere(index) => write!(f,"anywhere at {index}"),
+            Placement::End => write!(f,"end"),
+            Placement::Near(distance) => write!(f,"near at distance {distance}"),
+            Placement::Window { start, end, index } => write!(f,"window {start}:{end} at {index}"),
+        }
+    }
+}
+
+/// Reasons a saved match can fail verification
+#[derive(Debug)]
+pub enum VerifyError {
+    /// No mnemonic was saved with the match
+    MissingMnemonic,
+    /// The mnemonic is not a valid account mnemonic
+    InvalidMnemonic(String),
+    /// The mnemonic derives a different address than the one recorded
+    AddressMismatch(String),
+    /// The recorded seed does not belong to the mnemonic
+    SeedMismatch,
+    /// The address does not contain the target at the recorded placement
+    PatternMismatch,
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyError::MissingMnemonic => write!(f,"no mnemonic was saved"),
+            VerifyError::InvalidMnemonic(e) => write!(f,"invalid mnemonic ({e})"),
+            VerifyError::AddressMismatch(derived) => write!(f,"mnemonic derives address {derived}"),
+            VerifyError::SeedMismatch => write!(f,"recorded seed does not match mnemonic"),
+            VerifyError::PatternMismatch => write!(f,"address does not contain target at recorded placement"),
+        }
+    }
+}
+
+/// Re-derive the account of a saved match and check that it matches the recorded
+/// address, seed, target and placement.
+///
+/// ```
+/// use algo_vanity_rs::{AddressMatch, Placement, verify_match};
+///
+/// let acc = algo_vanity_rs::accounts().next().unwrap();
+/// let public = acc.address().encode_string();
+/// let mut record = AddressMatch::new(&public[..3], public.clone(), acc.mnemonic(), Placement::Start);
+/// assert!(verify_match(&record).is_ok());
+///
+/// record.public.replace_range(..1, if public.starts_with('A') { "B" } else { "A" });
+/// assert!(verify_match(&record).is_err());
+/// ```
+pub fn verify_match(address_match: &AddressMatch) -> Result<(), VerifyError> {
+    if address_match.mnemonic.is_empty() {
+        return Err(VerifyError::MissingMnemonic);
+    }
+    let acc = Account::from_mnemonic(&address_match.mnemonic)
+        .map_err(|e| VerifyError::InvalidMnemonic(e.to_string()))?;
+
+    let derived = acc.address().encode_string();
+    if derived != address_match.public {
+        return Err(VerifyError::AddressMismatch(derived));
+    }
+    if address_match.seed.as_ref().is_some_and(|seed| *seed != seed_to_hex(&acc.seed())) {
+        return Err(VerifyError::SeedMismatch);
+    }
+    if !address_match.placement.matches(&derived, &address_match.matched_text()) {
+        return Err(VerifyError::PatternMismatch);
+    }
+    Ok(())
+}
+
+/// State of a running search, shared between all threads
+pub struct GlobalState {
+    pub vanities: Vec<String>,
+    pub labels: HashMap<String, String>,
+    pub threads: usize,
+    pub active_threads: usize,
+    pub placement: SearchPlacement,
+    pub matches: Vec<AddressMatch>,
+    pub search_rate: f32,
+    /// Whether the search rate is still warming up, following samples without filtering
+    pub warming_up: bool,
+    pub total_count: usize,
+    pub match_count: usize,
+    /// Number of times a worker thread panicked or got stuck, and was restarted
+    pub worker_restarts: usize,
+    /// Matches found, but not saved to disk yet
+    pub unsaved: usize,
+    pub start_time: Instant,
+    pub run_time: Duration,
+    pub save_path: String,
+    pub run_label: Option<String>,
+    pub save_error: Option<String>,
+    /// Number of anywhere matches found at each offset in the address
+    pub anywhere_offsets: Vec<usize>,
+    /// Longest [`matched_prefix`] of any match of each pattern so far
+    pub best_prefix: HashMap<String, usize>,
+    /// Expected attempts to find each pattern, see [`difficulty::DifficultyModel::pattern_estimates`].
+    /// Regex patterns have no estimate.
+    pub expected_attempts: HashMap<String, f64>,
+    /// Indices into `matches` of the matches which extended the best prefix of their pattern,
+    /// as milestones of a long search
+    pub milestones: Vec<usize>,
+    /// Addresses matching several different patterns, see [`is_double_vanity`]
+    pub double_vanities: HashSet<String>,
+    /// How the search completed by itself, if it did rather than being stopped
+    pub completion: Option<Completion>,
+    /// Why the search aborted itself, such as its rate dropping below the floor
+    pub aborted: Option<String>,
+    /// Patterns retired after being found enough times with `once`, in the order they were
+    pub pattern_completions: Vec<PatternCompletion>,
+    /// Names of the threads of the search which have finished, in the order they did
+    pub terminated_threads: Vec<String>,
+}
+
+/// Event of a `once` pattern being found enough times and retired from the search
+#[derive(Clone,Debug,PartialEq,Serialize)]
+pub struct PatternCompletion {
+    pub pattern: String,
+    /// Address of the match which completed the pattern
+    pub address: String,
+    /// Local time the pattern was completed at, in RFC 3339 format
+    pub timestamp: String,
+    /// Time from the start of this run until the pattern was completed
+    pub time_to_find_secs: f64,
+    /// Attempts of this run until the pattern was completed. Workers report their attempts
+    /// in whole batches, so this falls short by up to a batch per thread.
+    pub attempts_to_find: usize,
+}
+
+/// Events written as json lines to the events file of a search
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum SearchEvent<'a> {
+    PatternCompleted(&'a PatternCompletion),
+}
+
+/// Ways a search completes by itself, as opposed to being interrupted
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Completion {
+    /// Every `once` pattern was found
+    AllFound,
+    /// The attempt budget was used up
+    AttemptBudget,
+    /// The time limit passed
+    TimeLimit,
+}
+
+/// Stop the search as completed, unless it was already stopped some other way
+fn complete(state: &mut GlobalState, keep_alive: &AtomicBool, completion: Completion) {
+    if keep_alive.swap(false, Ordering::Relaxed) {
+        state.completion = Some(completion);
+    }
+}
+
+/// Settings shared by all worker threads
+#[derive(Clone,Debug)]
+pub(crate) struct WorkerOptions {
+    pub(crate) placement: SearchPlacement,
+    pub(crate) key_type: KeyType,
+    pub(crate) seed: Option<u64>,
+    pub(crate) record_seed: bool,
+    pub(crate) partition: Partition,
+    pub(crate) entropy: Option<Vec<[u8; ENTROPY_PER_THREAD]>>,
+    pub(crate) one_per_account: bool,
+    pub(crate) no_secrets: bool,
+    pub(crate) address_only: bool,
+    pub(crate) region: Option<RegionConstraint>,
+    pub(crate) checksum: Option<ChecksumConstraint>,
+    pub(crate) order: Option<OrderConstraint>,
+    #[cfg(feature = "script")]
+    pub(crate) script: Option<script::ScriptPredicate>,
+    pub(crate) max_distance: Option<usize>,
+    pub(crate) windows: Vec<PatternWindow>,
+    pub(crate) regexes: Vec<RegexPattern>,
+    pub(crate) reverse: bool,
+    pub(crate) dry_run: bool,
+    pub(crate) seed_pool: Option<SeedPool>,
+    pub(crate) batch_len: usize,
+    pub(crate) active_threads: Arc<AtomicUsize>,
+    pub(crate) fault: Option<Arc<Mutex<Option<WorkerFault>>>>,
+}
+
+/// Fault injected into a single worker thread, to check that the search recovers from it
+#[derive(Clone,Copy,Debug)]
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) enum WorkerFault {
+    /// The worker panics
+    Panic,
+    /// The worker stops reporting until the search stops, as if stuck
+    Stall,
+}
+
+/// Settings of the main loop, deciding when to retire patterns and stop searching
+#[derive(Clone,Debug)]
+pub(crate) struct LoopOptions {
+    pub(crate) find_only_once: bool,
+    pub(crate) candidates: usize,
+    pub(crate) time_limit: Option<Duration>,
+    pub(crate) attempt_limit: Option<usize>,
+    pub(crate) min_quality: Option<f64>,
+    pub(crate) unique_words: bool,
+    pub(crate) rate_floor: Option<RateFloor>,
+    pub(crate) match_rate_cap: Option<f64>,
+    pub(crate) ladder: bool,
+    pub(crate) reverse: bool,
+    pub(crate) record_discovery: bool,
+    pub(crate) scaler: Option<ThreadScaler>,
+    pub(crate) active_threads: Arc<AtomicUsize>,
+    pub(crate) warm_up: Option<Duration>,
+    pub(crate) worker_timeout: Duration,
+}
+
+/// Settings of the file handler thread
+#[derive(Clone,Debug)]
+pub(crate) struct FileOptions {
+    pub(crate) path: String,
+    pub(crate) match_log: Option<String>,
+    pub(crate) shards: Option<usize>,
+    pub(crate) fields: Vec<MatchField>,
+    pub(crate) rotate: Option<Duration>,
+    pub(crate) format: Option<ResultsFormat>,
+    pub(crate) events_file: Option<String>,
+    pub(crate) save_every: Option<usize>,
+    pub(crate) save_interval: Option<Duration>,
+    pub(crate) wipe_secrets: bool,
+}
+
+/// Places to search in addresses
+#[derive(Clone,Debug,Serialize)]
+pub struct SearchPlacement {
+    pub start:bool,
+    pub anywhere:bool,
+    pub end:bool,
+}
+
+/// Named combinations of places to search in addresses
+#[derive(Clone,Copy,Debug,PartialEq,Eq,clap::ValueEnum,Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PlacementMode {
+    Start,
+    Anywhere,
+    End,
+    StartEnd,
+    Any,
+}
+
+/// ```
+/// use algo_vanity_rs::{PlacementMode, SearchPlacement};
+///
+/// let flags = |mode: PlacementMode| {
+///     let p = SearchPlacement::from(mode);
+///     (p.start, p.anywhere, p.end)
+/// };
+/// assert_eq!(flags(PlacementMode::Start), (true, false, false));
+/// assert_eq!(flags(PlacementMode::Anywhere), (false, true, false));
+/// assert_eq!(flags(PlacementMode::End), (false, false, true));
+/// assert_eq!(flags(PlacementMode::StartEnd), (true, false, true));
+/// assert_eq!(flags(PlacementMode::Any), (true, true, true));
+/// ```
+impl From<PlacementMode> for SearchPlacement {
+    fn from(mode: PlacementMode) -> Self {
+        let (start, anywhere, end) = match mode {
+            PlacementMode::Start => (true, false, false),
+            PlacementMode::Anywhere => (false, true, false),
+            PlacementMode::End => (false, false, true),
+            PlacementMode::StartEnd => (true, false, true),
+            PlacementMode::Any => (true, true, true),
+        };
+        SearchPlacement { start, anywhere, end }
+    }
+}
+
+impl Display for SearchPlacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f,"{}",match (self.start,self.anywhere,self.end) {
+            (_, true, _) => "Anywhere",
+            (true, false, true) => "Start and end",
+            (true, false, false) => "Start",
+            (false, false, true) => "End",
+            (false, false, false) => "Nowhere"
+        })
+    }
+}
+
+
+/// Process the messages of worker threads until every worker has stopped. Active workers which
+/// stop reporting batches for `options.worker_timeout` are replaced by calling `respawn` with
+/// their thread id, which is dropped once the search stops.
+pub(crate) fn thread_main_loop(
+    rx_worker_msg: mpsc::Receiver<WorkerMsg>,
+    tx_address_match: mpsc::Sender<AddressMatch>,
+    state: Arc<Mutex<GlobalState>>,
+    options: LoopOptions,
+    keep_alive: Arc<AtomicBool>,
+    respawn: impl FnMut(usize),
+) {
+
+    let threads = state.lock().expect("Unable to lock mutex").threads;
+    let mut rates = RateAggregator::new(threads);
+    let mut scaler = options.scaler.clone();
+    let mut last_scaled = Instant::now();
+    let mut rate_floor = options.rate_floor.clone();
+    let mut match_rate_cap = options.match_rate_cap.map(|rate| MatchRateCap::new(rate, Instant::now()));
+    let mut last_reported = vec![Instant::now(); threads];
+    let mut respawn = Some(respawn);
+    loop {
+
+        // Replacements would keep the search from stopping, as they hold on to their channel
+        if !keep_alive.load(Ordering::Relaxed) {
+            respawn = None;
+        }
+
+        // Replace active workers which stopped reporting, while idle ones are not expected to
+        if let Some(respawn) = respawn.as_mut() {
+            let active = options.active_threads.load(Ordering::Relaxed);
+            for (id, reported) in last_reported.iter_mut().enumerate() {
+                if id >= active {
+                    *reported = Instant::now();
+                } else if reported.elapsed() >= options.worker_timeout {
+                    respawn(id);
+                    if let Ok(mut s) = state.lock() { s.worker_restarts += 1 }
+                    *reported = Instant::now();
+                }
+            }
+        }
+
+        let msg = match rx_worker_msg.recv_timeout(IDLE_INTERVAL) {
+            Ok(msg) => msg,
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+
+        let mut state_mut = state.lock().expect("Unable to lock mutex");
+
+        state_mut.run_time = Instant::now().duration_since(state_mut.start_time);
+
+        // Stop searching once the time limit has passed
+        if options.time_limit.is_some_and(|limit| state_mut.run_time >= limit) {
+            complete(&mut state_mut, &keep_alive, Completion::TimeLimit)
+        }
+
+        let mut address_match = match msg {
+
+            // Address match has been found
+            WorkerMsg::AddressMatch(address_match) => address_match,
+
+            // Account matched several patterns, each of which was reported before
+            WorkerMsg::DoubleVanity(public) => {
+                state_mut.double_vanities.insert(public);
+                continue
+            },
+
+            // Address only match has been found, which is kept without secrets
+            WorkerMsg::Address((target, public, placement, matched)) => {
+                AddressMatch { matched, ..AddressMatch::new(target, public, String::new(), placement) }
+            },
+
+            // Worker thread counting update
+            WorkerMsg::Count((id,count,duration)) => {
+                last_reported[id] = Instant::now();
+                state_mut.total_count += count;
+                state_mut.warming_up = options.warm_up.is_some_and(|warm_up| state_mut.run_time < warm_up);
+                state_mut.search_rate = rates.observe(id, count, duration, state_mut.warming_up);
+
+                // Abort when the rate stays below the floor, except while it is not representative yet
+                if let Some(floor) = rate_floor.as_mut() {
+                    if state_mut.warming_up || scaler.as_ref().is_some_and(|s| !s.is_settled()) {
+                        floor.reset();
+                    } else if floor.observe(state_mut.search_rate, Instant::now()) && keep_alive.swap(false, Ordering::Relaxed) {
+                        state_mut.aborted = Some(format!("Search rate of {:.0} a/s stayed below the floor of {:.0} a/s, aborting", state_mut.search_rate, floor.floor()));
+                    }
+                }
+
+                // Stop searching once the attempt budget has been used up
+                if options.attempt_limit.is_some_and(|limit| state_mut.total_count >= limit) {
+                    complete(&mut state_mut, &keep_alive, Completion::AttemptBudget)
+                }
+
+                // Add threads while they raise the rate enough, measuring each count of threads for a while
+                if let Some(scaler) = scaler.as_mut().filter(|s| !s.is_settled()) {
+                    if last_scaled.elapsed() >= AUTO_THREADS_WINDOW {
+                        let active = scaler.observe(rates.rate_of(scaler.active()));
+                        rates.forget_from(active);
+                        options.active_threads.store(active, Ordering::Relaxed);
+                        state_mut.active_threads = active;
+                        last_scaled = Instant::now();
+                    }
+                }
+                continue
+            },
+        };
+
+        // Workers may still report patterns which were found already, which are neither saved nor counted
+        if (options.find_only_once || options.ladder) && !state_mut.vanities.contains(&address_match.target) {
+            continue
+        }
+
+        if let Placement::Anywhere(index) = address_match.placement {
+            if let Some(count) = state_mut.anywhere_offsets.get_mut(index) { *count += 1 }
+        }
+
+        // Matches below the quality threshold are counted, but not kept
+        if options.min_quality.is_some_and(|min| difficulty::quality(&address_match) < min) {
+            state_mut.match_count += 1;
+            continue
+        }
+
+        // Matches whose mnemonic repeats a word are counted, but not kept
+        if options.unique_words && !has_unique_words(&address_match.mnemonic) {
+            state_mut.match_count += 1;
+            continue
+        }
+
+        // Matches beyond the rate cap are counted, but not kept
+        if match_rate_cap.as_mut().is_some_and(|cap| !cap.allow(Instant::now())) {
+            state_mut.match_count += 1;
+            continue
+        }
+
+        address_match.label = state_mut.labels.get(&address_match.target).cloned();
+        address_match.reversed = options.reverse;
+        address_match.rarity = Some(difficulty::rarity_bits(&address_match));
+        if options.record_discovery {
+            address_match.found_after_attempts = Some(state_mut.total_count);
+            address_match.found_after_secs = Some(state_mut.start_time.elapsed().as_secs_f64());
+        }
+        state_mut.matches.push(address_match.clone());
+
+        // Matches fixing more of their pattern than any before are milestones
+        let prefix = matched_prefix(&address_match);
+        if state_mut.best_prefix.get(&address_match.target).is_none_or(|best| prefix > *best) {
+            state_mut.best_prefix.insert(address_match.target.clone(), prefix);
+            let index = state_mut.matches.len() - 1;
+            state_mut.milestones.push(index);
+        }
+
+        if options.ladder {
+            state_mut.match_count += 1;
+            state_mut.unsaved += 1;
+
+            // Reaching a rung retires it along with every shorter rung below it
+            let target = address_match.target.clone();
+            _ = tx_address_match.send(address_match);
+            state_mut.vanities.retain(|rung| !target.starts_with(rung.as_str()));
+            if state_mut.vanities.is_empty() {
+                complete(&mut state_mut, &keep_alive, Completion::AllFound)
+            }
+        } else if options.find_only_once {
+            if let Some(index) = state_mut.vanities.iter().position(|r| r == &address_match.target)  {
+                state_mut.match_count += 1;
+                state_mut.unsaved += 1;
+
+                // Patterns are done once enough candidates were found, including earlier ones.
+                // Their completion is recorded before sending the match, such that the file
+                // handler sees it once the match is saved.
+                let found = state_mut.matches.iter().filter(|m| m.target == address_match.target).count();
+                if found >= options.candidates {
+                    let completion = PatternCompletion {
+                        pattern: address_match.target.clone(),
+                        address: address_match.public.clone(),
+                        timestamp: chrono::Local::now().to_rfc3339(),
+                        time_to_find_secs: state_mut.start_time.elapsed().as_secs_f64(),
+                        attempts_to_find: state_mut.total_count,
+                    };
+                    state_mut.pattern_completions.push(completion);
+                }
+                _ = tx_address_match.send(address_match);
+                if found >= options.candidates {
+                    let _removed = state_mut.vanities.remove(index);
+                    if state_mut.vanities.is_empty() {
+                        complete(&mut state_mut, &keep_alive, Completion::AllFound)
+                    }
+                }
+            }
+        } else {
+            state_mut.match_count += 1;
+            state_mut.unsaved += 1;
+            _ = tx_address_match.send(address_match);
+        }
+    }
+}
+
+/// Threads to handle saving matches to json file, forwarding saved matches to `tx_streams`
+pub(crate) fn thread_file_handler(
+    rx_address_match: mpsc::Receiver<AddressMatch>,
+    options: FileOptions,
+    tx_streams: Vec<mpsc::Sender<AddressMatch>>,
+    state: Arc<Mutex<GlobalState>>,
+    keep_alive: Arc<AtomicBool>,
+) -> io::Result<()> {
+
+    let FileOptions { path, match_log, shards, fields, rotate, format, events_file, save_every, save_interval, wipe_secrets } = options;
+
+    // Open human-readable match log for appending, if requested
+    let mut match_log = match match_log {
+        Some(log_path) => Some(OpenOptions::new().create(true).append(true).open(log_path)?),
+        None => None,
+    };
+
+    // Likewise the json lines of events
+    let mut events_file = match events_file {
+        Some(events_path) => Some(OpenOptions::new().create(true).append(true).open(events_path)?),
+        None => None,
+    };
+    let mut events_written = 0;
+
+    // Rotated results files are labeled with the time they were started at
+    let period_path = || match rotate {
+        Some(_) => labeled_path(&path, &chrono::Local::now().format("%Y%m%d-%H%M%S").to_string()),
+        None => path.clone(),
+    };
+    let mut period_start = Instant::now();
+    let (mut paths, mut outputs) = open_outputs(&period_path(), shards, format, &state)?;
+
+    let mut backoff = Backoff::new(SAVE_RETRY_INITIAL, SAVE_RETRY_CAP);
+
+    // Matches are saved once enough of them are pending, or the oldest has waited long enough.
+    // Without either limit, every match is saved right away.
+    let save_every = save_every.unwrap_or(if save_interval.is_some() { usize::MAX } else { 1 });
+    let mut pending: Vec<AddressMatch> = Vec::new();
+    let mut pending_since = Instant::now();
+
+    // Receive new address matches, add them to their output and save it to disk
+    loop {
+        let received = match save_interval.filter(|_| !pending.is_empty()) {
+            Some(interval) => rx_address_match.recv_timeout(interval.saturating_sub(pending_since.elapsed())),
+            None => rx_address_match.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        };
+        let disconnected = match received {
+            Ok(message) => {
+                if pending.is_empty() { pending_since = Instant::now() }
+                pending.push(message);
+                pending.extend(rx_address_match.try_iter());
+                false
+            },
+            Err(mpsc::RecvTimeoutError::Timeout) => false,
+            Err(mpsc::RecvTimeoutError::Disconnected) => true,
+        };
+
+        // Pending matches are all saved once the search ends
+        let due = disconnected || pending.len() >= save_every || save_interval.is_some_and(|interval| pending_since.elapsed() >= interval);
+        if pending.is_empty() || !due {
+            if disconnected { break }
+            continue
+        }
+        let mut new_matches = std::mem::take(&mut pending);
+
+        // Start new results files once the rotation interval has passed, leaving the old ones
+        if rotate.is_some_and(|rotate| period_start.elapsed() >= rotate) {
+            period_start = Instant::now();
+            (paths, outputs) = open_outputs(&period_path(), shards, format, &state)?;
+        }
+
+        if let Some(log) = match_log.as_mut() {
+            let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+            for m in &new_matches {
+                writeln!(log, "{timestamp} {}", m.summary())?;
+            }
+            log.flush()?;
+        }
+
+        let mut changed = vec![false; outputs.len()];
+        for m in &new_matches {
+            let output = shards.map_or(0, |shards| shard_of(&m.target, shards));
+            outputs[output].0.push(m.clone());
+            changed[output] = true;
+        }
+
+        // Retry saving with increasing delays while the search is running, such that
+        // a temporarily unavailable disk does not end the search
+        for ((path, (matches, format)), _) in paths.iter().zip(&outputs).zip(&changed).filter(|(_, changed)| **changed) {
+            while let Err(e) = save_matches(path, *format, matches, &fields) {
+                if !keep_alive.load(Ordering::Relaxed) { return Err(e) }
+                let delay = backoff.fail();
+                if let Ok(mut s) = state.lock() {
+                    s.save_error = Some(format!("{e}, retrying in {:.1}s", delay.as_secs_f32()));
+                }
+                std::thread::sleep(delay);
+            }
+        }
+        let recovered = backoff.reset();
+        if let Ok(mut s) = state.lock() {
+            s.unsaved = s.unsaved.saturating_sub(new_matches.len());
+            if recovered { s.save_error = None }
+
+            // Patterns are reported completed only once their match is saved
+            if let Some(events) = events_file.as_mut() {
+                for completion in &s.pattern_completions[events_written..] {
+                    writeln!(events, "{}", serde_json::to_string(&SearchEvent::PatternCompleted(completion))?)?;
+                }
+                events.flush()?;
+                events_written = s.pattern_completions.len();
+            }
+        }
+
+        for tx in &tx_streams {
+            new_matches.iter().for_each(|m| _ = tx.send(m.clone()));
+        }
+        if wipe_secrets { new_matches.iter_mut().for_each(AddressMatch::wipe_secrets) }
+        if disconnected { break }
+    }
+
+    // The matches of the results files are held until the end, to be written along with new ones
+    if wipe_secrets {
+        outputs.iter_mut().flat_map(|(matches, _)| matches).for_each(AddressMatch::wipe_secrets);
+    }
+    Ok(())
+}
+
+/// Matches of a results file along with its format
+type Output = (Vec<AddressMatch>, ResultsFormat);
+
+/// Paths of the results files, which are either the one file at `path` or its shards, along
+/// with the results already in them. Missing files are created in `format`, or else the format
+/// implied by their extension, and the index of shards written.
+fn open_outputs(path: &str, shards: Option<usize>, format: Option<ResultsFormat>, state: &Mutex<GlobalState>) -> io::Result<(Vec<String>, Vec<Output>)> {
+
+    // Matches are either all saved to the results file, or routed to shards by their pattern
+    let paths: Vec<String> = match shards {
+        Some(shards) => (0..shards).map(|shard| shard_path(path, shard)).collect(),
+        None => vec![path.to_string()],
+    };
+    if let Some(shards) = shards {
+        let vanities = state.lock().map(|s| s.vanities.clone()).unwrap_or_default();
+        let index: BTreeMap<_, _> = vanities.iter().map(|v| (v, &paths[shard_of(v, shards)])).collect();
+        std::fs::write(labeled_path(path, "index"), serde_json::to_string_pretty(&index)?)?;
+    }
+
+    // Load existing results in whichever format they were written, or create new files
+    let mut outputs = Vec::new();
+    for path in &paths {
+        outputs.push(match format::read_matches(path) {
+            Ok(loaded) => loaded,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let format = format.or(ResultsFormat::from_extension(path)).unwrap_or(ResultsFormat::Json);
+                let mut file = File::create(path)?;
+                write!(file, "{}", format.serialize(&[])?)?;
+                (Vec::new(), format)
+            },
+            Err(e) => return Err(e),
+        });
+    }
+    Ok((paths, outputs))
+}
+
+/// Shard of the results a pattern is saved to, from a hash of the pattern which is stable
+/// across runs and platforms (64 bit FNV-1a)
+///
+/// ```
+/// use algo_vanity_rs::shard_of;
+///
+/// assert_eq!(shard_of("ALGO", 1), 0);
+/// assert_eq!(shard_of("ALGO", 16), shard_of("ALGO", 16));
+/// assert!((0..100).all(|i| shard_of(&format!("P{i}"), 7) < 7));
+/// ```
+pub fn shard_of(pattern: &str, shards: usize) -> usize {
+    let hash = pattern.bytes().fold(0xcbf29ce484222325u64, |hash, b| (hash ^ b as u64).wrapping_mul(0x100000001b3));
+    (hash % shards.max(1) as u64) as usize
+}
+
+/// Path of a shard of the results file
+///
+/// ```
+/// use algo_vanity_rs::shard_path;
+///
+/// assert_eq!(shard_path("vanities.json", 3), "vanities-shard3.json");
+/// ```
+pub fn shard_path(path: &str, shard: usize) -> String {
+    labeled_path(path, &format!("shard{shard}"))
+}
+
+/// Overwrite the results file with all matches, keeping only the given fields if any
+fn save_matches(path: &str, format: ResultsFormat, matches: &[AddressMatch], fields: &[MatchField]) -> io::Result<()> {
+    let message = format.serialize_fields(matches, fields)?;
+    let mut file = File::create(path)?;
+    write!(file,"{}", message.as_str())
+}
+
+/// Write the marker file of a completed search, for orchestrators to watch for. The marker is
+/// written to a temporary file first and then renamed, so it never appears half written.
+pub(crate) fn write_completion_file(path: &str, state: &GlobalState, completion: Completion) -> io::Result<()> {
+    let marker = serde_json::json!({
+        "status": completion,
+        "matches": state.match_count,
+        "attempts": state.total_count,
+        "run_time_secs": state.run_time.as_secs_f64(),
+        "remaining": state.vanities,
+        "results": state.save_path,
+    });
+    let temporary = format!("{path}.tmp");
+    std::fs::write(&temporary, serde_json::to_string_pretty(&marker)?)?;
+    std::fs::rename(&temporary, path)
+}
+
+/// Delay before retrying to save after the first failure
+const SAVE_RETRY_INITIAL: Duration = Duration::from_millis(100);
+
+/// Longest delay between retries to save
+const SAVE_RETRY_CAP: Duration = Duration::from_secs(30);
+
+/// Exponentially increasing delay between retries, up to a cap
+///
+/// ```
+/// use std::time::Duration;
+/// use algo_vanity_rs::Backoff;
+///
+/// let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(5));
+/// let delays: Vec<_> = (0..5).map(|_| backoff.fail().as_secs()).collect();
+/// assert_eq!(delays, [1, 2, 4, 5, 5]);
+///
+/// assert!(backoff.reset());
+/// assert_eq!(backoff.fail(), Duration::from_secs(1));
+/// ```
+pub struct Backoff {
+    initial: Duration,
+    cap: Duration,
+    current: Option<Duration>,
+}
+
+impl Backoff {
+    /// Start at `initial` delay, doubling after each failure up to `cap`
+    pub fn new(initial: Duration, cap: Duration) -> Self {
+        Backoff { initial, cap, current: None }
+    }
+
+    /// Register a failure, returning the delay before retrying
+    pub fn fail(&mut self) -> Duration {
+        let delay = match self.current {
+            Some(previous) => (previous * 2).min(self.cap),
+            None => self.initial.min(self.cap),
+        };
+        self.current = Some(delay);
+        delay
+    }
+
+    /// Register a success, returning whether there were failures before it
+    pub fn reset(&mut self) -> bool {
+        self.current.take().is_some()
+    }
+}
+
+/// Low-pass filtered search rate. During a warm-up phase the rate follows the samples
+/// without filtering, and the filter restarts from the first sample after it, so the
+/// unrepresentative samples of warming up do not linger in the steady-state rate.
+///
+/// ```
+/// use algo_vanity_rs::RateFilter;
+///
+/// let mut warmed = RateFilter::default();
+/// let mut cold = RateFilter::default();
+/// for _ in 0..20 {
+///     warmed.observe(100.0, true);
+///     cold.observe(100.0, false);
+/// }
+/// assert_eq!(warmed.observe(1000.0, false), 1000.0);
+/// assert!(cold.observe(1000.0, false) < 200.0);
+///
+/// assert_eq!(warmed.observe(2000.0, false), 1050.0);
+/// ```
+#[derive(Default)]
+pub struct RateFilter {
+    rate: f32,
+    restart: bool,
+}
+
+impl RateFilter {
+    /// Register a rate sample, returning the filtered rate
+    pub fn observe(&mut self, sample: f32, warming_up: bool) -> f32 {
+        if warming_up || self.restart {
+            self.restart = warming_up;
+            self.rate = sample;
+        } else {
+            self.rate = self.rate*0.95 + sample*0.05; // LP-filtered rate
+        }
+        self.rate
+    }
+}
+
+/// Watches the filtered search rate for dropping below a floor, such as on a throttled or
+/// preempted cloud instance. Only a rate which stays below the floor for a whole grace
+/// period counts, so short dips do not abort a search.
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use algo_vanity_rs::RateFloor;
+///
+/// let start = Instant::now();
+/// let at = |secs| start + Duration::from_secs(secs);
+/// let mut floor = RateFloor::new(1000.0, Duration::from_secs(60));
+///
+/// assert!(!floor.observe(500.0, at(0)));
+/// assert!(!floor.observe(2000.0, at(30)));
+/// assert!(!floor.observe(500.0, at(40)));
+/// assert!(floor.observe(500.0, at(100)));
+/// ```
+#[derive(Clone,Debug)]
+pub struct RateFloor {
+    floor: f32,
+    grace: Duration,
+    below_since: Option<Instant>,
+}
+
+impl RateFloor {
+    /// Watch for a rate below `floor` for longer than `grace`
+    pub fn new(floor: f32, grace: Duration) -> Self {
+        RateFloor { floor, grace, below_since: None }
+    }
+
+    /// Lowest acceptable rate
+    pub fn floor(&self) -> f32 {
+        self.floor
+    }
+
+    /// Register the filtered rate at `now`, returning whether it has been below the floor
+    /// for the whole grace period
+    pub fn observe(&mut self, rate: f32, now: Instant) -> bool {
+        if rate >= self.floor {
+            self.below_since = None;
+            return false
+        }
+        let since = *self.below_since.get_or_insert(now);
+        now.duration_since(since) >= self.grace
+    }
+
+    /// Forget any time spent below the floor, such as while the rate is not representative
+    pub fn reset(&mut self) {
+        self.below_since = None;
+    }
+}
+
+/// Caps the rate of matches kept by the search, such as for a steady trickle of matches of
+/// easy patterns rather than a flood. Up to one second worth of matches, or at least one, may
+/// be kept in a burst, after which matches are kept at the capped rate.
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use algo_vanity_rs::MatchRateCap;
+///
+/// let start = Instant::now();
+/// let at = |millis| start + Duration::from_millis(millis);
+/// let mut cap = MatchRateCap::new(2.0, start);
+///
+/// assert!(cap.allow(at(0)) && cap.allow(at(0)));
+/// assert!(!cap.allow(at(100)));
+/// assert!(cap.allow(at(600)));
+/// assert!(!cap.allow(at(700)));
+/// ```
+#[derive(Clone,Debug)]
+pub struct MatchRateCap {
+    rate: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl MatchRateCap {
+    /// Keep at most `rate` matches per second, starting at `now` with a full burst
+    pub fn new(rate: f64, now: Instant) -> Self {
+        MatchRateCap { rate, tokens: rate.max(1.0), last: now }
+    }
+
+    /// Whether a match found at `now` may be kept, using up its share of the rate if so
+    pub fn allow(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate.max(1.0));
+        self.last = now.max(self.last);
+        if self.tokens < 1.0 { return false }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+/// Run `work` until it returns, restarting it after each panic while `keep_alive` is set,
+/// such that a worker thread hitting a bug is replaced instead of silently reducing the
+/// search rate. `on_restart` is called before each restart.
+///
+/// ```
+/// use std::sync::atomic::AtomicBool;
+/// use algo_vanity_rs::supervise;
+///
+/// let (mut runs, mut restarts) = (0, 0);
+/// supervise(&AtomicBool::new(true), || { runs += 1; if runs < 3 { panic!("worker failed") } }, || restarts += 1);
+/// assert_eq!((runs, restarts), (3, 2));
+/// ```
+pub fn supervise(keep_alive: &AtomicBool, mut work: impl FnMut(), mut on_restart: impl FnMut()) {
+    while std::panic::catch_unwind(std::panic::AssertUnwindSafe(&mut work)).is_err() && keep_alive.load(Ordering::Relaxed) {
+        on_restart();
+    }
+}
+
+/// Build and runtime details for bug reports, as pairs of keys and values
+///
+/// ```
+/// let info = algo_vanity_rs::version_info();
+/// let keys: Vec<_> = info.iter().map(|(key, _)| *key).collect();
+/// assert_eq!(keys, ["version", "algo_rust_sdk", "rand", "rng", "features", "profile", "target", "threads"]);
+/// assert_eq!(info[0].1, env!("CARGO_PKG_VERSION"));
+///
+/// let features = &info[4].1;
+/// assert_eq!(features.contains("serve"), cfg!(feature = "serve"));
+/// assert_eq!(features.contains("script"), cfg!(feature = "script"));
+/// ```
+pub fn version_info() -> Vec<(&'static str, String)> {
+    let features: Vec<&str> = [("serve", cfg!(feature = "serve")), ("script", cfg!(feature = "script"))].iter()
+        .filter(|(_, enabled)| *enabled).map(|(name, _)| *name).collect();
+    vec![
+        ("version", env!("CARGO_PKG_VERSION").to_string()),
+        ("algo_rust_sdk", env!("SDK_VERSION").to_string()),
+        ("rand", env!("RAND_VERSION").to_string()),
+        ("rng", String::from("StdRng (ChaCha12) seeded from thread_rng, or from --seed or --entropy-file")),
+        ("features", if features.is_empty() { String::from("none") } else { features.join(", ") }),
+        ("profile", String::from(if cfg!(debug_assertions) { "debug" } else { "release" })),
+        ("target", format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS)),
+        ("threads", std::thread::available_parallelism().map_or(String::from("unknown"), |t| t.to_string())),
+    ]
+}
+
+/// Serialize a duration as fractional seconds, such as with `#[serde(serialize_with = "serialize_secs")]`
+pub fn serialize_secs<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+/// Serialize an optional duration as fractional seconds, see [`serialize_secs`]
+pub fn serialize_opt_secs<S: serde::Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    duration.map(|d| d.as_secs_f64()).serialize(serializer)
+}
+
+/// Fixed memory used by each worker thread, for its generator and copy of the patterns
+pub fn thread_memory(vanities: &[String]) -> usize {
+    std::mem::size_of::<Accounts>() + vanities.iter().map(|v| std::mem::size_of::<String>() + v.len()).sum::<usize>()
+}
+
+/// Number of items a per-thread buffer may hold, such that together with the fixed
+/// [`thread_memory`] it stays within `max_memory` bytes. Buffers hold at least one item.
+///
+/// ```
+/// use algo_vanity_rs::capped_batch_len;
+///
+/// assert_eq!(capped_batch_len(32, 1000, 1_000, None), 1000);
+/// assert_eq!(capped_batch_len(32, 1000, 1_000, Some(1_000_000)), 1000);
+/// assert_eq!(capped_batch_len(32, 1000, 1_000, Some(17_000)), 500);
+/// assert_eq!(capped_batch_len(32, 1000, 1_000, Some(500)), 1);
+/// ```
+pub fn capped_batch_len(item_size: usize, len: usize, fixed_memory: usize, max_memory: Option<usize>) -> usize {
+    match max_memory {
+        Some(max) => (max.saturating_sub(fixed_memory) / item_size.max(1)).clamp(1, len.max(1)),
+        None => len,
+    }
+}
+
+/// Sum offset counts into `buckets` equally wide buckets covering all offsets
+///
+/// ```
+/// use algo_vanity_rs::offset_histogram;
+///
+/// let mut offsets = vec![0; 58];
+/// offsets[0] = 3;
+/// offsets[16] = 1;
+/// offsets[15] = 2;
+/// offsets[57] = 5;
+/// assert_eq!(offset_histogram(&offsets, 4), [3, 3, 0, 5]);
+/// assert_eq!(offset_histogram(&offsets, 1), [11]);
+/// assert_eq!(offset_histogram(&offsets, 0), [0; 0]);
+/// ```
+pub fn offset_histogram(offsets: &[usize], buckets: usize) -> Vec<usize> {
+    let mut histogram = vec![0; buckets];
+    if buckets == 0 { return histogram }
+    for (offset, count) in offsets.iter().enumerate() {
+        histogram[offset * buckets / offsets.len()] += count;
+    }
+    histogram
+}
+
+/// Addresses of freshly generated random accounts, without any matching, to get a feel for
+/// what addresses look like. Each account has a seed of its own, which is discarded, so the
+/// addresses can never be spent from.
+///
+/// ```
+/// use algo_vanity_rs::{ADDRESS_CHARS, difficulty::ADDRESS_LENGTH, keys::KeyType, sample_addresses};
+///
+/// let addresses = sample_addresses(3, KeyType::default());
+/// assert_eq!(addresses.len(), 3);
+/// assert!(addresses.iter().all(|a| a.len() == ADDRESS_LENGTH && a.chars().all(|c| ADDRESS_CHARS.contains(c))));
+/// ```
+pub fn sample_addresses(count: usize, key_type: KeyType) -> Vec<String> {
+    let mut rng = thread_rng();
+    (0..count).map(|_| key_type.account_from_seed(rng.gen()).address().encode_string()).collect()
+}
+
+/// Length of automatically generated run labels
+const RUN_LABEL_LENGTH: usize = 6;
+
+/// Generate a short random label to tell concurrent runs apart
+pub fn random_run_label() -> String {
+    let chars = ADDRESS_CHARS.to_lowercase();
+    let mut rng = thread_rng();
+    (0..RUN_LABEL_LENGTH).map(|_| chars.as_bytes()[rng.gen_range(0..chars.len())] as char).collect()
+}
+
+/// Insert the run label into a file path, just before its extension
+///
+/// ```
+/// use algo_vanity_rs::labeled_path;
+///
+/// assert_eq!(labeled_path("vanities.json", "x7k2qa"), "vanities-x7k2qa.json");
+/// assert_eq!(labeled_path("out/matches", "run1"), "out/matches-run1");
+/// assert_eq!(labeled_path("my.dir/log", "run1"), "my.dir/log-run1");
+/// ```
+pub fn labeled_path(path: &str, label: &str) -> String {
+    let file_start = path.rfind(['/', '\\']).map_or(0, |i| i + 1);
+    match path[file_start..].rfind('.') {
+        Some(dot) if dot > 0 => {
+            let (stem, extension) = path.split_at(file_start + dot);
+            format!("{stem}-{label}{extension}")
+        },
+        _ => format!("{path}-{label}"),
+    }
+}
+
+/// Collect patterns from arguments which are either literal patterns or paths of pattern
+/// files, keeping only the first of any duplicates. Pattern files hold a json list of
+/// strings, or plain text with whitespace separated patterns. The patterns of structured
+/// pattern files are included without their settings, see [`collect_pattern_entries`].
+///
+/// ```
+/// use algo_vanity_rs::collect_patterns;
+///
+/// let dir = std::env::temp_dir();
+/// let json = dir.join(format!("algo-vanity-patterns-{}.json", std::process::id()));
+/// let text = dir.join(format!("algo-vanity-patterns-{}.txt", std::process::id()));
+/// std::fs::write(&json, r#"["algo", "rand"]"#).unwrap();
+/// std::fs::write(&text, "rand\nrocks\n").unwrap();
+///
+/// let args = [json.to_string_lossy().into_owned(), text.to_string_lossy().into_owned(), String::from("moon")];
+/// let patterns = collect_patterns(&args).unwrap();
+/// std::fs::remove_file(json).unwrap();
+/// std::fs::remove_file(text).unwrap();
+///
+/// assert_eq!(patterns, ["algo", "rand", "rocks", "moon"]);
+/// ```
+pub fn collect_patterns(args: &[String]) -> io::Result<Vec<String>> {
+    Ok(collect_pattern_entries(args)?.into_iter().map(|entry| entry.pattern).collect())
+}
+
+/// Collect patterns like [`collect_patterns`], along with the settings given to them in
+/// structured pattern files, detected by their `.yaml`, `.yml` or `.toml` extension. The
+/// entries are ordered by descending priority, and otherwise in the order they were given.
+pub fn collect_pattern_entries(args: &[String]) -> io::Result<Vec<PatternEntry>> {
+    let mut entries: Vec<PatternEntry> = Vec::new();
+    for arg in args {
+        let from_arg = match std::fs::read_to_string(arg) {
+            Ok(contents) => match PatternFormat::from_extension(arg) {
+                Some(format) => format.parse(&contents).map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData, format!("{arg} is not a valid {format:?} pattern file: {e}"))
+                })?,
+                None if contents.trim_start().starts_with('[') => serde_json::from_str::<Vec<String>>(&contents).map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData, format!("{arg} is not a json list of strings, e.g. [\"algo\",\"rand\"]: {e}"))
+                })?.into_iter().map(PatternEntry::new).collect(),
+                None => contents.split_whitespace().map(PatternEntry::new).collect(),
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![PatternEntry::new(arg.clone())],
+            Err(e) => return Err(e),
+        };
+        for entry in from_arg {
+            if !entries.iter().any(|e| e.pattern == entry.pattern) { entries.push(entry) }
+        }
+    }
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.priority));
+    Ok(entries)
+}
+
+/// Number of words in an Algorand mnemonic
+pub const MNEMONIC_WORDS: usize = 25;
+
+/// Whether the mnemonic is complete and repeats none of its words.
+///
+/// Each of the 25 words is one of 2048, so roughly 14% of mnemonics repeat a word, and
+/// keeping only unique ones takes about 16% more attempts per kept match. Accounts of the
+/// same batch share most of their seed, see [`Accounts`], and thereby most of their words, so
+/// matches are dropped in bursts rather than evenly. Checking a match costs little next to
+/// generating it, as its mnemonic was derived already.
+///
+/// ```
+/// use algo_vanity_rs::has_unique_words;
+///
+/// let unique = "abandon ability able about above absent absorb abstract absurd abuse access accident account accuse achieve acid acoustic acquire across act action actor actress actual adapt";
+/// assert!(has_unique_words(unique));
+/// assert!(!has_unique_words(&unique.replace("adapt", "abandon")));
+/// assert!(!has_unique_words(""));
+/// ```
+pub fn has_unique_words(mnemonic: &str) -> bool {
+    let words: HashSet<&str> = mnemonic.split_whitespace().collect();
+    words.len() == MNEMONIC_WORDS && mnemonic.split_whitespace().count() == MNEMONIC_WORDS
+}
+
+/// Prefixes of the pattern from a single character up to the whole pattern, the rungs of
+/// a ladder climbed by a best effort search for the pattern
+///
+/// ```
+/// assert_eq!(algo_vanity_rs::prefix_ladder("ALGO"), ["A", "AL", "ALG", "ALGO"]);
+/// ```
+pub fn prefix_ladder(pattern: &str) -> Vec<String> {
+    pattern.char_indices().map(|(i, c)| pattern[..i + c.len_utf8()].to_string()).collect()
+}
+
+/// Highest rung of the prefix ladder of the pattern reached by any of the matches
+///
+/// ```
+/// use algo_vanity_rs::{AddressMatch, Placement, ladder_best};
+///
+/// let matches = vec![
+///     AddressMatch::new("AL", "ALXYZ", "", Placement::Start),
+///     AddressMatch::new("ALG", "ALGYZ", "", Placement::Start),
+///     AddressMatch::new("RAND", "RANDZ", "", Placement::Start),
+/// ];
+/// assert_eq!(ladder_best("ALGO", &matches).map(|m| m.target()), Some("ALG"));
+/// assert!(ladder_best("BLGO", &matches).is_none());
+/// ```
+pub fn ladder_best<'a>(pattern: &str, matches: &'a [AddressMatch]) -> Option<&'a AddressMatch> {
+    matches.iter().filter(|m| pattern.starts_with(m.target.as_str())).max_by_key(|m| m.target.len())
+}
+
+/// Remove a pattern from a running search, which workers pick up from their next batch.
+/// Returns whether the pattern was being searched for.
+pub fn cancel_pattern(state: &Mutex<GlobalState>, keep_alive: &AtomicBool, pattern: &str) -> bool {
+    let Ok(mut s) = state.lock() else { return false };
+    let Some(index) = s.vanities.iter().position(|v| v == pattern) else { return false };
+    s.vanities.remove(index);
+    if s.vanities.is_empty() {
+        keep_alive.store(false, Ordering::Relaxed);
+    }
+    true
+}
+
+/// Overwrite the secrets of every match of a search with zeros, see [`AddressMatch::wipe_secrets`],
+/// such as on exit once they are saved
+pub fn wipe_secrets(state: &mut GlobalState) {
+    state.matches.iter_mut().for_each(AddressMatch::wipe_secrets);
+}
+
+/// Load previously saved matches from a results file of any supported format
+pub fn load_matches(path: &str) -> io::Result<Vec<AddressMatch>> {
+    format::read_matches(path).map(|(matches, _)| matches)
+}
+
+/// Shareable text card of the matches of a finished search, with its run time and rate. The
+/// mnemonics are always left out, such that the card can be pasted anywhere.
+///
+/// ```
+/// use std::time::Duration;
+/// use algo_vanity_rs::{AddressMatch, Placement, summary_card};
+///
+/// let matches = vec![AddressMatch::new("ALGO", "ALGOXYZ", "secret words", Placement::Start)];
+/// let card = summary_card(&matches, Duration::from_secs(100), 3_000_000);
+///
+/// assert!(card.contains("ALGO    start      ALGOXYZ\n"));
+/// assert!(card.contains("Found 1 address in 1m 40s, out of 3.00 million generated at 30,000 per second"));
+/// assert!(!card.contains("secret"));
+/// ```
+pub fn summary_card(matches: &[AddressMatch], run_time: Duration, attempts: usize) -> String {
+    const TITLE: &str = "Algorand vanity addresses";
+    let rate = attempts as f64 / run_time.as_secs_f64().max(f64::MIN_POSITIVE);
+    let found = match matches.len() {
+        1 => String::from("1 address"),
+        n => format!("{n} addresses"),
+    };
+
+    let mut card = format!("{TITLE}\n{}\n\n", "=".repeat(TITLE.len()));
+    card += &render_matches(matches, true, false, false);
+    card += &format!("\nFound {found} in {}, out of {} generated at {} per second\n",
+        difficulty::format_duration(run_time.as_secs_f64()), difficulty::format_count(attempts), difficulty::format_attempts(rate));
+    card
+}
+
+/// Render matches for printing, either as a summary per match or as an aligned table,
+/// optionally including the mnemonics. Addresses can be shown lowercased next to their
+/// canonical uppercase form, for comparing them with wallets which display them lowercased.
+///
+/// ```
+/// use algo_vanity_rs::{AddressMatch, Placement, render_matches};
+///
+/// let matches = vec![
+///     AddressMatch::new("ALGO", "ALGOXYZ", "secret words", Placement::Start),
+///     AddressMatch::new("RAND", "XRANDYZ", "more secrets", Placement::Anywhere(1)).with_label("rand"),
+/// ];
+///
+/// assert_eq!(render_matches(&matches, false, false, false), "ALGO ALGOXYZ (start)\nRAND \"rand\" XRANDYZ (anywhere at 1)\n");
+/// assert!(render_matches(&matches, false, true, false).contains("  secret words\n"));
+/// assert!(render_matches(&matches, false, false, true).starts_with("ALGO ALGOXYZ (start)\n  lowercase algoxyz\n"));
+///
+/// let table = render_matches(&matches, true, false, false);
+/// assert!(table.starts_with("TARGET  PLACEMENT      ADDRESS\n"));
+/// assert!(table.contains("RAND    anywhere at 1  XRANDYZ\n"));
+/// assert!(!table.contains("secret"));
+///
+/// let table = render_matches(&matches, true, false, true);
+/// assert!(table.starts_with("TARGET  PLACEMENT      ADDRESS  LOWERCASE\n"));
+/// assert!(table.contains("RAND    anywhere at 1  XRANDYZ  xrandyz\n"));
+/// ```
+pub fn render_matches(matches: &[AddressMatch], table: bool, show_mnemonic: bool, show_lowercase: bool) -> String {
+    let mut out = String::new();
+
+    if !table {
+        for m in matches {
+            out += &format!("{}\n", m.summary());
+            if show_lowercase { out += &format!("  lowercase {}\n", m.public.to_lowercase()) }
+            if show_mnemonic && !m.mnemonic.is_empty() { out += &format!("  {}\n", m.mnemonic) }
+        }
+        return out
+    }
+
+    // Collect the cells of each row, starting with the header, leaving out disabled columns
+    let header = ["TARGET", "PLACEMENT", "ADDRESS", "LOWERCASE", "MNEMONIC"];
+    let shown = |column: usize| match column {
+        3 => show_lowercase,
+        4 => show_mnemonic,
+        _ => true,
+    };
+    let rows: Vec<Vec<String>> = std::iter::once(header.map(String::from).to_vec())
+        .chain(matches.iter().map(|m| vec![m.target.clone(), m.placement.to_string(), m.public.clone(), m.public.to_lowercase(), m.mnemonic.clone()]))
+        .map(|row| row.into_iter().enumerate().filter(|(c, _)| shown(*c)).map(|(_, cell)| cell).collect())
+        .collect();
+    let columns = rows[0].len();
+
+    // Pad every column but the last to its widest cell
+    let widths: Vec<usize> = (0..columns).map(|c| rows.iter().map(|r| r[c].len()).max().unwrap_or(0)).collect();
+    for row in rows {
+        let cells: Vec<String> = row.iter().zip(&widths).map(|(cell, width)| format!("{cell:width$}")).collect();
+        out += cells.join("  ").trim_end();
+        out += "\n";
+    }
+    out
+}
+
+pub(crate) fn thread_worker(
+    thread_id: usize,
+    tx_worker_msg: mpsc::Sender<WorkerMsg>,
+    state: Arc<Mutex<GlobalState>>,
+    keep_alive: Arc<AtomicBool>,
+    mut options: WorkerOptions,
+) {
+    let mut prev_time = Instant::now();
+
+    // Matching the reversed address is matching the reversed targets with the start and the
+    // end swapped, which spares reversing every address
+    if options.reverse {
+        let placement = &mut options.placement;
+        std::mem::swap(&mut placement.start, &mut placement.end);
+    }
+
+    // Deterministic mode derives the seed of each batch from the user seed and batch index,
+    // while an entropy file seeds the generator of each thread
+    let entropy = options.entropy.as_ref().and_then(|seeds| seeds.get(thread_id));
+    let accounts = match (options.seed, entropy, options.seed_pool.clone()) {
+        (Some(seed), _, _) => Accounts::partitioned(seed, options.partition, options.key_type),
+        (None, Some(entropy), _) => Accounts::new(StdRng::from_seed(*entropy), options.key_type),
+        (None, None, Some(pool)) => Accounts::with_pool(StdRng::from_rng(thread_rng()).expect("Unable to seed random number generator"), options.key_type, pool),
+        (None, None, None) => Accounts::new(StdRng::from_rng(thread_rng()).expect("Unable to seed random number generator"), options.key_type),
+    };
+    // Each thread generates accounts in its own domain, so threads never repeat each other's work
+    let mut accounts = accounts.with_domain(thread_id as u16); // Distinct for up to 65536 threads
+    while keep_alive.load(Ordering::Relaxed) {
+
+        // Wait until automatic scaling activates this thread
+        if thread_id >= options.active_threads.load(Ordering::Relaxed) {
+            std::thread::sleep(IDLE_INTERVAL);
+            prev_time = Instant::now();
+            continue
+        }
+
+        let vanity_targets = if let Ok(s) = state.lock() { s.vanities.clone() } else { return };
+
+        // Patterns with a window are only searched for within it
+        let windows: Vec<_> = options.windows.iter().filter(|w| vanity_targets.contains(&w.pattern)).collect();
+        // Regex patterns are matched as expressions rather than literally
+        let regexes: Vec<_> = options.regexes.iter().filter(|r| vanity_targets.contains(&r.source)).collect();
+        let vanity_targets: Vec<_> = vanity_targets.into_iter()
+            .filter(|t| windows.iter().all(|w| &w.pattern != t) && regexes.iter().all(|r| &r.source != t))
+            .map(|t| if options.reverse { t.chars().rev().collect() } else { t })
+            .collect();
+
+        // Dry runs skip generating the batch, leaving only the coordination around it
+        if !options.dry_run {
+            for acc in accounts.by_ref().take(options.batch_len) {
+                find_vanity(&tx_worker_msg, &vanity_targets, &windows, &regexes, &acc, &options);
+            }
+        }
+
+        let current_time = Instant::now();
+        let duration = Instant::now().duration_since(prev_time);
+        prev_time = current_time;
+        _ = tx_worker_msg.send(WorkerMsg::Count((thread_id,options.batch_len,duration)));
+
+        match options.fault.as_ref().and_then(|fault| fault.lock().ok()?.take()) {
+            Some(WorkerFault::Panic) => panic!("Injected fault in worker {thread_id}"),
+            Some(WorkerFault::Stall) => while keep_alive.load(Ordering::Relaxed) { std::thread::sleep(IDLE_INTERVAL) },
+            None => {},
+        }
+    }
+}
+
+fn find_vanity(
+    tx_worker_msg: &mpsc::Sender<WorkerMsg>,
+    vanity_targets: &[String],
+    windows: &[&PatternWindow],
+    regexes: &[&RegexPattern],
+    acc: &Account,
+    options: &WorkerOptions,
+) {
+    let placement = &options.placement;
+    let acc_string = acc.address().encode_string();
+
+    // No match counts without the checksum suffix, whatever the patterns
+    if options.checksum.as_ref().is_some_and(|c| !c.satisfied(&acc_string)) { return }
+    // Likewise for addresses sorting at the wrong side of the reference address
+    if options.order.as_ref().is_some_and(|o| !o.satisfied(&acc_string)) { return }
+
+    // Send a match of the target with the given placement to the main loop, reversing targets
+    // back which were reversed to match the reversed address
+    let send_match = |target: &String, placement: Placement, matched: Option<String>| {
+        let target = &match options.reverse {
+            true => target.chars().rev().collect(),
+            false => target.clone(),
+        };
+        if options.address_only {
+            _ = tx_worker_msg.send(WorkerMsg::Address((target.clone(), acc_string.clone(), placement, matched)));
+            return
+        }
+        _ = tx_worker_msg.send(
+            WorkerMsg::AddressMatch(AddressMatch {
+                target: target.clone(),
+                public: acc_string.clone(),
+                mnemonic: if options.no_secrets { String::new() } else { acc.mnemonic() },
+                placement,
+                seed: (options.record_seed && !options.no_secrets).then(|| seed_to_hex(&acc.seed())),
+                label: None,
+                rarity: None,
+                reversed: false,
+                found_after_attempts: None,
+                found_after_secs: None,
+                matched,
+            })
+        );
+    };
+
+    let mut matches: Vec<_> = find_matches(&acc_string, vanity_targets, placement, options.one_per_account)
+        .into_iter().map(|(target, placement)| (target, placement, None)).collect();
+
+    for window in windows {
+        if options.one_per_account && !matches.is_empty() { break }
+        if let Some(index) = window.find(&acc_string) {
+            matches.push((&window.pattern, Placement::Window { start: window.start, end: window.end, index }, None));
+        }
+    }
+
+    for regex in regexes {
+        if options.one_per_account && !matches.is_empty() { break }
+        let found = regex.find(&acc_string, placement).into_iter().map(|(placement, text)| (&regex.source, placement, Some(text)));
+        matches.extend(found.take(if options.one_per_account { 1 } else { usize::MAX }));
+    }
+
+    // Comparing every target in full against every address makes near matching costly
+    if let Some(max_distance) = options.max_distance.filter(|_| !options.one_per_account || matches.is_empty()) {
+        for target in vanity_targets {
+            let distance = acc_string.get(..target.len()).and_then(|prefix| hamming_distance(prefix, target));
+            if let Some(distance) = distance.filter(|d| *d <= max_distance && (*d > 0 || !placement.start)) {
+                matches.push((target, Placement::Near(distance), None));
+                if options.one_per_account { break }
+            }
+        }
+    }
+
+    let mut sent = Vec::new();
+    for (target, placement, matched) in matches {
+        if options.region.as_ref().is_none_or(|r| r.satisfied(&acc_string, target, &placement)) && script_satisfied(options, &acc_string, target) {
+            send_match(target, placement, matched);
+            sent.push(target.as_str());
+        }
+    }
+    if is_double_vanity(sent) {
+        _ = tx_worker_msg.send(WorkerMsg::DoubleVanity(acc_string));
+    }
+}
+
+/// Whether the address matching the target satisfies the script predicate of the search, if it
+/// has one. The predicate sees the target as given, also in reversed searches.
+#[cfg(feature = "script")]
+fn script_satisfied(options: &WorkerOptions, address: &str, target: &str) -> bool {
+    let Some(script) = &options.script else { return true };
+    match options.reverse {
+        true => script.satisfied(address, &target.chars().rev().collect::<String>()),
+        false => script.satisfied(address, target),
+    }
+}
+
+#[cfg(not(feature = "script"))]
+fn script_satisfied(_options: &WorkerOptions, _address: &str, _target: &str) -> bool {
+    true
+}
+
+/// Whether the patterns matched by an address include at least two different ones, which is
+/// rare enough to point out. A pattern found at several places only counts once.
+///
+/// ```
+/// use algo_vanity_rs::{SearchPlacement, find_matches, is_double_vanity};
+///
+/// let address = "ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZE";
+/// let targets = vec![String::from("ALGO"), String::from("VUDU"), String::from("ZE")];
+/// let anywhere = SearchPlacement { start: false, anywhere: true, end: false };
+/// let double = |targets: &[String]| is_double_vanity(find_matches(address, targets, &anywhere, false).into_iter().map(|(t, _)| t.as_str()));
+///
+/// assert!(double(&targets));
+/// assert!(!double(&targets[..1]));
+/// assert!(!is_double_vanity(["ALGO", "ALGO"]));
+/// ```
+pub fn is_double_vanity<'a>(targets: impl IntoIterator<Item = &'a str>) -> bool {
+    let mut targets = targets.into_iter();
+    let Some(first) = targets.next() else { return false };
+    targets.any(|t| t != first)
+}
+
+/// Every pattern found in the address with the given placement, in the order of the
+/// targets. An account matching several patterns reports each of them, and a pattern
+/// at both the start and the end is reported twice. With `first_only`, only the first
+/// match is reported.
+///
+/// ```
+/// use algo_vanity_rs::{SearchPlacement, find_matches};
+///
+/// let address = "ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZE";
+/// let targets = vec![String::from("ALGO"), String::from("ALG"), String::from("ZE")];
+/// let placement = SearchPlacement { start: true, anywhere: false, end: true };
+///
+/// let all: Vec<_> = find_matches(address, &targets, &placement, false).into_iter().map(|(t, p)| format!("{t} {p}")).collect();
+/// assert_eq!(all, ["ALGO start", "ALG start", "ZE end"]);
+///
+/// let first = find_matches(address, &targets, &placement, true);
+/// assert_eq!(first.len(), 1);
+/// assert_eq!(first[0].0, "ALGO");
+/// ```
+pub fn find_matches<'a>(
+    address: &str,
+    targets: &'a [String],
+    placement: &SearchPlacement,
+    first_only: bool,
+) -> Vec<(&'a String, Placement)> {
+    let mut matches = Vec::new();
+
+    for target in targets {
+
+        let mut matched_start_end = false;
+
+        // Look for match at start of address
+        if placement.start && Placement::Start.matches(address, target) {
+            matches.push((target, Placement::Start));
+            matched_start_end = true;
+        }
+
+        // Look for match at end of address
+        if placement.end && Placement::End.matches(address, target) {
+            matches.push((target, Placement::End));
+            matched_start_end = true;
+        }
+
+        // Look for match anywhere in address
+        if !matched_start_end && placement.anywhere {
+            if let Some(index) = find_anywhere(address, target) {
+                matches.push((target, Placement::Anywhere(index)));
+            }
+        }
+
+        if first_only && !matches.is_empty() {
+            matches.truncate(1);
+            break
+        }
+    };
+    matches
+}
+
+/// Index of the occurrence of the target reported by an anywhere search.
+///
+/// The leftmost occurrence is chosen, and among occurrences starting at the same index the
+/// longest one. Every occurrence of a literal target has the same length, so this is simply
+/// the first one, also when occurrences overlap. Regex patterns follow the same rule, see
+/// [`regex_pattern::RegexPattern::find`].
+///
+/// ```
+/// use algo_vanity_rs::find_anywhere;
+///
+/// assert_eq!(find_anywhere("BAAAAC", "AA"), Some(1));
+/// assert_eq!(find_anywhere("ABABAB", "BAB"), Some(1));
+/// assert_eq!(find_anywhere("ALGOALGO", "ALGO"), Some(0));
+/// assert_eq!(find_anywhere("ALGO", "RAND"), None);
+/// ```
+pub fn find_anywhere(address: &str, target: &str) -> Option<usize> {
+    address.find(target)
+}
+
+/// Number of positions at which two strings differ, if they have the same length
+///
+/// ```
+/// use algo_vanity_rs::hamming_distance;
+///
+/// assert_eq!(hamming_distance("ALGO", "ALGO"), Some(0));
+/// assert_eq!(hamming_distance("ALGO", "ALSO"), Some(1));
+/// assert_eq!(hamming_distance("ALGO", "BLGA"), Some(2));
+/// assert_eq!(hamming_distance("ALGO", "ALG"), None);
+/// ```
+pub fn hamming_distance(a: &str, b: &str) -> Option<usize> {
+    (a.len() == b.len()).then(|| a.chars().zip(b.chars()).filter(|(x, y)| x != y).count())
+}
+
+/// Number of leading characters of the target which a match fixes in a row. Exact matches fix
+/// the whole target, while near matches only fix the characters before their first difference.
+///
+/// ```
+/// use algo_vanity_rs::{AddressMatch, Placement, matched_prefix};
+///
+/// let address = "ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZE";
+/// assert_eq!(matched_prefix(&AddressMatch::new("ALGO", address, "", Placement::Start)), 4);
+/// assert_eq!(matched_prefix(&AddressMatch::new("ALSO", address, "", Placement::Near(1))), 2);
+/// assert_eq!(matched_prefix(&AddressMatch::new("BLGO", address, "", Placement::Near(1))), 0);
+/// ```
+pub fn matched_prefix(address_match: &AddressMatch) -> usize {
+    match address_match.placement {
+        Placement::Near(_) => address_match.public.chars().zip(address_match.target.chars()).take_while(|(a, t)| a == t).count(),
+        _ => address_match.matched_text().len(),
+    }
+}
+
+/// Encode account seed bytes as a hexadecimal string
+fn seed_to_hex(seed: &[u8; 32]) -> String {
+    seed.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,105 @@
+//! Number formatting conventions for displayed statistics
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Grouping separator, decimal mark and magnitude words used when formatting numbers
+///
+/// ```
+/// use algo_vanity_rs::locale::NumberLocale;
+///
+/// assert_eq!(NumberLocale::En.group(1_234_567), "1,234,567");
+/// assert_eq!(NumberLocale::De.group(1_234_567), "1.234.567");
+/// assert_eq!(NumberLocale::Fr.group(1_234_567), "1 234 567");
+///
+/// assert_eq!(NumberLocale::En.count(25_300_000), "25.3 million");
+/// assert_eq!(NumberLocale::De.count(25_300_000), "25,3 Mio.");
+/// assert_eq!(NumberLocale::Si.count(4_200_000_000), "4.20 G");
+/// assert_eq!(NumberLocale::Fr.count(999), "999");
+/// ```
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,ValueEnum,Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NumberLocale {
+    /// 1,234,567 and 1.23 million
+    #[default]
+    En,
+    /// 1.234.567 and 1,23 Mio.
+    De,
+    /// 1 234 567 and 1,23 M
+    Fr,
+    /// 1 234 567 and 1.23 M
+    Si,
+}
+
+impl NumberLocale {
+    /// Separator between groups of three digits
+    pub fn separator(&self) -> &'static str {
+        match self {
+            NumberLocale::En => ",",
+            NumberLocale::De => ".",
+            NumberLocale::Fr | NumberLocale::Si => " ",
+        }
+    }
+
+    /// Mark between the integer and fractional part
+    pub fn decimal_mark(&self) -> char {
+        match self {
+            NumberLocale::En | NumberLocale::Si => '.',
+            NumberLocale::De | NumberLocale::Fr => ',',
+        }
+    }
+
+    /// Words for trillions, billions, millions and thousands
+    pub fn units(&self) -> [&'static str; 4] {
+        match self {
+            NumberLocale::En => ["trillion", "billion", "million", "thousand"],
+            NumberLocale::De => ["Bio.", "Mrd.", "Mio.", "Tsd."],
+            NumberLocale::Fr => ["T", "Md", "M", "k"],
+            NumberLocale::Si => ["T", "G", "M", "k"],
+        }
+    }
+
+    /// Integer with its digits grouped by three
+    pub fn group(&self, n: u64) -> String {
+        let digits = n.to_string();
+        let mut grouped = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push_str(self.separator());
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    /// Count with a unit suited to its magnitude, keeping three significant digits
+    pub fn count(&self, n: usize) -> String {
+        const SIZES: [f64; 4] = [1e12, 1e9, 1e6, 1e3];
+
+        // Round to three significant digits first, such that rounding up can change the unit
+        let mut n = n as f64;
+        if n >= 1e3 {
+            let precision = 10f64.powi(n.log10().floor() as i32 - 2);
+            n = (n / precision).round() * precision;
+        }
+
+        match SIZES.iter().zip(self.units()).find(|(size, _)| n >= **size) {
+            Some((size, unit)) => {
+                let scaled = n / size;
+                let decimals = if scaled < 10.0 { 2 } else if scaled < 100.0 { 1 } else { 0 };
+                let scaled = format!("{scaled:.decimals$}").replace('.', &self.decimal_mark().to_string());
+                format!("{scaled} {unit}")
+            },
+            None => format!("{n}"),
+        }
+    }
+
+    /// Number of attempts with grouped digits
+    pub fn attempts(&self, attempts: f64) -> String {
+        if attempts.is_finite() {
+            self.group(attempts.ceil() as u64)
+        } else {
+            String::from("∞ (impossible)")
+        }
+    }
+}
@@ -1,123 +1,554 @@
+// The resolved configuration printed by --print-config outgrows the default json! macro recursion
+#![recursion_limit = "256"]
+
 use std::{
+    cmp,
+    collections::HashMap,
     thread,
-    fs::File,
-    io::{Write, self},
-    fmt::Display,
-    time::{Instant, Duration},
-    sync::{Arc,mpsc,atomic::{AtomicBool, Ordering}, Mutex},
+    time::Duration,
+    sync::atomic::Ordering,
 };
 
-use clap::Parser;
-use rand::{Rng,thread_rng};
-use serde::{Serialize,Deserialize};
-use algo_rust_sdk::account::Account;
+use thousands::Separable;
+use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
+use ratatui::style::Color;
+use algo_vanity_rs::{
+    Completion, Partition, PlacementMode, SearchConfig, SearchPlacement, VanitySearcher, collect_pattern_entries, labeled_path, ladder_best, load_matches, thread_memory, random_run_label, read_entropy, render_matches, sample_addresses, summary_card, verify_match, wipe_secrets,
+    ADDRESS_CHARS, DEFAULT_PATH, DEFAULT_THREADS, difficulty::{self, DifficultyModel}, format::{MatchField, ResultsFormat}, keys::KeyType, interesting::InterestingRule, locale::NumberLocale, region::RegionConstraint, checksum::ChecksumConstraint, ordering::{OrderConstraint, reference_address}, regex_pattern::RegexPattern, window::PatternWindow,
+};
 
+mod simple;
 mod tui;
 
-/// Number of per-thread account checks between notifying main thread
-const COUNT_PER_LOOP: usize = 100;
-
-/// Default file path to save vanity addresses to
-const DEFAULT_PATH: &str = "vanities.json";
-
 // Maximum number of threads before stopping user
 const MAX_THREADS: usize = 128;
 
-// Default number of threads if auto detect fails
-const DEFAULT_THREADS: usize = 4;
-
-/// Message types worker threads send back to the main thread loop
-enum WorkerMsg {
-    AddressMatch(AddressMatch),
-    Count((usize,Duration))
-}
-
-/// Struct for when an address has matched a vanity string
-#[derive(Serialize,Deserialize,Clone)]
-struct AddressMatch {
-    target : String,
-    public : String,
-    mnemonic : String,
-    placement : Placement
-}
+/// Default refresh rate of the terminal ui
+const DEFAULT_TUI_FPS: u32 = 10;
 
-/// Placement of matched string pattern
-#[derive(Serialize,Deserialize,Clone)]
-enum Placement {
-    Start,
-    Anywhere(usize),
-    End,
-}
+/// Accounts to measure with --benchmark-encoding
+const BENCHMARK_ACCOUNTS: usize = 100_000;
 
-struct GlobalState {
-    vanities: Vec<String>,
-    threads: usize,
-    placement: SearchPlacement,
-    matches: Vec<AddressMatch>,
-    search_rate: f32,
-    total_count: usize,
-    match_count: usize,
-    start_time: Instant,
-    run_time: Duration,
-    save_path: String,
-}
-
-/// Places to search in addresses
-#[derive(Clone,Debug)]
-struct SearchPlacement {
-    start:bool,
-    anywhere:bool,
-    end:bool,
-}
-
-impl Display for SearchPlacement {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f,"{}",match (self.start,self.anywhere,self.end) {
-            (_, true, _) => "Anywhere",
-            (true, false, true) => "Start and end",
-            (true, false, false) => "Start",
-            (false, false, true) => "End",
-            (false, false, false) => "Nowhere"
-        })
-    }
-}
+/// Time to wait for threads to finish after stopping, before exiting regardless
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 
 // Command line arguments.
 #[derive(Parser,Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    /// Vanity strings to search for (or json file path)
-    #[clap(num_args = 1..,required = true)]
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Vanity strings to search for, or paths of files listing them, as json, plain text, or yaml
+    /// and toml giving patterns their own placement, priority and label
+    #[clap(num_args = 1..,required_unless_present_any = ["window", "benchmark_encoding", "preview", "less_than", "greater_than"])]
     vanities: Vec<String>,
 
     /// Number of threads (auto detects by default)
     #[clap(short, long)]
     threads: Option<usize>,
 
-    /// Look for match at start of address (default)
+    /// Measure the time of deriving accounts against encoding their addresses, and exit
+    #[clap(long, default_value_t = false)]
+    benchmark_encoding: bool,
+
+    /// Print this many random addresses, without matching or saving anything, and exit
+    #[clap(long, value_name = "N")]
+    preview: Option<usize>,
+
+    /// Experimental: start with few threads and add more while they raise the search rate
+    #[clap(long, default_value_t = false)]
+    auto_threads: bool,
+
+    #[command(flatten)]
+    placement: PlacementArgs,
+
+    /// File path for saving vanity addresses
+    #[clap(short, long)]
+    path: Option<String>,
+
+    /// Split matches across this many files by a hash of their pattern, with an index file
+    /// listing the file of each pattern, for searches with many patterns
+    #[clap(long)]
+    shards: Option<usize>,
+
+    /// Save only these fields of each match, in this order, e.g. public,target. The results
+    /// file must be new, as it can not be loaded again
+    #[clap(long, value_enum, value_delimiter = ',')]
+    fields: Vec<MatchField>,
+
+    /// Format of the results file, instead of the one implied by its extension. The addresses
+    /// format lists only the address of each match, and requires --no-secrets or --address-only.
+    /// The wallet format lists each account with its mnemonic and private key for importing into wallets
+    #[clap(long, value_enum, conflicts_with = "fields")]
+    format: Option<ResultsFormat>,
+
+    /// Start a new results file labeled with the current time after each interval, e.g. 1h
+    #[clap(long, value_parser = parse_duration)]
+    rotate: Option<Duration>,
+
+    /// Save matches once this many are waiting to be saved, rather than each one right away,
+    /// to spare the disk when easy patterns flood in matches
+    #[clap(long)]
+    save_every: Option<usize>,
+
+    /// Save matches once the oldest of them has waited this long, e.g. 5s, or once --save-every
+    /// matches are waiting if that comes first
+    #[clap(long, value_parser = parse_duration)]
+    save_interval: Option<Duration>,
+
+    /// Overwrite the mnemonics and seeds held in memory with zeros on exit, once they are saved,
+    /// such that they do not linger in freed memory
+    #[clap(long, default_value_t = false)]
+    overwrite_secrets_on_exit: bool,
+
+    /// Label this run, added to the output file names (random label if no value is given)
+    #[clap(long, num_args = 0..=1, default_missing_value = "")]
+    run_label: Option<String>,
+
+    /// Stop searching after this long, e.g. 90s, 30m, 2h or 1d
+    #[clap(short, long, value_parser = parse_duration)]
+    duration: Option<Duration>,
+
+    /// Warm-up time before measuring the steady-state search rate, such as 5s
+    #[clap(long, value_parser = parse_duration)]
+    warm_up: Option<Duration>,
+
+    /// Abort if the search rate stays below this many addresses per second, such as on a
+    /// throttled or preempted cloud instance
+    #[clap(long)]
+    min_rate: Option<f32>,
+
+    /// How long the search rate may stay below --min-rate before aborting, such as 5m
+    #[clap(long, value_parser = parse_duration, default_value = "60s", requires = "min_rate")]
+    min_rate_grace: Duration,
+
+    /// Keep at most this many matches per second, counting but dropping the rest, for a steady
+    /// trickle of matches rather than a flood of easy ones
+    #[clap(long)]
+    max_match_rate: Option<f64>,
+
+    /// Stop searching after generating this many addresses in total
+    #[clap(long)]
+    attempts: Option<usize>,
+
+    /// Treat vanity strings as words, skipping those which can not appear in an address
+    #[clap(short, long, default_value_t = false)]
+    words: bool,
+
+    /// Print only the mnemonic of the first match of a single pattern, without the terminal ui
+    #[clap(long, default_value_t = false, conflicts_with_all = ["no_secrets", "address_only"])]
+    first: bool,
+
+    /// Print the resolved configuration as json and exit without searching
+    #[clap(long, default_value_t = false)]
+    print_config: bool,
+
+    /// Exit after finding each vanity pattern once
+    #[clap(short, long, default_value_t = false)]
+    once: bool,
+
+    /// With --once, print a shareable card of the found addresses (without mnemonics) once all are found
+    #[clap(long, default_value_t = false, requires = "once")]
+    summary_card: bool,
+
+    /// Write the summary card to this file instead of printing it
+    #[clap(long, requires = "once")]
+    summary_file: Option<String>,
+
+    /// With --once, find this many matches of each pattern to choose from
+    #[clap(long, default_value_t = 1, requires = "once", value_parser = clap::value_parser!(u16).range(1..))]
+    candidates: u16,
+
+    /// Also require the characters next to the pattern to come from a class, as CLASS:LENGTH
+    /// where the class is digits, letters or a list of characters, e.g. digits:4
+    #[clap(long)]
+    region: Option<RegionConstraint>,
+
+    /// Also require the checksum characters at the end of matching addresses to end with this
+    /// suffix, e.g. ZE, making every match rarer by 8 times for the last character and 32 times
+    /// for each other one
+    #[clap(long)]
+    checksum_suffix: Option<ChecksumConstraint>,
+
+    /// Only match addresses sorting before this address, such as for on-chain ordering. About
+    /// the share of addresses before it match, so combine it with a pattern such as a prefix
+    /// for rarer matches. Without patterns, every such address matches
+    #[clap(long, value_name = "ADDR", value_parser = reference_address)]
+    less_than: Option<String>,
+
+    /// Only match addresses sorting after this address, like --less-than
+    #[clap(long, value_name = "ADDR", value_parser = reference_address, conflicts_with = "less_than")]
+    greater_than: Option<String>,
+
+    /// Also require matches to satisfy this rhai expression, which sees the matching `address`
+    /// and the `target` pattern, e.g. 'address.ends_with("Q")'. It runs only for addresses
+    /// matching a pattern, in a sandbox with a bounded number of operations.
+    #[cfg(feature = "script")]
+    #[clap(long)]
+    script: Option<algo_vanity_rs::script::ScriptPredicate>,
+
+    /// Keep this many random seeds ready in a dedicated thread for the workers to take
+    #[clap(long, conflicts_with = "seed")]
+    seed_pool: Option<usize>,
+
+    /// Search for a pattern only within a window of characters, as START:END:PATTERN where the
+    /// window is the characters START..END of the address, e.g. 20:40:ALGO (may be given several times)
+    #[clap(long)]
+    window: Vec<PatternWindow>,
+
+    /// Also match addresses starting within this Hamming distance of a pattern, such as of a full
+    /// 58-character address. Compares every pattern in full against every address, which is slower
+    #[clap(long)]
+    max_distance: Option<usize>,
+
+    /// Upper bound of memory per worker thread, e.g. 4096, 64K or 1M. Smaller seed pools and
+    /// batches keep within it
+    #[clap(long, value_parser = parse_size)]
+    max_thread_memory: Option<usize>,
+
+    /// Report only the first pattern an address matches, instead of every one
+    #[clap(long, default_value_t = false)]
+    one_per_account: bool,
+
+    /// Best effort search climbing every prefix of each pattern, such as A, AL, ALG and ALGO,
+    /// retiring shorter prefixes as longer ones are found. Reports the longest prefix reached
+    /// within --duration or --attempts
+    #[clap(long, default_value_t = false)]
+    ladder: bool,
+
+    /// Match patterns against the reversed address, such that with the start placement ALGO
+    /// matches addresses ending in OGLA, reading backward from the end
+    #[clap(long, default_value_t = false, conflicts_with_all = ["window", "max_distance", "region"])]
+    reverse: bool,
+
+    /// Match patterns as case-insensitive regular expressions, such as ALGO[2-7]{3} or XY.*Z,
+    /// anchored to the start or end of the address with those placements
+    #[clap(long, default_value_t = false, conflicts_with_all = ["window", "max_distance", "region", "reverse", "ladder", "words"])]
+    regex: bool,
+
+    /// Start searching even if some patterns are effectively impossible to find
+    #[clap(long, default_value_t = false)]
+    force: bool,
+
+    /// Estimate difficulty by the bits each address character carries, also for patterns
+    /// reaching the end of the address with --placement anywhere
+    #[clap(long, default_value_t = false)]
+    include_checksum_aware: bool,
+
+    /// Only save matches scoring at least this quality (roughly the number of matched characters)
+    #[clap(long)]
+    min_quality: Option<f64>,
+
+    /// Only save matches whose 25 word mnemonic repeats no word, taking about 16% more attempts
+    #[clap(long, default_value_t = false, conflicts_with_all = ["no_secrets", "address_only"])]
+    unique_words: bool,
+
+    /// Stream matches as json lines to consumers connecting to a Unix domain socket at this path
+    #[clap(long)]
+    socket: Option<String>,
+
+    /// Write a json marker file here once the search completes by itself, by finding all --once
+    /// patterns or reaching its --attempts or --duration limit, for orchestrators to watch for
+    #[clap(long)]
+    completion_file: Option<String>,
+
+    /// Append a json line to this file for every --once pattern once it is found, with the
+    /// address, and the time and attempts it took to find, for pipelines tracking each pattern
+    #[clap(long, requires = "once")]
+    events_file: Option<String>,
+
+    /// Overwrite this json file every --status-interval with the current rate, counts, matches,
+    /// run time and remaining patterns, for dashboards and scripts to poll
+    #[clap(long)]
+    status_file: Option<String>,
+
+    /// Interval between updates of the --status-file
+    #[clap(long, value_parser = parse_duration, default_value = "1s", requires = "status_file")]
+    status_interval: Duration,
+
+    /// Append a one-line summary of each match to this log file
+    #[clap(long)]
+    match_log: Option<String>,
+
+    /// Refresh rate of the terminal ui in frames per second
+    #[clap(long, default_value_t = DEFAULT_TUI_FPS)]
+    tui_fps: u32,
+
+    /// Print plain progress lines instead of the terminal ui, for consoles where it misbehaves.
+    /// Selected automatically on legacy Windows consoles and when the output is not a terminal
+    #[clap(long, default_value_t = false)]
+    simple: bool,
+
+    /// Never touch the terminal, printing a line for each match and a progress line every
+    /// --progress-interval instead, for services, pipes and CI logs. Selected automatically when
+    /// the output is not a terminal
+    #[clap(long, alias = "no-tui", default_value_t = false, conflicts_with = "simple")]
+    headless: bool,
+
+    /// Interval between the progress lines of --headless output
+    #[clap(long, value_parser = parse_duration, default_value = "10s")]
+    progress_interval: Duration,
+
+    /// Render the terminal ui without colors or text styles, also when NO_COLOR is set
+    #[clap(long, default_value_t = false)]
+    no_color: bool,
+
+    /// Mark matches with a star in the terminal ui when they satisfy this rule: repeat:N for N
+    /// identical characters in a row, contains:TEXT or quality:Q (may be given several times)
+    #[clap(long = "interesting")]
+    interesting: Vec<InterestingRule>,
+
+    /// Display addresses in the terminal ui in groups of this many characters, e.g. 4
+    #[clap(long, value_parser = clap::value_parser!(u16).range(1..), value_name = "N")]
+    group_display: Option<u16>,
+
+    /// Quit the terminal ui right away, without asking for confirmation while matches are unsaved
+    #[clap(long, default_value_t = false)]
+    no_confirm: bool,
+
+    /// Mask mnemonics in the terminal ui, such as while screen sharing. Matches are still saved in full
+    #[clap(long, default_value_t = false)]
+    redact: bool,
+
+    /// Also mask the middle of addresses in the terminal ui, leaving the pattern and both ends visible
+    #[clap(long, default_value_t = false, requires = "redact")]
+    redact_addresses: bool,
+
+    /// Number formatting in the terminal ui, such as the digit grouping separator and magnitude words
+    #[clap(long, value_enum, default_value_t = NumberLocale::En)]
+    locale: NumberLocale,
+
+    /// Render the trailing characters of addresses derived from their checksum in a distinct style
+    #[clap(long, default_value_t = false)]
+    show_checksum: bool,
+
+    /// Also show each address lowercased next to it in the terminal ui, as some wallets display
+    /// them. Patterns are still matched against the canonical uppercase address
+    #[clap(long, default_value_t = false)]
+    show_lowercase: bool,
+
+    /// Show several matches per row in the terminal ui when it is wide enough
+    #[clap(long, default_value_t = false)]
+    multi_column: bool,
+
+    /// Highlight color of matches at the start of the address
+    #[clap(long, default_value_t = Color::Green)]
+    start_color: Color,
+
+    /// Highlight color of matches anywhere in the address
+    #[clap(long, default_value_t = Color::Yellow)]
+    anywhere_color: Color,
+
+    /// Highlight color of matches at the end of the address
+    #[clap(long, default_value_t = Color::Cyan)]
+    end_color: Color,
+
+    /// Key type to generate accounts with
+    #[clap(long, value_enum, default_value_t = KeyType::Ed25519)]
+    key_type: KeyType,
+
+    /// Seed the random number generators for a reproducible search (not secure)
+    #[clap(long)]
+    seed: Option<u64>,
+
+    /// Start the deterministic seed space at this batch, searching every --stride'th batch from it.
+    /// Machine k of N splitting a search uses offset k and stride N with the same --seed
+    #[clap(long, default_value_t = 0, requires = "seed")]
+    offset: u64,
+
+    /// Number of machines splitting the deterministic seed space of a search, see --offset
+    #[clap(long, default_value_t = 1, requires = "seed")]
+    stride: u64,
+
+    /// Seed the random number generators from the first 32 bytes per thread of this file, such as
+    /// one written from a hardware random number generator. Never reuse the file for another search
+    #[clap(long, conflicts_with_all = ["seed", "seed_pool"])]
+    entropy_file: Option<String>,
+
+    /// Store the account seed in each match, allowing it to be regenerated (sensitive)
+    #[clap(long, default_value_t = false, requires = "seed")]
+    record_seed: bool,
+
+    /// Store the attempts and time into the run at which each match was found, for analyzing
+    /// the distribution of find times offline
+    #[clap(long, default_value_t = false)]
+    record_discovery: bool,
+
+    /// Only save the addresses of matches, never their mnemonics
+    #[clap(long, default_value_t = false, conflicts_with = "record_seed")]
+    no_secrets: bool,
+
+    /// Only handle the addresses of matches, skipping all secrets for maximum throughput
+    #[clap(long, default_value_t = false, conflicts_with = "record_seed")]
+    address_only: bool,
+}
+
+// Command line arguments for placement of the pattern within the address
+#[derive(Args,Debug)]
+struct PlacementArgs {
+    /// Where to look for matches in the address
+    #[clap(long, value_enum, conflicts_with_all = ["start", "anywhere", "end"])]
+    placement: Option<PlacementMode>,
+
+    /// Look for match at start of address (deprecated, use --placement)
     #[clap(short, long, default_value_t = false)]
     start: bool,
 
-    /// Look for match anywhere in address
+    /// Look for match anywhere in address (deprecated, use --placement)
     #[clap(short, long, default_value_t = false)]
     anywhere: bool,
 
-    /// Look for match at end of address
+    /// Look for match at end of address (deprecated, use --placement)
     #[clap(short, long, default_value_t = false)]
     end: bool,
+}
 
-    /// File path for saving vanity addresses
+impl PlacementArgs {
+    /// Resolve the requested placement, defaulting to the start of the address
+    fn search_placement(&self) -> SearchPlacement {
+        if let Some(mode) = self.placement {
+            mode.into()
+        } else if !(self.start | self.anywhere | self.end) {
+            PlacementMode::Start.into()
+        } else {
+            SearchPlacement { start: self.start, anywhere: self.anywhere, end: self.end }
+        }
+    }
+}
+
+// Subcommands which do not start a search
+#[derive(Subcommand,Debug)]
+enum Command {
+    /// Estimate how many addresses must be generated to find a pattern with some probability
+    Plan(PlanArgs),
+    /// Check that each match in a results file is derived from its mnemonic and fits its pattern
+    Verify(VerifyArgs),
+    /// Print the matches found so far in a results file
+    ListFound(ListFoundArgs),
+    /// Print the versions of linked libraries and other build details for bug reports
+    VersionInfo,
+    /// Measure the search rate at several thread counts and recommend one for this machine
+    Tune(TuneArgs),
+    /// Run searches submitted over a minimal HTTP API
+    #[cfg(feature = "serve")]
+    Serve(ServeArgs),
+}
+
+#[derive(Args,Debug)]
+struct PlanArgs {
+    /// Vanity strings to estimate for
+    #[clap(num_args = 1..,required = true)]
+    vanities: Vec<String>,
+
+    #[command(flatten)]
+    placement: PlacementArgs,
+
+    /// Desired probability of having found a match, between 0 and 1
+    #[clap(short = 'P', long, default_value_t = 0.95)]
+    probability: f64,
+
+    /// Search rate in addresses per second, used to estimate the time required
     #[clap(short, long)]
-    path: Option<String>,
+    rate: Option<f64>,
 
-    /// Exit after finding each vanity pattern once
-    #[clap(short, long, default_value_t = false)]
-    once: bool
+    /// Estimate difficulty by the bits each address character carries, also for patterns
+    /// reaching the end of the address with --placement anywhere
+    #[clap(long, default_value_t = false)]
+    include_checksum_aware: bool,
+
+    /// Estimate for matches which also require the checksum characters to end with this suffix
+    #[clap(long)]
+    checksum_suffix: Option<ChecksumConstraint>,
+}
+
+#[derive(Args,Debug)]
+struct VerifyArgs {
+    /// Results file to verify
+    #[clap(default_value = DEFAULT_PATH)]
+    path: String,
+}
+
+#[derive(Args,Debug)]
+struct ListFoundArgs {
+    /// Results file to list
+    #[clap(default_value = DEFAULT_PATH)]
+    path: String,
+
+    /// Print the matches as an aligned table
+    #[clap(long, default_value_t = false)]
+    table: bool,
+
+    /// Leave out the mnemonics
+    #[clap(long, default_value_t = false)]
+    hide_mnemonics: bool,
+
+    /// Also show each address lowercased, as some wallets display them
+    #[clap(long, default_value_t = false)]
+    show_lowercase: bool,
+}
+
+#[derive(Args,Debug)]
+struct TuneArgs {
+    /// Time to measure each thread count for, long enough for several batches of accounts per thread
+    #[clap(long, value_parser = parse_duration, default_value = "5s")]
+    duration: Duration,
+
+    /// Highest thread count to measure (all logical threads by default)
+    #[clap(short, long)]
+    threads: Option<usize>,
+}
+
+#[cfg(feature = "serve")]
+#[derive(Args,Debug)]
+struct ServeArgs {
+    /// Address to listen on
+    #[clap(long, default_value = "127.0.0.1:8080")]
+    listen: String,
+
+    /// Number of threads (auto detects by default)
+    #[clap(short, long)]
+    threads: Option<usize>,
+
+    /// File path for saving vanity addresses
+    #[clap(short, long, default_value = DEFAULT_PATH)]
+    path: String,
 }
 
 fn main() {
 
     let mut args = Cli::parse();
 
+    if let Some(command) = args.command {
+        match command {
+            Command::Plan(plan_args) => plan(plan_args),
+            Command::Verify(verify_args) => verify(verify_args),
+            Command::ListFound(list_args) => list_found(list_args),
+            Command::Tune(tune_args) => tune(tune_args),
+            Command::VersionInfo => {
+                algo_vanity_rs::version_info().iter().for_each(|(key, value)| println!("{key:<14}{value}"))
+            },
+            #[cfg(feature = "serve")]
+            Command::Serve(serve_args) => serve(serve_args),
+        }
+        return
+    }
+
+    if args.benchmark_encoding {
+        println!("Measuring {BENCHMARK_ACCOUNTS} accounts on one thread...");
+        print!("{}", algo_vanity_rs::benchmark::encoding(BENCHMARK_ACCOUNTS).report());
+        return
+    }
+
+    // Only print sample addresses, their secrets are never kept
+    if let Some(count) = args.preview {
+        for address in sample_addresses(count, args.key_type) {
+            println!("{address}");
+        }
+        return
+    }
+
     // Check for realistic number of threads (fewer than MAX_THREADS)
     let num_threads = match args.threads {
         Some(t @ 1..=MAX_THREADS) => t, // Valid number of threads requested
@@ -126,287 +557,608 @@ fn main() {
         None => thread::available_parallelism().map_or(DEFAULT_THREADS, |t|t.get())
     };
     
+    // Check for sensible terminal ui refresh rate
+    let Some(tui_interval) = tui::refresh_interval(args.tui_fps) else {
+        println!("Error: User requested {} fps for the terminal ui, please select between 1 and {}", args.tui_fps, tui::MAX_FPS);
+        return
+    };
+
     // String representing path for saving vanities
-    let save_path = args.path.unwrap_or(DEFAULT_PATH.to_string());
+    let mut save_path = args.path.unwrap_or(DEFAULT_PATH.to_string());
 
-    // Default to searching in start if nothing is specified
-    if !(args.start | args.anywhere | args.end ) {
-        args.start = true;
+    // Keep files of concurrent runs apart by their run label
+    let run_label = args.run_label.map(|l| if l.is_empty() { random_run_label() } else { l });
+    if let Some(label) = &run_label {
+        save_path = labeled_path(&save_path, label);
+        args.match_log = args.match_log.map(|log| labeled_path(&log, label));
     }
 
-    // Collect search placement and inform user
-    let placement = SearchPlacement { start: args.start, anywhere: args.anywhere, end: args.end };
+    // Collect search placement, defaulting to the start of the address
+    let mut placement = args.placement.search_placement();
 
-    // Attempt to load first argument as json file
-    let file_name = args.vanities.first().expect("Clap struct entry 'vanities' is must contain one or more elements.");
-    if let Ok(file) = File::open(file_name) {
-        args.vanities = if let Ok(vanities_from_file) = serde_json::from_reader::<_,Vec<String>>(&file) { vanities_from_file }
-        else { println!("Error: Unable to parse file as valid JSON of correct format, e.g. [\"algo\",\"rand\"]"); return }
+    // Merge patterns from every pattern file argument with the literal patterns
+    let entries = match collect_pattern_entries(&args.vanities) {
+        Ok(entries) => entries,
+        Err(e) => { println!("Error: Unable to read pattern file: {e}"); return },
+    };
+
+    // Patterns with a placement of their own are searched for within the windows covering it
+    args.vanities.clear();
+    for entry in &entries {
+        if args.regex && entry.placement.is_some() {
+            println!("Error: Pattern {} can not have a placement of its own with --regex", entry.pattern);
+            return
+        }
+        match entry.windows() {
+            Ok(windows) if windows.is_empty() => args.vanities.push(entry.pattern.clone()),
+            Ok(windows) => args.window.extend(windows),
+            Err(e) => { println!("Error: Pattern {} can not be placed at {:?}: {e}", entry.pattern, entry.placement); return },
+        }
     }
 
-    // Ensure all patterns are upper-case
-    args.vanities.iter_mut().for_each(|s|{*s = s.to_uppercase()});
+    // Turn words into patterns, remembering the original words as labels
+    let mut labels = HashMap::new();
+    if args.words {
+        let (patterns, skipped);
+        (patterns, labels, skipped) = words_to_patterns(&args.vanities);
+        if skipped > 0 {
+            println!("Skipped {skipped} word(s) containing characters which can not exist in an Algorand Address");
+        }
+        if patterns.is_empty() && args.window.is_empty() { println!("Error: None of the words can appear in an Algorand Address"); return }
+        args.vanities = patterns;
+    }
 
-    // Ensure all patterns are valid
-    let mut invalid_patterns = false;
-    let allowed_chars = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
-    args.vanities.iter().for_each(|vanity|{vanity.chars().for_each(|c|{
-        if ! allowed_chars.contains(c) {
-            invalid_patterns = true;
-            println!("Pattern {vanity} contains '{c}' which can not exist in an Algorand Address")
+    // Ensure all patterns are upper-case and valid, or valid regexes which are kept as given
+    match args.regex {
+        true => if !validate_regexes(&args.vanities) { println!("Error: Exiting due to invalid pattern(s)"); return },
+        false => if !normalize_patterns(&mut args.vanities) { println!("Error: Exiting due to invalid pattern(s)"); return },
+    }
+    // Ordering alone matches every address at its side of the reference, as the empty pattern
+    let order = match (args.less_than.take(), args.greater_than.take()) {
+        (Some(reference), _) => Some(OrderConstraint { ordering: cmp::Ordering::Less, reference }),
+        (None, Some(reference)) => Some(OrderConstraint { ordering: cmp::Ordering::Greater, reference }),
+        (None, None) => None,
+    };
+    if order.is_some() && args.vanities.is_empty() && args.window.is_empty() {
+        args.vanities.push(String::new());
+        placement = PlacementMode::Start.into();
+    }
+    labels.extend(entries.into_iter().filter_map(|entry| {
+        let pattern = if args.regex { entry.pattern } else { entry.pattern.to_uppercase() };
+        Some((pattern, entry.label?))
+    }));
+
+    // Ensure every pattern leaves room for the region next to it
+    if let Some(region) = &args.region {
+        if let Some(vanity) = args.vanities.iter().find(|v| !region.fits(v)) {
+            println!("Error: Pattern {vanity} leaves no room for {} region characters in an Algorand Address", region.length);
+            return
         }
-    })});
-    if invalid_patterns { println!("Error: Exiting due to invalid pattern(s)"); return }
+    }
 
-    // Atomic boolean to keep worker threads alive
-    let keep_alive = Arc::new(AtomicBool::new(true));
+    if args.save_every == Some(0) { println!("Error: --save-every must be at least 1"); return }
+    if args.status_interval.is_zero() { println!("Error: Status interval must be positive"); return }
+    if args.max_match_rate.is_some_and(|rate| rate.is_nan() || rate <= 0.0) { println!("Error: Maximum match rate must be positive"); return }
+    if args.shards == Some(0) { println!("Error: User requested 0 shards, please select 1 or more"); return }
+    if !args.fields.is_empty() && std::path::Path::new(&save_path).exists() {
+        println!("Error: Results file {save_path} already exists, selecting --fields requires a new file");
+        return
+    }
+    if args.format == Some(ResultsFormat::Wallet) {
+        if args.no_secrets || args.address_only {
+            println!("Error: --format wallet saves the mnemonic of each account, which --no-secrets and --address-only do not keep");
+            return
+        }
+        if std::path::Path::new(&save_path).exists() {
+            println!("Error: Results file {save_path} already exists, --format wallet requires a new file");
+            return
+        }
+    }
+    if args.format == Some(ResultsFormat::Addresses) {
+        if !(args.no_secrets || args.address_only) {
+            println!("Error: --format addresses saves no mnemonics, add --no-secrets or --address-only to search for addresses only");
+            return
+        }
+        if std::path::Path::new(&save_path).exists() {
+            println!("Error: Results file {save_path} already exists, --format addresses requires a new file");
+            return
+        }
+    }
+
+    let partition = match Partition::new(args.offset, args.stride) {
+        Ok(partition) => partition,
+        Err(e) => { println!("Error: Invalid seed partition, {e}"); return },
+    };
 
-    // Initialize system state
-    let state = Arc::new(Mutex::new(GlobalState{
-        vanities: args.vanities.clone(),
+    // Ensure the entropy file can seed every thread before starting the search
+    if let Some(path) = &args.entropy_file {
+        if let Err(e) = read_entropy(path, num_threads) {
+            println!("Error: Unable to seed from entropy file {path}: {e}");
+            return
+        }
+    }
+
+    // Ensure the threads fit within their memory budget
+    if let Some(max) = args.max_thread_memory {
+        let required = thread_memory(&args.vanities);
+        if required > max {
+            println!("Error: Each thread requires at least {required} bytes, above the limit of {max} bytes");
+            return
+        }
+    }
+
+    // Ladders climb prefixes, and are searched as far as they get
+    if args.ladder && (!placement.start || placement.anywhere || placement.end) {
+        println!("Error: --ladder climbs the prefixes of patterns, which requires the start placement");
+        return
+    }
+
+    // Warn about patterns which would take practically forever to find
+    let model = difficulty_model(args.include_checksum_aware);
+    let factor = args.checksum_suffix.as_ref().map_or(1.0, |c| c.factor(model));
+    if let Some(checksum) = &args.checksum_suffix {
+        eprintln!("Note: Checksum suffix {} makes every match {} times rarer", checksum.suffix, difficulty::format_attempts(factor));
+    }
+    if let Some(order) = &order {
+        eprintln!("Note: About {:.1}% of addresses sort {} {}", order.probability() * 100.0, if order.ordering == cmp::Ordering::Less { "before" } else { "after" }, order.reference);
+    }
+    if !args.ladder && !args.regex && !patterns_are_practical(&args.vanities, &placement, model, factor) && !args.force {
+        println!("Error: Exiting due to impractical pattern(s), use --force to search anyway");
+        return
+    }
+
+    // Start searching
+    let vanities = args.vanities.clone();
+    let config = SearchConfig {
+        vanities: args.vanities,
+        labels,
         threads: num_threads,
-        placement: placement.clone(),
-        matches: Vec::new(),
-        search_rate: 0.0f32,
-        total_count: 0usize,
-        match_count: 0usize,
-        start_time: Instant::now(),
-        run_time: Duration::ZERO,
-        save_path: save_path.clone(),
-    }));
+        auto_threads: args.auto_threads,
+        placement,
+        save_path,
+        shards: args.shards,
+        fields: args.fields,
+        format: args.format,
+        rotate: args.rotate,
+        save_every: args.save_every,
+        save_interval: args.save_interval,
+        wipe_secrets: args.overwrite_secrets_on_exit,
+        run_label,
+        once: args.once,
+        candidates: args.candidates.into(),
+        duration: args.duration,
+        warm_up: args.warm_up,
+        attempts: args.attempts,
+        min_quality: args.min_quality,
+        unique_words: args.unique_words,
+        min_rate: args.min_rate,
+        min_rate_grace: args.min_rate_grace,
+        max_match_rate: args.max_match_rate,
+        match_log: args.match_log,
+        key_type: args.key_type,
+        seed: args.seed,
+        record_seed: args.record_seed,
+        record_discovery: args.record_discovery,
+        partition,
+        no_secrets: args.no_secrets,
+        address_only: args.address_only,
+        region: args.region,
+        checksum: args.checksum_suffix,
+        order,
+        difficulty: model,
+        #[cfg(feature = "script")]
+        script: args.script,
+        max_distance: args.max_distance,
+        windows: args.window,
+        seed_pool: args.seed_pool,
+        entropy_file: args.entropy_file,
+        max_thread_memory: args.max_thread_memory,
+        one_per_account: args.one_per_account,
+        ladder: args.ladder,
+        reverse: args.reverse,
+        regex: args.regex,
+        socket: args.socket,
+        completion_file: args.completion_file,
+        events_file: args.events_file,
+        status_file: args.status_file,
+        status_interval: args.status_interval,
+    };
 
-    // Configure and create threads
-    let thread_handles = {
-
-        // Setup communication channels between threads
-        let (tx_worker_msg,rx_worker_msg) = mpsc::channel::<WorkerMsg>();
-        let (tx_address_match,rx_address_match) = mpsc::channel::<AddressMatch>();
-
-        // Setup worker threads (num_threads of them)
-        let mut thread_handles:Vec<_> = (0..num_threads).map(|thread_id|{
-
-            let tx_worker_msg_clone = tx_worker_msg.clone();
-            let state_clone = state.clone();
-            let keep_alive_clone = keep_alive.clone();
-            let placement_clone = placement.clone();
-
-            thread::spawn(move || {
-                thread_worker(thread_id,tx_worker_msg_clone, state_clone, keep_alive_clone, placement_clone);
-                println!("Terminated thread [worker {}]",thread_id)
-            })
-        }).collect();
-
-        // Setup main loop thread
-        let keep_alive_clone = keep_alive.clone();
-        let state_clone = state.clone();
-        thread_handles.push(thread::spawn(move||{
-            thread_main_loop(rx_worker_msg, tx_address_match, state_clone, args.once, keep_alive_clone);
-            println!("Terminated thread [main loop]")
-        }));
-
-        // Setup file handler thread
-        let keep_alive_clone = keep_alive.clone();
-        thread_handles.push(thread::spawn(move||{
-            if let Err(e) = thread_file_handler(rx_address_match, save_path) {
+    let tui_config = tui::TuiConfig {
+        refresh_interval: tui_interval,
+        highlight: tui::PlacementColors {
+            start: args.start_color,
+            anywhere: args.anywhere_color,
+            end: args.end_color,
+        },
+        interesting: args.interesting,
+        multi_column: args.multi_column,
+        locale: args.locale,
+        difficulty: model,
+        confirm_quit: !args.no_confirm,
+        group_display: args.group_display.map(usize::from),
+        redact_mnemonics: args.redact,
+        redact_addresses: args.redact_addresses,
+        show_checksum: args.show_checksum,
+        show_lowercase: args.show_lowercase,
+        simple: args.simple || simple::terminal_ui_unsupported(),
+        headless: (args.headless || (!args.simple && simple::output_is_not_terminal())).then_some(args.progress_interval),
+        color: !args.no_color && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()),
+    };
+
+    if args.print_config {
+        print_config(&config, &tui_config);
+        return
+    }
+
+    if args.first {
+        first(config);
+        return
+    }
+
+    // Show how hard each pattern is before the search takes over the terminal
+    if !config.regex {
+        let estimates = model.pattern_estimates(&config.vanities, &config.placement, &config.windows, factor);
+        print!("{}", difficulty::estimates_table(&estimates, tui_config.locale));
+    }
+
+    let handle = VanitySearcher::new(config).start();
+
+    // Without the terminal ui to catch key presses, stop the search on interrupts
+    if tui_config.simple || tui_config.headless.is_some() {
+        let keep_alive = handle.keep_alive().clone();
+        if let Err(e) = ctrlc::set_handler(move || keep_alive.store(false, Ordering::Relaxed)) {
+            println!("Warning: Unable to handle interrupts: {e}");
+        }
+    }
+
+    // Setup user interface thread
+    let keep_alive_clone = handle.keep_alive().clone();
+    let state_clone = handle.state().clone();
+    let tui_handle = thread::spawn(move||{
+        if let Some(progress_interval) = tui_config.headless {
+            if let Err(e) = simple::headless(&state_clone, keep_alive_clone.clone(), tui_config.refresh_interval, progress_interval, tui_config.locale) {
                 keep_alive_clone.store(false,Ordering::Relaxed);
-                println!("Error: Unable to save vanity addresses to file: {}",e);
+                println!("Error: Unable to print progress: {}",e);
             }
-            println!("Terminated thread [file handler]")
-        }));
-
-        // Setup user interface thread
-        let keep_alive_clone = keep_alive.clone();
-        let state_clone = state.clone();
-        thread_handles.push(thread::spawn(move||{
-            // Wait for other threads to start
-            thread::sleep(Duration::from_millis(10));
-            if let Err(e) = tui::main(&state_clone, keep_alive_clone.clone()) {
+            return
+        }
+        if tui_config.simple {
+            if let Err(e) = simple::main(&state_clone, keep_alive_clone.clone(), tui_config.refresh_interval, tui_config.locale) {
                 keep_alive_clone.store(false,Ordering::Relaxed);
-                println!("Error: Unable to start terminal ui: {}",e);
+                println!("Error: Unable to print progress: {}",e);
             }
-            println!("Terminated thread [terminal ui]")
-        }));
+            return
+        }
 
-        // return thread handles
-        thread_handles
-    };
+        // Wait for other threads to start
+        thread::sleep(Duration::from_millis(10));
+        if let Err(e) = tui::main(&state_clone, keep_alive_clone.clone(), &tui_config) {
+            keep_alive_clone.store(false,Ordering::Relaxed);
+            println!("Error: Unable to start terminal ui: {}",e);
+        }
+        println!("Terminated thread [terminal ui]")
+    });
 
     // Wait for all threads to finish
-    for handle in thread_handles {
-        _ = handle.join();
+    let state = handle.state().clone();
+    _ = tui_handle.join();
+    handle.stop();
+    if !handle.join_timeout(SHUTDOWN_TIMEOUT) {
+        println!("Warning: Some threads did not finish within {} seconds, exiting anyway", SHUTDOWN_TIMEOUT.as_secs());
+        std::process::exit(1);
+    }
+    if let Ok(s) = state.lock() {
+        for name in &s.terminated_threads {
+            println!("Terminated thread [{name}]");
+        }
+        if s.completion == Some(Completion::AllFound) {
+            println!("{}", if args.ladder { "Reached the top of every ladder!" } else { "Found all vanity addresses!" });
+        }
+    }
+
+    // Report which patterns were found before running out of time
+    if let (Some(limit), Ok(s)) = (args.duration, state.lock()) {
+        if s.run_time >= limit {
+            println!("Time limit of {} reached", difficulty::format_duration(limit.as_secs_f64()));
+            if args.once {
+                let (remaining, found): (Vec<_>, Vec<_>) = vanities.iter().partition(|v| s.vanities.contains(v));
+                println!("Found:     {}", found.iter().map(|v| v.as_str()).collect::<Vec<_>>().join(", "));
+                println!("Not found: {}", remaining.iter().map(|v| v.as_str()).collect::<Vec<_>>().join(", "));
+            }
+        }
+    }
+
+    // Report the highest rung of each ladder
+    if let (true, Ok(s)) = (args.ladder, state.lock()) {
+        for vanity in &vanities {
+            match ladder_best(vanity, &s.matches) {
+                Some(m) => println!("Ladder {vanity}: reached {} of {} characters with {}", m.target.len(), vanity.len(), m.public),
+                None => println!("Ladder {vanity}: no prefix reached"),
+            }
+        }
+    }
+
+    // Explain why the search aborted itself
+    if let Some(reason) = state.lock().ok().and_then(|s| s.aborted.clone()) {
+        println!("Error: {reason}");
+    }
+
+    // Report the matches found within the attempt budget
+    if let (Some(limit), Ok(s)) = (args.attempts, state.lock()) {
+        if s.total_count >= limit {
+            println!("Attempt budget of {} reached, found {} match(es) in {} attempts",
+                limit.separate_with_commas(), s.match_count, s.total_count.separate_with_commas());
+        }
+    }
+
+    // Share the found addresses once every pattern was found
+    if let (true, Ok(s)) = (args.summary_card || args.summary_file.is_some(), state.lock()) {
+        if s.vanities.is_empty() {
+            let card = summary_card(&s.matches, s.run_time, s.total_count);
+            match &args.summary_file {
+                Some(path) => match std::fs::write(path, card) {
+                    Ok(()) => println!("Summary card written to {path}"),
+                    Err(e) => println!("Error: Unable to write summary card to {path}: {e}"),
+                },
+                None => print!("\n{card}\n"),
+            }
+        }
+    }
+
+    // Wipe the secrets of the search, which the file handler did for its own copies already
+    if let (true, Ok(mut s)) = (args.overwrite_secrets_on_exit, state.lock()) {
+        wipe_secrets(&mut s);
     }
 
     println!("All threads finished, exiting")
 }
 
-fn thread_main_loop(
-    rx_worker_msg: mpsc::Receiver<WorkerMsg>,
-    tx_address_match: mpsc::Sender<AddressMatch>,
-    state: Arc<Mutex<GlobalState>>,
-    find_only_once: bool,
-    keep_alive: Arc<AtomicBool>,
-) {
+/// Search for the first match of a single pattern and print its mnemonic, exiting with
+/// a nonzero code if no match was found, such as when interrupted or out of time
+fn first(config: SearchConfig) {
+    if config.vanities.len() != 1 {
+        eprintln!("Error: --first searches for exactly one pattern, got {}", config.vanities.len());
+        std::process::exit(2);
+    }
 
-    let mut rates = vec![0.0;state.lock().expect("Unable to lock mutex").threads];
-    while let Ok(msg) = rx_worker_msg.recv() {
+    let mut searcher = VanitySearcher::new(SearchConfig { once: true, ..config });
+    let matches = searcher.stream_matches();
+    let handle = searcher.start();
 
-        let mut state_mut = state.lock().expect("Unable to lock mutex");
+    let keep_alive = handle.keep_alive().clone();
+    if let Err(e) = ctrlc::set_handler(move || keep_alive.store(false, Ordering::Relaxed)) {
+        eprintln!("Warning: Unable to handle interrupts: {e}");
+    }
 
-        state_mut.run_time = Instant::now().duration_since(state_mut.start_time);
+    let found = matches.recv().ok();
+    handle.stop();
+    let finished = handle.join_timeout(SHUTDOWN_TIMEOUT);
 
-        match msg {
+    // A match is printed even if some threads hang, as it was saved before being streamed
+    match &found {
+        Some(m) => println!("{}", m.mnemonic),
+        None => eprintln!("Stopped before finding a match"),
+    }
+    if !finished {
+        eprintln!("Warning: Some threads did not finish within {} seconds, exiting anyway", SHUTDOWN_TIMEOUT.as_secs());
+    }
+    if found.is_none() || !finished {
+        std::process::exit(1);
+    }
+}
 
-            // Address match has been found
-            WorkerMsg::AddressMatch(address_match) => {
+/// Configuration resolved from all arguments, pattern files and environment, as printed by
+/// --print-config
+#[derive(Serialize)]
+struct ResolvedConfig<'a> {
+    #[serde(flatten)]
+    search: &'a SearchConfig,
+    format: ResultsFormat,
+    #[serde(flatten)]
+    tui: &'a tui::TuiConfig,
+}
 
-                state_mut.matches.push(address_match.clone());
+/// Print the configuration resolved from all arguments, pattern files and environment as json
+fn print_config(config: &SearchConfig, tui_config: &tui::TuiConfig) {
+    // The format is printed as given, or otherwise as implied by the extension of the results file
+    let format = config.format.or(ResultsFormat::from_extension(&config.save_path)).unwrap_or(ResultsFormat::Json);
+    match serde_json::to_string_pretty(&ResolvedConfig { search: config, format, tui: tui_config }) {
+        Ok(json) => println!("{json}"),
+        Err(e) => println!("Error: Unable to serialize configuration: {e}"),
+    }
+}
 
-                if find_only_once {
-                    if let Some(index) = state_mut.vanities.iter().position(|r| r == &address_match.target)  {
-                        state_mut.match_count += 1;
-                        _ = tx_address_match.send(address_match);
-                        let _removed = state_mut.vanities.remove(index);
-                        if state_mut.vanities.is_empty() {
-                            println!("Found all vanity addresses!");
-                            keep_alive.store(false,Ordering::Relaxed)
-                        }
-                    }
-                } else {
-                    state_mut.match_count += 1;
-                    _ = tx_address_match.send(address_match);
-                }
+/// Parse a duration given in seconds, or with an s, m, h or d suffix
+fn parse_duration(arg: &str) -> Result<Duration, String> {
+    let (number, unit) = match arg.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(index) => arg.split_at(index),
+        None => (arg, "s"),
+    };
+    let number: f64 = number.parse().map_err(|_| format!("invalid duration '{arg}'"))?;
+    let seconds = match unit {
+        "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 60.0 * 60.0,
+        "d" => number * 60.0 * 60.0 * 24.0,
+        _ => return Err(format!("unknown unit '{unit}', expected s, m, h or d")),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
+}
 
-            },
+/// Parse a number of bytes, optionally with a K, M or G suffix
+fn parse_size(arg: &str) -> Result<usize, String> {
+    let (number, multiplier) = match arg.char_indices().last() {
+        Some((i, 'K' | 'k')) => (&arg[..i], 1 << 10),
+        Some((i, 'M' | 'm')) => (&arg[..i], 1 << 20),
+        Some((i, 'G' | 'g')) => (&arg[..i], 1 << 30),
+        _ => (arg, 1),
+    };
+    let number: usize = number.parse().map_err(|_| format!("invalid size '{arg}'"))?;
+    number.checked_mul(multiplier).ok_or(format!("size '{arg}' is too large"))
+}
 
-            // Worker thread counting update
-            WorkerMsg::Count((id,duration)) => {
-                state_mut.total_count += COUNT_PER_LOOP * COUNT_PER_LOOP ;
-                rates[id] = (COUNT_PER_LOOP * COUNT_PER_LOOP) as f32 / duration.as_secs_f32();
-                state_mut.search_rate = state_mut.search_rate*0.95 + rates.iter().sum::<f32>()*0.05; // LP-filtered rate
-            },
+/// Convert patterns to upper-case and report any which can not exist in an address
+fn normalize_patterns(vanities: &mut [String]) -> bool {
+    vanities.iter_mut().for_each(|s|{*s = s.to_uppercase()});
+
+    let mut valid_patterns = true;
+    vanities.iter().for_each(|vanity|{vanity.chars().for_each(|c|{
+        if ! ADDRESS_CHARS.contains(c) {
+            valid_patterns = false;
+            println!("Pattern {vanity} contains '{c}' which can not exist in an Algorand Address")
+        }
+    })});
+    valid_patterns
+}
+
+fn validate_regexes(vanities: &[String]) -> bool {
+    let mut valid_patterns = true;
+    for vanity in vanities {
+        if let Err(e) = vanity.parse::<RegexPattern>() {
+            valid_patterns = false;
+            println!("Pattern {vanity} is not a valid regex for an Algorand Address: {e}");
         }
     }
+    valid_patterns
 }
 
-/// Threads to handle saving matches to json file
-fn thread_file_handler(
-    rx_address_match: mpsc::Receiver<AddressMatch>,
-    path: String
-) -> io::Result<()> {
-
-    // Load existing vanity json or create a new one
-    let mut matches = if let Ok(file) = File::open(&path) {
-        serde_json::from_reader(&file)?
-    } else {
-        let file = File::create(&path)?;
-        serde_json::to_writer(&file, &[0;0])?;
-        Vec::new()
-    };
+/// Convert words to their upper-case patterns, returning the patterns, a map from each
+/// pattern to its original word, and the number of words which can not appear in an address
+fn words_to_patterns(words: &[String]) -> (Vec<String>, HashMap<String, String>, usize) {
+    let mut patterns = Vec::new();
+    let mut labels = HashMap::new();
+    let mut skipped = 0;
+
+    for word in words {
+        let pattern = word.to_uppercase();
+        if pattern.is_empty() || !pattern.chars().all(|c| ADDRESS_CHARS.contains(c)) {
+            skipped += 1;
+        } else if !labels.contains_key(&pattern) {
+            labels.insert(pattern.clone(), word.clone());
+            patterns.push(pattern);
+        }
+    }
 
-    // Receive new address match, add it to vector and save to disk
-    while let Ok(message) = rx_address_match.recv() {
+    (patterns, labels, skipped)
+}
 
-        matches.push(message);
-        matches.append(& mut rx_address_match.try_iter().collect());
+/// Difficulty model selected by the `--include-checksum-aware` flag
+fn difficulty_model(checksum_aware: bool) -> DifficultyModel {
+    if checksum_aware { DifficultyModel::ChecksumAware } else { DifficultyModel::Uniform }
+}
 
-        if let Ok(json_message) = serde_json::to_string_pretty(&matches) {
-            let mut file = File::create(&path)?;
-            write!(file,"{}", json_message.as_str())?;
+/// Report patterns which are effectively impossible to find with the given placement, when
+/// every match is rarer by `factor`, such as due to a checksum suffix
+fn patterns_are_practical(vanities: &[String], placement: &SearchPlacement, model: DifficultyModel, factor: f64) -> bool {
+    let mut practical = true;
+    for vanity in vanities.iter().filter(|v| model.expected_attempts(v, placement) * factor > difficulty::IMPRACTICAL_ATTEMPTS) {
+        practical = false;
+        if placement.end && !placement.anywhere && difficulty::impossible_at_end(vanity) {
+            eprintln!("Warning: Pattern {vanity} can not end an address, as the last character only encodes 3 bits of the checksum");
+        } else {
+            eprintln!("Warning: Pattern {vanity} is expected to take more than {:e} attempts to find", difficulty::IMPRACTICAL_ATTEMPTS);
         }
     }
+    practical
+}
+
+/// Print the number of attempts (and optionally time) needed to find each pattern
+fn plan(mut args: PlanArgs) {
 
-    Ok(())
+    if !(args.probability > 0.0 && args.probability < 1.0) {
+        println!("Error: Probability must be between 0 and 1, got {}", args.probability);
+        return
+    }
+    if let Some(rate) = args.rate.filter(|r| *r <= 0.0) {
+        println!("Error: Search rate must be positive, got {rate}");
+        return
+    }
+    if !normalize_patterns(&mut args.vanities) { println!("Error: Exiting due to invalid pattern(s)"); return }
+
+    let placement = args.placement.search_placement();
+    let model = difficulty_model(args.include_checksum_aware);
+    println!("Placement: {placement}, probability: {}%", args.probability * 100.0);
+
+    let checksum = args.checksum_suffix.as_ref().map_or(1.0, |c| c.probability(model));
+    if let Some(constraint) = &args.checksum_suffix {
+        println!("Checksum suffix: {}, every match {} times rarer", constraint.suffix, difficulty::format_attempts(checksum.recip()));
+    }
+
+    for vanity in &args.vanities {
+        let probability = model.probability(vanity, &placement) * checksum;
+        let expected = probability.recip();
+        let attempts = difficulty::confidence_attempts(probability, args.probability);
+        println!();
+        println!("{vanity}");
+        println!("  Expected attempts: {}", difficulty::format_attempts(expected));
+        println!("  Required attempts: {}", difficulty::format_attempts(attempts));
+        if let Some(rate) = args.rate {
+            println!("  Required time:     {}", difficulty::format_duration(attempts / rate));
+        }
+    }
 }
 
-fn thread_worker(
-    thread_id: usize,
-    tx_worker_msg: mpsc::Sender<WorkerMsg>,
-    state: Arc<Mutex<GlobalState>>,
-    keep_alive: Arc<AtomicBool>,
-    placement: SearchPlacement
-) {
-    let mut prev_time = Instant::now();
-    let mut rng = thread_rng();
-    while keep_alive.load(Ordering::Relaxed) {
-
-        // This hack allows for only generating orders of magnitudes fewer random numbers.
-        // After generating the first seed, we generate two random numbers which represent
-        // two indeces of the seed. These indeces are counted up in the for loops to change
-        // the seed ever so slightly. For loops and counting is much faster than generating
-        // 32 new random numbers every time. The same perturbed seed is used COUNT_PER_LOOP^2
-        // times before a new seed is generated. By default this is 10_000 times.
-
-        let mut seed: [u8; 32] = rng.gen();
-        let index0 = rng.gen_range(0..32);
-        let index1 = rng.gen_range(0..32);
-        if index0 == index1 { continue } // Ensure indeces are different
-        let vanity_targets = if let Ok(s) = state.lock() { s.vanities.clone() } else { return };
-        let mut acc: Account;
-        for _ in 0..COUNT_PER_LOOP {
-            seed[index0] = seed[index0].wrapping_add(1);
-            for _ in 0..COUNT_PER_LOOP {
-                seed[index1] = seed[index1].wrapping_add(1);
-                acc = Account::from_seed(seed);
-                find_vanity(&tx_worker_msg, &vanity_targets, &acc, &placement);
-            }
+/// Re-derive every match in a results file and report those which do not check out
+fn verify(args: VerifyArgs) {
+
+    let matches = match load_matches(&args.path) {
+        Ok(matches) => matches,
+        Err(e) => { println!("Error: Unable to load results file {}: {e}", args.path); return },
+    };
+
+    let mut failed = 0;
+    for (index, m) in matches.iter().enumerate() {
+        if let Err(e) = verify_match(m) {
+            failed += 1;
+            println!("Match {index} ({} for {}): {e}", m.public, m.target);
         }
+    }
 
-        let current_time = Instant::now();
-        let duration = Instant::now().duration_since(prev_time);
-        prev_time = current_time;
-        _ = tx_worker_msg.send(WorkerMsg::Count((thread_id,duration)));
+    println!("Verified {} of {} matches in {}", matches.len() - failed, matches.len(), args.path);
+    if failed > 0 {
+        std::process::exit(1);
     }
 }
 
-fn find_vanity(
-    tx_worker_msg: &mpsc::Sender<WorkerMsg>,
-    vanity_targets: &Vec<String>,
-    acc: &Account,
-    placement: &SearchPlacement
-) {
-    let acc_string = acc.address().encode_string();
-    for target in vanity_targets {
-
-        let mut matched_start_end = false;
-
-        // Look for match at start of address
-        if placement.start && acc_string.starts_with(target) {
-            _ = tx_worker_msg.send(
-                WorkerMsg::AddressMatch(AddressMatch {
-                    target: target.clone(),
-                    public: acc_string.clone(),
-                    mnemonic: acc.mnemonic(),
-                    placement: Placement::Start
-                })
-            );
-            matched_start_end = true;
-        }
+/// Print the contents of a results file
+fn list_found(args: ListFoundArgs) {
+    match load_matches(&args.path) {
+        Ok(matches) if matches.is_empty() => println!("No matches found in {} yet", args.path),
+        Ok(matches) => print!("{}", render_matches(&matches, args.table, !args.hide_mnemonics, args.show_lowercase)),
+        Err(e) => println!("Error: Unable to load results file {}: {e}", args.path),
+    }
+}
 
-        // Look for match at end of address
-        if placement.end && acc_string.ends_with(target) {
-            _ = tx_worker_msg.send(
-                WorkerMsg::AddressMatch(AddressMatch {
-                    target: target.clone(),
-                    public: acc_string.clone(),
-                    mnemonic: acc.mnemonic(),
-                    placement: Placement::End
-                })
-            );
-            matched_start_end = true;
-        }
+/// Measure the search rate at several thread counts and print them with a recommendation
+fn tune(args: TuneArgs) {
+    let logical = args.threads.unwrap_or_else(|| thread::available_parallelism().map_or(DEFAULT_THREADS, |t| t.get())).clamp(1, MAX_THREADS);
+    let counts = algo_vanity_rs::benchmark::tune_thread_counts(logical);
+    println!("Measuring {} thread counts for {} each...", counts.len(), difficulty::format_duration(args.duration.as_secs_f64()));
+
+    let tuning = algo_vanity_rs::benchmark::tune_with(&counts, |threads| {
+        algo_vanity_rs::benchmark::search_rate(threads, args.duration)
+    });
+    print!("{}", tuning.table());
+    if let Some(best) = tuning.recommended() {
+        println!("Recommended: --threads {}", best.threads);
+    }
+}
 
-        // Look for match anywhere in address
-        if !matched_start_end && placement.anywhere {
-            if let Some(index) = acc_string.find(target) {
-                _ = tx_worker_msg.send(
-                    WorkerMsg::AddressMatch(AddressMatch {
-                        target: target.clone(),
-                        public: acc_string.clone(),
-                        mnemonic: acc.mnemonic(),
-                        placement: Placement::Anywhere(index)
-                    })
-                );
-            }
-        }
+/// Serve the HTTP API until it fails
+#[cfg(feature = "serve")]
+fn serve(args: ServeArgs) {
+    let listener = match std::net::TcpListener::bind(&args.listen) {
+        Ok(listener) => listener,
+        Err(e) => { println!("Error: Unable to listen on {}: {e}", args.listen); return },
     };
+    println!("Serving search API on {}", args.listen);
+
+    let mut config = SearchConfig { save_path: args.path, ..SearchConfig::default() };
+    if let Some(threads) = args.threads {
+        config.threads = threads.clamp(1, MAX_THREADS);
+    }
+    if let Err(e) = algo_vanity_rs::serve::serve(listener, config) {
+        println!("Error: Search API stopped: {e}");
+    }
 }
@@ -0,0 +1,106 @@
+//! Constraints on how addresses sort relative to a reference address
+
+use std::cmp::Ordering;
+
+use algo_rust_sdk::Address;
+use serde::{Serialize, Serializer};
+
+use crate::ADDRESS_CHARS;
+
+/// Leading characters of the reference which the probability is estimated from, beyond which
+/// further characters are below the precision of an `f64`
+const PROBABILITY_CHARS: usize = 13;
+
+/// Requires addresses to sort before or after a reference address, comparing their encoded
+/// strings lexicographically, in addition to matching a pattern.
+///
+/// Strings compare by their bytes, so the digits `2` to `7` sort before the letters. Addresses
+/// are uniformly random, so about the share of addresses at that side of the reference satisfy
+/// the constraint, see [`OrderConstraint::probability`]. Combining it with a pattern, such as
+/// a prefix, makes matches rarer. The two are not independent, as a prefix sorting before the
+/// reference already sorts the whole address before it.
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use algo_vanity_rs::ordering::{OrderConstraint, reference_address};
+///
+/// let address = algo_vanity_rs::accounts().next().unwrap().address().encode_string();
+/// let reference = reference_address(&address.to_lowercase()).unwrap();
+/// let less = OrderConstraint { ordering: Ordering::Less, reference: reference.clone() };
+/// let greater = OrderConstraint { ordering: Ordering::Greater, reference };
+///
+/// assert!(less.satisfied(&"2".repeat(58)) && !greater.satisfied(&"2".repeat(58)));
+/// assert!(greater.satisfied(&"Z".repeat(58)) && !less.satisfied(&"Z".repeat(58)));
+/// assert!(!less.satisfied(&less.reference) && !greater.satisfied(&greater.reference));
+/// ```
+#[derive(Clone,Debug,PartialEq,Serialize)]
+pub struct OrderConstraint {
+    /// How addresses have to compare to the reference, `Less` or `Greater`
+    #[serde(serialize_with = "serialize_ordering")]
+    pub ordering: Ordering,
+    /// Upper-case reference address
+    pub reference: String,
+}
+
+impl OrderConstraint {
+    /// Whether the address compares to the reference with the ordering
+    pub fn satisfied(&self, address: &str) -> bool {
+        address.cmp(self.reference.as_str()) == self.ordering
+    }
+
+    /// Probability that a random address satisfies the constraint, which is the share of
+    /// addresses sorting before the reference, or after it
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use algo_vanity_rs::ordering::OrderConstraint;
+    ///
+    /// // Addresses starting with N or later sort after the 6 digits and the letters A to M
+    /// let reference = format!("N{}", "2".repeat(57));
+    /// let less = OrderConstraint { ordering: Ordering::Less, reference: reference.clone() };
+    /// let greater = OrderConstraint { ordering: Ordering::Greater, reference };
+    /// assert_eq!(less.probability(), 0.59375);
+    /// assert_eq!(greater.probability(), 0.40625);
+    /// ```
+    pub fn probability(&self) -> f64 {
+        let mut sorted: Vec<char> = ADDRESS_CHARS.chars().collect();
+        sorted.sort_unstable();
+        let base = sorted.len() as f64;
+
+        let before: f64 = self.reference.chars().take(PROBABILITY_CHARS).enumerate()
+            .map(|(i, c)| sorted.iter().position(|s| *s == c).unwrap_or(0) as f64 / base.powi(i as i32 + 1))
+            .sum();
+        match self.ordering {
+            Ordering::Less => before,
+            _ => 1.0 - before,
+        }
+    }
+}
+
+/// Serialize the ordering by the name of its side, `less` or `greater`
+fn serialize_ordering<S: Serializer>(ordering: &Ordering, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(match ordering {
+        Ordering::Less => "less",
+        Ordering::Equal => "equal",
+        Ordering::Greater => "greater",
+    })
+}
+
+/// Upper-case reference address, if it is a valid address including its checksum
+///
+/// ```
+/// use algo_vanity_rs::ordering::reference_address;
+///
+/// let address = algo_vanity_rs::accounts().next().unwrap().address().encode_string();
+/// assert_eq!(reference_address(&address.to_lowercase()), Ok(address.clone()));
+///
+/// // Addresses with a wrong checksum or length are rejected
+/// let last = if address.ends_with('A') { "Q" } else { "A" };
+/// assert!(reference_address(&format!("{}{last}", &address[..57])).is_err());
+/// assert!(reference_address("ALGO").is_err());
+/// ```
+pub fn reference_address(s: &str) -> Result<String, String> {
+    let reference = s.to_uppercase();
+    Address::from_string(&reference).map_err(|e| format!("invalid reference address: {e}"))?;
+    Ok(reference)
+}
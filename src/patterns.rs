@@ -0,0 +1,119 @@
+//! Structured pattern files, giving each pattern settings of its own
+
+use std::{io, path::Path};
+
+use serde::Deserialize;
+
+use crate::{PlacementMode, difficulty::ADDRESS_LENGTH, window::PatternWindow};
+
+/// A pattern with its own settings, from a structured pattern file or given as is
+#[derive(Clone,Debug,Default,PartialEq,Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PatternEntry {
+    /// Pattern to search for
+    pub pattern: String,
+    /// Where to look for this pattern, instead of the placement of the search
+    #[serde(default)]
+    pub placement: Option<PlacementMode>,
+    /// Patterns with a higher priority are listed first, such as to be the one reported
+    /// when reporting only the first pattern an address matches
+    #[serde(default)]
+    pub priority: i64,
+    /// Label reported with matches of this pattern
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl PatternEntry {
+    /// Entry of a pattern without settings of its own
+    pub fn new(pattern: impl Into<String>) -> Self {
+        PatternEntry { pattern: pattern.into(), ..PatternEntry::default() }
+    }
+
+    /// Windows to search the pattern in for its own placement, if it has one. Each placement
+    /// is searched as the window covering it, such as `0:4` for a 4 character pattern at the start.
+    ///
+    /// ```
+    /// use algo_vanity_rs::{PlacementMode, patterns::PatternEntry};
+    ///
+    /// let entry = |placement| PatternEntry { placement, ..PatternEntry::new("algo") };
+    /// let windows = |placement| entry(placement).windows().unwrap().iter()
+    ///     .map(|w| format!("{}:{}:{}", w.start, w.end, w.pattern)).collect::<Vec<_>>();
+    ///
+    /// assert!(windows(None).is_empty());
+    /// assert_eq!(windows(Some(PlacementMode::Start)), ["0:4:ALGO"]);
+    /// assert_eq!(windows(Some(PlacementMode::StartEnd)), ["0:4:ALGO", "54:58:ALGO"]);
+    /// assert_eq!(windows(Some(PlacementMode::Any)), ["0:58:ALGO"]);
+    /// assert!(PatternEntry { placement: Some(PlacementMode::End), ..PatternEntry::new("al0o") }.windows().is_err());
+    /// ```
+    pub fn windows(&self) -> Result<Vec<PatternWindow>, String> {
+        let len = self.pattern.len();
+        let ranges = match self.placement {
+            None => return Ok(Vec::new()),
+            Some(PlacementMode::Start) => vec![(0, len)],
+            Some(PlacementMode::End) => vec![(ADDRESS_LENGTH.saturating_sub(len), ADDRESS_LENGTH)],
+            Some(PlacementMode::StartEnd) => vec![(0, len), (ADDRESS_LENGTH.saturating_sub(len), ADDRESS_LENGTH)],
+            Some(PlacementMode::Anywhere | PlacementMode::Any) => vec![(0, ADDRESS_LENGTH)],
+        };
+        ranges.into_iter().map(|(start, end)| format!("{start}:{end}:{}", self.pattern).parse()).collect()
+    }
+}
+
+/// Entries of a structured pattern file, either patterns as is or with settings
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawEntry {
+    Pattern(String),
+    Entry(PatternEntry),
+}
+
+/// Contents of a structured pattern file, listing its entries under `patterns`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PatternFile {
+    patterns: Vec<RawEntry>,
+}
+
+/// Formats of structured pattern files
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum PatternFormat {
+    Yaml,
+    Toml,
+}
+
+impl PatternFormat {
+    /// Format of a structured pattern file from its extension, if it is one
+    pub fn from_extension(path: &str) -> Option<Self> {
+        match Path::new(path).extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "yaml" | "yml" => Some(PatternFormat::Yaml),
+            "toml" => Some(PatternFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// Parse the entries of a structured pattern file
+    ///
+    /// ```
+    /// use algo_vanity_rs::{PlacementMode, patterns::PatternFormat};
+    ///
+    /// let toml = r#"
+    ///     patterns = ["algo", { pattern = "rand", placement = "end", priority = 2, label = "Rand" }]
+    /// "#;
+    /// let entries = PatternFormat::Toml.parse(toml).unwrap();
+    /// assert_eq!(entries[0].pattern, "algo");
+    /// assert_eq!(entries[1].placement, Some(PlacementMode::End));
+    /// assert_eq!(entries[1].label.as_deref(), Some("Rand"));
+    ///
+    /// assert!(PatternFormat::Yaml.parse("patterns:\n  - pattern: algo\n    colour: red\n").is_err());
+    /// ```
+    pub fn parse(self, contents: &str) -> io::Result<Vec<PatternEntry>> {
+        let file: PatternFile = match self {
+            PatternFormat::Yaml => serde_yaml::from_str(contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            PatternFormat::Toml => toml::from_str(contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        };
+        Ok(file.patterns.into_iter().map(|entry| match entry {
+            RawEntry::Pattern(pattern) => PatternEntry::new(pattern),
+            RawEntry::Entry(entry) => entry,
+        }).collect())
+    }
+}
@@ -0,0 +1,168 @@
+//! Patterns given as regular expressions, in place of literal substrings
+
+use std::str::FromStr;
+
+use regex::{Regex, RegexBuilder};
+use regex_syntax::ast::{self, Ast, ClassSet, ClassSetItem};
+
+use crate::{ADDRESS_CHARS, Placement, SearchPlacement};
+
+/// A pattern matched as a case-insensitive regular expression, anchored to the start or end of
+/// addresses for those placements.
+///
+/// Every character the expression spells out, as a literal or within a character class, has to
+/// exist in an address. The wildcard `.` and negated classes such as `[^A]` stand for any address
+/// character. Named classes such as `\d` or `[[:digit:]]` are rejected, as are expressions which
+/// match the empty string, and so every address.
+///
+/// ```
+/// use algo_vanity_rs::{Placement, SearchPlacement, regex_pattern::RegexPattern};
+///
+/// let address = "XALGO234YZ";
+/// let anywhere = SearchPlacement { start: false, anywhere: true, end: false };
+/// let start_end = SearchPlacement { start: true, anywhere: false, end: true };
+///
+/// let algo: RegexPattern = "algo[2-7]{3}".parse().unwrap();
+/// assert_eq!(algo.find(address, &anywhere), [(Placement::Anywhere(1), String::from("ALGO234"))]);
+/// assert!(algo.find(address, &start_end).is_empty());
+///
+/// // Among matches starting at the same index, the longest one is reported
+/// let alternation: RegexPattern = "A|AL".parse().unwrap();
+/// assert_eq!(alternation.find(address, &anywhere), [(Placement::Anywhere(1), String::from("AL"))]);
+///
+/// let ends: RegexPattern = "X.*Z".parse().unwrap();
+/// assert_eq!(ends.find(address, &start_end), [(Placement::Start, String::from(address)), (Placement::End, String::from(address))]);
+///
+/// // Characters which can not exist in an address are rejected
+/// assert!("ALGO[0-9]".parse::<RegexPattern>().is_err());
+/// assert!(r"ALGO\d".parse::<RegexPattern>().is_err());
+/// assert!("A*".parse::<RegexPattern>().is_err());
+/// assert!("ALGO(".parse::<RegexPattern>().is_err());
+/// ```
+#[derive(Clone,Debug)]
+pub struct RegexPattern {
+    /// Expression as given
+    pub source: String,
+    start: Regex,
+    end: Regex,
+    anywhere: Regex,
+    whole: Regex,
+}
+
+impl RegexPattern {
+    /// Every match of the expression in the address with the given placement, with the text it
+    /// matched. Like literal patterns, a match at the start or end takes the place of one
+    /// anywhere, which is the leftmost match, and the longest among those starting there, see
+    /// [`crate::find_anywhere`].
+    pub fn find(&self, address: &str, placement: &SearchPlacement) -> Vec<(Placement, String)> {
+        let mut matches = Vec::new();
+        if placement.start {
+            matches.extend(self.start.find(address).map(|m| (Placement::Start, self.longest(address, m.start(), m.end()))));
+        }
+        if placement.end {
+            matches.extend(self.end.find(address).map(|m| (Placement::End, m.as_str().to_string())));
+        }
+        if matches.is_empty() && placement.anywhere {
+            matches.extend(self.anywhere.find(address).map(|m| (Placement::Anywhere(m.start()), self.longest(address, m.start(), m.end()))));
+        }
+        matches
+    }
+
+    /// Longest match starting at `start`, given a match from `start` to `end`. The regex crate
+    /// prefers earlier alternatives rather than longer ones, so `A|AB` would otherwise match
+    /// only `A` of `AB`.
+    fn longest(&self, address: &str, start: usize, end: usize) -> String {
+        (end..=address.len()).rev()
+            .map(|end| &address[start..end])
+            .find(|text| self.whole.is_match(text))
+            .unwrap_or(&address[start..end])
+            .to_string()
+    }
+}
+
+/// Compile an expression matching case-insensitively
+fn compile(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern).case_insensitive(true).build().map_err(|e| format!("invalid regex: {e}"))
+}
+
+/// Character of a literal in the expression which can not exist in an address, if any
+fn foreign_char(c: char) -> Option<char> {
+    (!ADDRESS_CHARS.contains(c.to_ascii_uppercase())).then_some(c)
+}
+
+/// Check that every character an expression spells out can exist in an address
+fn check_ast(ast: &Ast) -> Result<(), String> {
+    match ast {
+        Ast::Literal(literal) => match foreign_char(literal.c) {
+            Some(c) => Err(format!("pattern character '{c}' can not exist in an Algorand Address")),
+            None => Ok(()),
+        },
+        Ast::ClassBracketed(class) => check_class(class),
+        Ast::ClassPerl(_) | Ast::ClassUnicode(_) => Err(String::from("named character classes can match characters outside an Algorand Address, list the characters instead")),
+        Ast::Repetition(repetition) => check_ast(&repetition.ast),
+        Ast::Group(group) => check_ast(&group.ast),
+        Ast::Alternation(alternation) => alternation.asts.iter().try_for_each(check_ast),
+        Ast::Concat(concat) => concat.asts.iter().try_for_each(check_ast),
+        Ast::Empty(_) | Ast::Flags(_) | Ast::Dot(_) | Ast::Assertion(_) => Ok(()),
+    }
+}
+
+/// Check the characters of a bracketed class, unless it is negated and so a wildcard
+fn check_class(class: &ast::ClassBracketed) -> Result<(), String> {
+    match class.negated {
+        true => Ok(()),
+        false => check_class_set(&class.kind),
+    }
+}
+
+fn check_class_set(set: &ClassSet) -> Result<(), String> {
+    match set {
+        ClassSet::Item(item) => check_class_item(item),
+        ClassSet::BinaryOp(op) => {
+            check_class_set(&op.lhs)?;
+            check_class_set(&op.rhs)
+        },
+    }
+}
+
+fn check_class_item(item: &ClassSetItem) -> Result<(), String> {
+    let foreign = match item {
+        ClassSetItem::Literal(literal) => foreign_char(literal.c),
+        ClassSetItem::Range(range) => (range.start.c..=range.end.c).find_map(foreign_char),
+        ClassSetItem::Bracketed(class) => return check_class(class),
+        ClassSetItem::Union(union) => return union.items.iter().try_for_each(check_class_item),
+        ClassSetItem::Ascii(_) | ClassSetItem::Perl(_) | ClassSetItem::Unicode(_) => {
+            return Err(String::from("named character classes can match characters outside an Algorand Address, list the characters instead"))
+        },
+        ClassSetItem::Empty(_) => None,
+    };
+    match foreign {
+        Some(c) => Err(format!("pattern character '{c}' can not exist in an Algorand Address")),
+        None => Ok(()),
+    }
+}
+
+impl FromStr for RegexPattern {
+    type Err = String;
+
+    /// Validate and compile the expression for each placement
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ast = ast::parse::Parser::new().parse(s).map_err(|e| format!("invalid regex: {e}"))?;
+        check_ast(&ast)?;
+
+        let hir = regex_syntax::hir::translate::Translator::new().translate(s, &ast).map_err(|e| format!("invalid regex: {e}"))?;
+        match hir.properties().minimum_len() {
+            Some(0) => return Err(format!("regex '{s}' matches the empty string, which every address contains")),
+            None => return Err(format!("regex '{s}' can not match any address")),
+            Some(_) => {},
+        }
+
+        Ok(RegexPattern {
+            source: s.to_string(),
+            start: compile(&format!("^(?:{s})"))?,
+            end: compile(&format!("(?:{s})$"))?,
+            anywhere: compile(s)?,
+            whole: compile(&format!("^(?:{s})$"))?,
+        })
+    }
+}
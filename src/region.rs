@@ -0,0 +1,79 @@
+//! Constraints on the characters next to a matched pattern
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::{ADDRESS_CHARS, Placement, difficulty::ADDRESS_LENGTH};
+
+/// Requires the characters adjacent to a match to all come from a set of characters.
+///
+/// For matches at the start or anywhere in the address the characters following the
+/// pattern are checked, and for matches at the end the characters preceding it.
+///
+/// ```
+/// use algo_vanity_rs::{Placement, region::RegionConstraint};
+///
+/// let digits: RegionConstraint = "digits:4".parse().unwrap();
+/// assert!(digits.satisfied("ALGO2345XYZ", "ALGO", &Placement::Start));
+/// assert!(!digits.satisfied("ALGO234XYZ", "ALGO", &Placement::Start));
+/// assert!(digits.satisfied("XY7777ALGO", "ALGO", &Placement::End));
+/// assert!(digits.satisfied("XALGO2345", "ALGO", &Placement::Anywhere(1)));
+/// assert!(!digits.satisfied("XALGO234", "ALGO", &Placement::Anywhere(1)));
+///
+/// let vowels: RegionConstraint = "AEIOU:2".parse().unwrap();
+/// assert!(vowels.satisfied("ALGOEA", "ALGO", &Placement::Start));
+/// assert!("digits:0".parse::<RegionConstraint>().is_err());
+/// assert!("digits".parse::<RegionConstraint>().is_err());
+/// assert!("019:2".parse::<RegionConstraint>().is_err());
+/// ```
+#[derive(Clone,Debug,Serialize)]
+pub struct RegionConstraint {
+    /// Characters allowed in the region
+    pub chars: String,
+    /// Number of characters in the region
+    pub length: usize,
+}
+
+impl RegionConstraint {
+    /// Whether the region next to the target at the given placement only has allowed characters
+    pub fn satisfied(&self, address: &str, target: &str, placement: &Placement) -> bool {
+        let region = match placement {
+            Placement::Start | Placement::Near(_) => address.get(target.len()..target.len() + self.length),
+            Placement::Anywhere(index) | Placement::Window { index, .. } => address.get(index + target.len()..index + target.len() + self.length),
+            Placement::End => address.len().checked_sub(target.len() + self.length)
+                .and_then(|start| address.get(start..start + self.length)),
+        };
+        region.is_some_and(|region| region.chars().all(|c| self.chars.contains(c)))
+    }
+
+    /// Whether the pattern and its region fit within an address
+    pub fn fits(&self, target: &str) -> bool {
+        target.len() + self.length <= ADDRESS_LENGTH
+    }
+}
+
+impl FromStr for RegionConstraint {
+    type Err = String;
+
+    /// Parse `CLASS:LENGTH`, where the class is `digits`, `letters` or the allowed characters
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (class, length) = s.rsplit_once(':').ok_or(format!("expected CLASS:LENGTH, got '{s}'"))?;
+        let length: usize = length.parse().map_err(|_| format!("invalid region length '{length}'"))?;
+        if length == 0 || length >= ADDRESS_LENGTH {
+            return Err(format!("region length must be between 1 and {}", ADDRESS_LENGTH - 1));
+        }
+
+        let chars = match class {
+            "digits" => ADDRESS_CHARS.chars().filter(|c| c.is_ascii_digit()).collect(),
+            "letters" => ADDRESS_CHARS.chars().filter(|c| c.is_ascii_alphabetic()).collect(),
+            chars => chars.to_uppercase(),
+        };
+        if chars.is_empty() { return Err(String::from("region characters can not be empty")) }
+        if let Some(c) = chars.chars().find(|c| !ADDRESS_CHARS.contains(*c)) {
+            return Err(format!("region character '{c}' can not exist in an Algorand Address"));
+        }
+
+        Ok(RegionConstraint { chars, length })
+    }
+}
@@ -0,0 +1,74 @@
+//! Experimental scaling of the number of active worker threads
+
+/// Adds worker threads one at a time for as long as each one raises the aggregate search
+/// rate by at least a minimum relative gain, then settles. The thread which did not pay
+/// off is removed again.
+///
+/// ```
+/// use algo_vanity_rs::scaling::ThreadScaler;
+///
+/// // A machine on which the rate stops growing beyond 4 threads
+/// let rate = |threads: usize| 1000.0 * threads.min(4) as f32;
+///
+/// let mut scaler = ThreadScaler::new(1, 16, 0.05);
+/// while !scaler.is_settled() {
+///     scaler.observe(rate(scaler.active()));
+/// }
+/// assert_eq!(scaler.active(), 4);
+///
+/// // Never beyond the maximum number of threads
+/// let mut scaler = ThreadScaler::new(1, 3, 0.05);
+/// while !scaler.is_settled() {
+///     scaler.observe(rate(scaler.active()));
+/// }
+/// assert_eq!(scaler.active(), 3);
+/// ```
+#[derive(Clone,Debug)]
+pub struct ThreadScaler {
+    active: usize,
+    max: usize,
+    min_gain: f32,
+    previous_rate: Option<f32>,
+    settled: bool,
+}
+
+impl ThreadScaler {
+    /// Start with `start` active threads, adding up to `max` while the relative gain is above `min_gain`
+    pub fn new(start: usize, max: usize, min_gain: f32) -> Self {
+        let active = start.clamp(1, max.max(1));
+        ThreadScaler { active, max, min_gain, previous_rate: None, settled: active >= max }
+    }
+
+    /// Number of threads which should be active
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Whether the number of active threads will no longer change
+    pub fn is_settled(&self) -> bool {
+        self.settled
+    }
+
+    /// Register the aggregate rate measured with the current number of active threads,
+    /// returning the number of threads which should be active next
+    pub fn observe(&mut self, rate: f32) -> usize {
+        if self.settled { return self.active }
+
+        if let Some(previous) = self.previous_rate {
+            if rate < previous * (1.0 + self.min_gain) {
+                self.active -= 1;
+                self.settled = true;
+                return self.active
+            }
+        }
+
+        if self.active >= self.max {
+            self.settled = true;
+            return self.active
+        }
+
+        self.previous_rate = Some(rate);
+        self.active += 1;
+        self.active
+    }
+}
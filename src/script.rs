@@ -0,0 +1,84 @@
+//! Custom match predicates written as rhai script expressions
+
+use std::{fmt, str::FromStr, sync::Arc};
+
+use rhai::{AST, Engine, Scope, module_resolvers::DummyModuleResolver};
+use serde::Serialize;
+
+/// Operations a predicate may take for a single address before it is cut off, which bounds the
+/// time it can add to the search of every matching address
+pub const SCRIPT_MAX_OPERATIONS: u64 = 10_000;
+
+/// Longest string a predicate may build, in characters
+const SCRIPT_MAX_STRING_SIZE: usize = 1024;
+
+/// Requires matches to also satisfy a predicate, a rhai expression evaluating to a boolean.
+///
+/// The expression sees the matching `address` and the `target` pattern it matched. It runs in
+/// a sandbox, without access to files or modules. Being a single expression without loops or
+/// closures, its time is bounded by its length, and it may take at most [`SCRIPT_MAX_OPERATIONS`]
+/// operations per address. Predicates which fail, run out of operations, or evaluate to anything
+/// but a boolean reject the address.
+///
+/// ```
+/// use algo_vanity_rs::script::ScriptPredicate;
+///
+/// let predicate: ScriptPredicate = r#"address.ends_with("E") && address.contains("Q")"#.parse().unwrap();
+/// assert!(predicate.satisfied("ALGOQAAE", "ALGO"));
+/// assert!(!predicate.satisfied("ALGOQAAA", "ALGO"));
+///
+/// // Predicates have to evaluate to a boolean, and can not loop or define closures
+/// assert!(!"address.len()".parse::<ScriptPredicate>().unwrap().satisfied("ALGO", "ALGO"));
+/// assert!("address.starts_with(".parse::<ScriptPredicate>().is_err());
+/// assert!("address.to_chars().all(|c| c != '0')".parse::<ScriptPredicate>().is_err());
+/// ```
+#[derive(Clone,Serialize)]
+pub struct ScriptPredicate {
+    /// Source of the expression
+    pub source: String,
+    #[serde(skip)]
+    engine: Arc<Engine>,
+    #[serde(skip)]
+    ast: Arc<AST>,
+}
+
+impl ScriptPredicate {
+    /// Whether the address matching the target satisfies the predicate
+    pub fn satisfied(&self, address: &str, target: &str) -> bool {
+        let mut scope = Scope::new();
+        scope.push_constant("address", address.to_string());
+        scope.push_constant("target", target.to_string());
+        self.engine.eval_ast_with_scope::<bool>(&mut scope, &self.ast).unwrap_or(false)
+    }
+}
+
+/// Engine which can not reach outside the script, nor print over the terminal ui
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine.set_max_operations(SCRIPT_MAX_OPERATIONS);
+    engine.set_max_string_size(SCRIPT_MAX_STRING_SIZE);
+    engine.set_max_array_size(SCRIPT_MAX_STRING_SIZE);
+    engine.set_max_map_size(SCRIPT_MAX_STRING_SIZE);
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+    engine
+}
+
+impl FromStr for ScriptPredicate {
+    type Err = String;
+
+    /// Compile the expression
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let engine = sandboxed_engine();
+        let ast = engine.compile_expression(s).map_err(|e| format!("invalid script: {e}"))?;
+        Ok(ScriptPredicate { source: s.to_string(), engine: Arc::new(engine), ast: Arc::new(ast) })
+    }
+}
+
+impl fmt::Debug for ScriptPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptPredicate").field("source", &self.source).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,828 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    thread::{self, JoinHandle},
+    time::{Instant, Duration},
+    sync::{Arc,mpsc,atomic::{AtomicBool, AtomicUsize, Ordering}, Mutex},
+};
+
+use crate::{
+    AddressMatch, FileOptions, GlobalState, Partition, RateFloor, SeedPool, LoopOptions, Placement, SearchPlacement, WorkerFault, WorkerMsg, WorkerOptions,
+    ADDRESS_CHARS, COUNT_PER_LOOP, AUTO_THREADS_MIN_GAIN, AUTO_THREADS_START, DEFAULT_MIN_RATE_GRACE, DEFAULT_PATH, DEFAULT_STATUS_INTERVAL, DEFAULT_THREADS, WORKER_TIMEOUT, scaling::ThreadScaler, format::{MatchField, ResultsFormat}, difficulty::{self, DifficultyModel}, keys::KeyType, region::RegionConstraint, checksum::ChecksumConstraint, ordering::OrderConstraint, regex_pattern::RegexPattern, window::PatternWindow, stats::{SearchStats, StatsCallback, thread_stats, write_status_file},
+    cancel_pattern, capped_batch_len, load_matches, read_entropy, matched_prefix, prefix_ladder, serialize_opt_secs, serialize_secs, supervise, thread_file_handler, write_completion_file, thread_main_loop, thread_memory, thread_worker,
+};
+use algo_rust_sdk::account::Account;
+use serde::Serialize;
+
+/// Interval for checking whether threads have finished while joining with a timeout
+const JOIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Configuration of a vanity search. New fields may be added in future versions, each with a
+/// default, so set only the fields of interest and fill in the rest from [`SearchConfig::default`].
+///
+/// ```
+/// use algo_vanity_rs::SearchConfig;
+///
+/// let config = SearchConfig { vanities: vec!["ALGO".into()], threads: 2, ..SearchConfig::default() };
+/// assert_eq!(config.vanities, ["ALGO"]);
+/// ```
+#[derive(Clone,Debug,Serialize)]
+pub struct SearchConfig {
+    /// Upper-case vanity patterns to search for
+    #[serde(rename = "patterns")]
+    pub vanities: Vec<String>,
+    /// Labels reported with matches of a pattern, such as the word it was derived from
+    pub labels: HashMap<String, String>,
+    /// Number of worker threads
+    pub threads: usize,
+    /// Experimental: start with fewer threads, adding more up to `threads` while they raise the rate
+    pub auto_threads: bool,
+    /// Places in the address to look for the patterns
+    pub placement: SearchPlacement,
+    /// File path for saving matches
+    pub save_path: String,
+    /// Route matches to this many files by a hash of their pattern instead of the one file at
+    /// `save_path`, see [`crate::shard_of`], with an index of the file of each pattern
+    pub shards: Option<usize>,
+    /// Save only these fields of each match, in this order, or every field if empty. Results
+    /// files missing fields can not be loaded again, such as for resuming.
+    pub fields: Vec<MatchField>,
+    /// Format of new results files, instead of the one implied by the extension of `save_path`
+    #[serde(skip)]
+    pub format: Option<ResultsFormat>,
+    /// Start new results files, labeled with the time they were started at, after each
+    /// interval of this long, leaving the earlier files in place
+    #[serde(rename = "rotate_secs", serialize_with = "serialize_opt_secs")]
+    pub rotate: Option<Duration>,
+    /// Save matches once this many are waiting to be saved, rather than each one right away
+    pub save_every: Option<usize>,
+    /// Save matches once the oldest of them has waited this long, whichever of this and
+    /// `save_every` comes first, capping how often the results file is written
+    #[serde(rename = "save_interval_secs", serialize_with = "serialize_opt_secs")]
+    pub save_interval: Option<Duration>,
+    /// Overwrite the secrets of the matches held by the results file handler with zeros once the
+    /// search ends. Those in the [`GlobalState`] are left for the embedder to read, and can be
+    /// wiped with [`crate::wipe_secrets`] once done.
+    #[serde(rename = "overwrite_secrets_on_exit")]
+    pub wipe_secrets: bool,
+    /// Stop looking for each pattern after it has been found once
+    pub once: bool,
+    /// Number of matches to find for each pattern with `once`, to choose from
+    pub candidates: usize,
+    /// Stop searching after this much time has passed
+    #[serde(rename = "duration_secs", serialize_with = "serialize_opt_secs")]
+    pub duration: Option<Duration>,
+    /// Show the unfiltered rate for this long after starting, measuring the steady-state
+    /// rate only after thread scheduling and CPU frequency scaling have settled
+    #[serde(rename = "warm_up_secs", serialize_with = "serialize_opt_secs")]
+    pub warm_up: Option<Duration>,
+    /// Stop searching after generating this many accounts across all threads. Workers
+    /// finish their current batch, so a few more accounts may be generated.
+    pub attempts: Option<usize>,
+    /// Identifier of this run, shown alongside its statistics
+    pub run_label: Option<String>,
+    /// Only keep matches with at least this [`crate::difficulty::quality`]
+    pub min_quality: Option<f64>,
+    /// Only keep matches whose mnemonic repeats no word, see [`crate::has_unique_words`].
+    /// Requires mnemonics, so it keeps no matches at all with `no_secrets` or `address_only`.
+    pub unique_words: bool,
+    /// Abort the search if its filtered rate stays below this many addresses per second for
+    /// `min_rate_grace`, such as on a throttled cloud instance. Not checked while warming up.
+    pub min_rate: Option<f32>,
+    /// How long the rate may stay below `min_rate` before aborting
+    #[serde(rename = "min_rate_grace_secs", serialize_with = "serialize_secs")]
+    pub min_rate_grace: Duration,
+    /// Keep at most this many matches per second, counting but dropping the rest, for a steady
+    /// trickle of matches of easy patterns, see [`crate::MatchRateCap`]
+    pub max_match_rate: Option<f64>,
+    /// Optional file to append a human-readable line per match to
+    pub match_log: Option<String>,
+    /// Key type to generate accounts with
+    pub key_type: KeyType,
+    /// Seed for a reproducible (not secure) search
+    pub seed: Option<u64>,
+    /// Store the account seed in each match
+    pub record_seed: bool,
+    /// Record the attempts and time of the run when each match was found, see
+    /// [`AddressMatch::found_after_attempts`], for analyzing the distribution of find times
+    pub record_discovery: bool,
+    /// Share of the deterministic seed space to search, for splitting a search with a `seed`
+    /// across machines. Not used without a `seed`.
+    #[serde(flatten)]
+    pub partition: Partition,
+    /// Seed the random number generators of the workers from the bytes of this file, see
+    /// [`crate::read_entropy`]. The search aborts if the file holds too few bytes for all
+    /// threads. Not used with a `seed`.
+    pub entropy_file: Option<String>,
+    /// Never derive or save mnemonics, only addresses
+    pub no_secrets: bool,
+    /// Send only the addresses of matches from the workers, the lightest and fastest path
+    pub address_only: bool,
+    /// Characters required next to each match
+    pub region: Option<RegionConstraint>,
+    /// Also require the checksum characters of matching addresses to end with a suffix
+    pub checksum: Option<ChecksumConstraint>,
+    /// Also require matching addresses to sort before or after a reference address
+    pub order: Option<OrderConstraint>,
+    /// Model of the expected attempts to find each pattern, see [`GlobalState::expected_attempts`]
+    pub difficulty: DifficultyModel,
+    /// Also require matches to satisfy a rhai script predicate, see [`crate::script::ScriptPredicate`]
+    #[cfg(feature = "script")]
+    pub script: Option<crate::script::ScriptPredicate>,
+    /// Also match addresses whose start is within this Hamming distance of a pattern, such as
+    /// of a full 58-character address. Every pattern is compared in full against every
+    /// address, which slows down the search, especially with many or long patterns.
+    pub max_distance: Option<usize>,
+    /// Keep this many fresh random seeds ready in a dedicated thread, see [`crate::SeedPool`].
+    /// Not used with a `seed` or `entropy_file`.
+    pub seed_pool: Option<usize>,
+    /// Patterns searched for only within a window of characters, in addition to `vanities`
+    pub windows: Vec<PatternWindow>,
+    /// Upper bound of bytes each worker thread may use, which shrinks the seed pool and the
+    /// batches of accounts between count updates to fit, see [`crate::capped_batch_len`]
+    pub max_thread_memory: Option<usize>,
+    /// Unix domain socket to stream matches to local consumers over, as json lines
+    pub socket: Option<String>,
+    /// Write a json marker file here once the search completes by itself, by finding every
+    /// `once` pattern or reaching its attempt budget or time limit, but not when stopped
+    pub completion_file: Option<String>,
+    /// Append a json line to this file for every `once` pattern once its match is saved, with
+    /// the address completing it and the time and attempts it took, see [`crate::PatternCompletion`]
+    pub events_file: Option<String>,
+    /// Overwrite this json file with a snapshot of the statistics every `status_interval`, such
+    /// as the rate, counts and remaining patterns, for dashboards to poll
+    pub status_file: Option<String>,
+    /// Interval between updates of the `status_file`
+    #[serde(rename = "status_interval_secs", serialize_with = "serialize_secs")]
+    pub status_interval: Duration,
+    /// Report only the first pattern an account matches, instead of all of them
+    pub one_per_account: bool,
+    /// Climb the prefix ladder of each pattern, see [`crate::prefix_ladder`], retiring every
+    /// prefix of a pattern once a longer one is found. Meant for the start placement.
+    pub ladder: bool,
+    /// Match the patterns against the reversed address, such that a pattern at the start reads
+    /// backward from the end of the address. Matches record where the reversed pattern is in the
+    /// address as is, see [`AddressMatch::matched_range`]. The `windows`, `max_distance` and
+    /// `region` settings are left out of reversed searches.
+    pub reverse: bool,
+    /// Match the patterns as case-insensitive regular expressions, see
+    /// [`crate::regex_pattern::RegexPattern`]. The search aborts if any of them is invalid. The
+    /// `windows`, `max_distance`, `region`, `ladder` and `reverse` settings are left out of
+    /// regex searches.
+    pub regex: bool,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            vanities: Vec::new(),
+            labels: HashMap::new(),
+            threads: thread::available_parallelism().map_or(DEFAULT_THREADS, |t|t.get()),
+            auto_threads: false,
+            placement: SearchPlacement { start: true, anywhere: false, end: false },
+            save_path: DEFAULT_PATH.to_string(),
+            shards: None,
+            fields: Vec::new(),
+            format: None,
+            rotate: None,
+            save_every: None,
+            save_interval: None,
+            wipe_secrets: false,
+            once: false,
+            candidates: 1,
+            duration: None,
+            warm_up: None,
+            attempts: None,
+            run_label: None,
+            min_quality: None,
+            unique_words: false,
+            min_rate: None,
+            min_rate_grace: DEFAULT_MIN_RATE_GRACE,
+            max_match_rate: None,
+            match_log: None,
+            key_type: KeyType::default(),
+            seed: None,
+            record_seed: false,
+            record_discovery: false,
+            partition: Partition::default(),
+            entropy_file: None,
+            no_secrets: false,
+            address_only: false,
+            region: None,
+            checksum: None,
+            order: None,
+            difficulty: DifficultyModel::default(),
+            #[cfg(feature = "script")]
+            script: None,
+            max_distance: None,
+            windows: Vec::new(),
+            seed_pool: None,
+            max_thread_memory: None,
+            socket: None,
+            completion_file: None,
+            events_file: None,
+            status_file: None,
+            status_interval: DEFAULT_STATUS_INTERVAL,
+            one_per_account: false,
+            ladder: false,
+            reverse: false,
+            regex: false,
+        }
+    }
+}
+
+/// A configured vanity search which has not been started yet
+pub struct VanitySearcher {
+    config: SearchConfig,
+    prior_matches: Vec<AddressMatch>,
+    tx_stream: Option<mpsc::Sender<AddressMatch>>,
+    stats_callback: Option<(Duration, StatsCallback)>,
+    dry_run: bool,
+    fault: Option<WorkerFault>,
+    worker_timeout: Duration,
+}
+
+impl VanitySearcher {
+    /// Create a new search from the given configuration
+    pub fn new(config: SearchConfig) -> Self {
+        VanitySearcher { config, prior_matches: Vec::new(), tx_stream: None, stats_callback: None, dry_run: false, fault: None, worker_timeout: WORKER_TIMEOUT }
+    }
+
+    /// Resume a search from a results file, carrying forward its matches.
+    ///
+    /// The patterns and placement are recovered from the saved matches, and new matches are
+    /// saved to the same file. The configuration can be adjusted with [`Self::config_mut`].
+    pub fn from_state(path: &str) -> io::Result<Self> {
+        let prior_matches = load_matches(path)?;
+
+        let mut config = SearchConfig {
+            save_path: path.to_string(),
+            placement: SearchPlacement { start: false, anywhere: false, end: false },
+            ..SearchConfig::default()
+        };
+
+        for m in &prior_matches {
+            if !config.vanities.contains(&m.target) {
+                config.vanities.push(m.target.clone());
+            }
+            if let Some(label) = &m.label {
+                config.labels.entry(m.target.clone()).or_insert_with(|| label.clone());
+            }
+            match m.placement {
+                Placement::Start => config.placement.start = true,
+                Placement::Anywhere(_) => config.placement.anywhere = true,
+                Placement::End => config.placement.end = true,
+                Placement::Near(distance) => config.max_distance = config.max_distance.max(Some(distance)),
+                Placement::Window { start, end, .. } => {
+                    let window = PatternWindow { start, end, pattern: m.target.clone() };
+                    if !config.windows.contains(&window) { config.windows.push(window) }
+                },
+            }
+        }
+
+        if prior_matches.is_empty() {
+            config.placement.start = true;
+        }
+
+        Ok(VanitySearcher { config, prior_matches, tx_stream: None, stats_callback: None, dry_run: false, fault: None, worker_timeout: WORKER_TIMEOUT })
+    }
+
+    /// Configuration the search will be started with
+    pub fn config(&self) -> &SearchConfig {
+        &self.config
+    }
+
+    /// Mutable access to the configuration before starting the search
+    pub fn config_mut(&mut self) -> &mut SearchConfig {
+        &mut self.config
+    }
+
+    /// Matches carried forward from a previous search
+    pub fn prior_matches(&self) -> &[AddressMatch] {
+        &self.prior_matches
+    }
+
+    /// Receive every match as it is saved to the results file. The receiver is
+    /// disconnected once the search has stopped.
+    pub fn stream_matches(&mut self) -> mpsc::Receiver<AddressMatch> {
+        let (tx, rx) = mpsc::channel();
+        self.tx_stream = Some(tx);
+        rx
+    }
+
+    /// Call `callback` with a snapshot of the statistics of the search every `interval`, and
+    /// once more after the search has stopped, such as to render progress in a ui of your own.
+    ///
+    /// The callback runs on a thread of its own, which holds no lock while calling it, so it
+    /// never blocks the search. A slow callback only delays the snapshots after it. Anything
+    /// it shares with other threads must be synchronized, such as by sending the snapshots
+    /// over a channel.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use algo_vanity_rs::{SearchConfig, VanitySearcher};
+    ///
+    /// let mut searcher = VanitySearcher::new(SearchConfig { vanities: vec![String::from("ALGO")], ..SearchConfig::default() });
+    /// searcher.on_stats(Duration::from_secs(1), |stats| {
+    ///     println!("{} addresses at {:.0}/s, {} matches", stats.total_count, stats.search_rate, stats.match_count);
+    /// });
+    /// searcher.start().join();
+    /// ```
+    pub fn on_stats(&mut self, interval: Duration, callback: impl FnMut(&SearchStats) + Send + 'static) {
+        self.stats_callback = Some((interval, Box::new(callback)));
+    }
+
+    /// Run the search without generating any accounts, such that the workers only go through
+    /// the locking and messaging around each batch, see [`crate::benchmark::coordination`]
+    pub(crate) fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Make the first worker to finish a batch run into `fault`, and replace workers which stop
+    /// reporting for `worker_timeout` rather than the default
+    #[cfg(test)]
+    fn inject_fault(mut self, fault: WorkerFault, worker_timeout: Duration) -> Self {
+        self.fault = Some(fault);
+        self.worker_timeout = worker_timeout;
+        self
+    }
+
+    /// Search until the first match is found, then stop the search and return the match. The
+    /// match is saved to the results file like any other. Fails if the search stops without
+    /// finding a match, such as when it runs out of time or the results file can not be written.
+    pub fn find_one(mut self) -> io::Result<AddressMatch> {
+        self.config.once = true;
+        self.config.candidates = 1;
+
+        let matches = self.stream_matches();
+        let handle = self.start();
+        let found = matches.recv();
+        handle.stop();
+        handle.join();
+
+        found.map_err(|_| io::Error::other("search stopped without finding a match"))
+    }
+
+    /// Spawn the worker, main loop and file handler threads
+    pub fn start(self) -> SearchHandle {
+
+        let VanitySearcher { mut config, prior_matches, tx_stream, stats_callback, dry_run, fault, worker_timeout } = self;
+
+        // Regex searches only support matching whole expressions
+        if config.regex {
+            config.windows.clear();
+            config.max_distance = None;
+            config.region = None;
+            config.ladder = false;
+            config.reverse = false;
+        }
+
+        // Ladders search every prefix of their patterns, longest first such as to be reported
+        // first, except for those reached before
+        if config.ladder {
+            let mut rungs: Vec<String> = Vec::new();
+            for rung in config.vanities.iter().flat_map(|v| prefix_ladder(v)) {
+                if !rungs.contains(&rung) { rungs.push(rung) }
+            }
+            rungs.sort_by_key(|rung| std::cmp::Reverse(rung.len()));
+            rungs.retain(|rung| !prior_matches.iter().any(|m| m.target.starts_with(rung.as_str())));
+            config.vanities = rungs;
+        }
+
+        // Reversed searches only support matching whole patterns
+        if config.reverse {
+            config.windows.clear();
+            config.max_distance = None;
+            config.region = None;
+        }
+
+        // Windowed patterns are tracked along with the others, such as for finding them once
+        for window in &config.windows {
+            if !config.vanities.contains(&window.pattern) {
+                config.vanities.push(window.pattern.clone());
+            }
+        }
+
+        // Patterns which were already found do not need to be searched for again
+        if config.once {
+            config.vanities.retain(|v| prior_matches.iter().filter(|m| &m.target == v).count() < config.candidates);
+        }
+
+        // Estimate the attempts to find each pattern, which a checksum suffix multiplies
+        let factor = config.checksum.as_ref().map_or(1.0, |c| c.factor(config.difficulty));
+        let expected_attempts = match config.regex {
+            true => HashMap::new(),
+            false => config.difficulty.pattern_estimates(&config.vanities, &config.placement, &config.windows, factor).into_iter().collect(),
+        };
+
+        // Threads beyond the active ones wait until automatic scaling activates them
+        let scaler = config.auto_threads.then(|| ThreadScaler::new(AUTO_THREADS_START, config.threads, AUTO_THREADS_MIN_GAIN));
+        let active_threads = Arc::new(AtomicUsize::new(scaler.as_ref().map_or(config.threads, |s| s.active())));
+
+        // Seeds from an entropy file stand in for the secure random number generator, so rather
+        // than fall back to it, the search aborts when the file can not seed every thread
+        let entropy = config.entropy_file.as_ref().filter(|_| config.seed.is_none())
+            .map(|path| read_entropy(path, config.threads).map_err(|e| format!("Unable to seed from entropy file {path}: {e}")))
+            .transpose();
+        let (entropy, entropy_error) = match entropy {
+            Ok(entropy) => (entropy, None),
+            Err(e) => (None, Some(e)),
+        };
+
+        // Likewise, rather than search for some of the patterns, the search aborts when any
+        // regex is invalid
+        let regexes = config.vanities.iter().filter(|_| config.regex)
+            .map(|v| v.parse::<RegexPattern>().map_err(|e| format!("Invalid regex pattern {v}: {e}")))
+            .collect::<Result<Vec<_>, _>>();
+        let (regexes, setup_error) = match regexes {
+            Ok(regexes) => (regexes, entropy_error),
+            Err(e) => (Vec::new(), entropy_error.or(Some(e))),
+        };
+
+        // Collect settings for worker threads
+        let (seed_pool_len, batch_len) = buffer_lens(&config);
+        let worker_options = WorkerOptions {
+            placement: config.placement.clone(),
+            key_type: config.key_type,
+            seed: config.seed,
+            record_seed: config.record_seed,
+            partition: config.partition,
+            seed_pool: seed_pool_len.filter(|_| config.seed.is_none() && entropy.is_none()).map(SeedPool::spawn),
+            batch_len,
+            entropy,
+            one_per_account: config.one_per_account,
+            no_secrets: config.no_secrets,
+            address_only: config.address_only,
+            region: config.region.clone(),
+            checksum: config.checksum.clone(),
+            order: config.order.clone(),
+            #[cfg(feature = "script")]
+            script: config.script.clone(),
+            max_distance: config.max_distance,
+            windows: config.windows.clone(),
+            regexes,
+            reverse: config.reverse,
+            dry_run,
+            active_threads: active_threads.clone(),
+            fault: fault.map(|fault| Arc::new(Mutex::new(Some(fault)))),
+        };
+
+        // Atomic boolean to keep worker threads alive
+        let keep_alive = Arc::new(AtomicBool::new(!config.vanities.is_empty() && setup_error.is_none()));
+
+        // Carry forward the best prefix of each pattern, such that only new records are milestones
+        let mut best_prefix = HashMap::new();
+        for m in &prior_matches {
+            let best = best_prefix.entry(m.target.clone()).or_insert(0);
+            *best = matched_prefix(m).max(*best);
+        }
+
+        // Initialize system state
+        let state = Arc::new(Mutex::new(GlobalState{
+            vanities: config.vanities.clone(),
+            labels: config.labels.clone(),
+            threads: config.threads,
+            active_threads: active_threads.load(Ordering::Relaxed),
+            placement: config.placement.clone(),
+            match_count: prior_matches.len(),
+            worker_restarts: 0,
+            unsaved: 0,
+            matches: prior_matches,
+            search_rate: 0.0f32,
+            warming_up: config.warm_up.is_some(),
+            total_count: 0usize,
+            start_time: Instant::now(),
+            run_time: Duration::ZERO,
+            save_path: config.save_path.clone(),
+            run_label: config.run_label.clone(),
+            save_error: None,
+            anywhere_offsets: vec![0; difficulty::ADDRESS_LENGTH],
+            best_prefix,
+            expected_attempts,
+            milestones: Vec::new(),
+            double_vanities: HashSet::new(),
+            completion: None,
+            aborted: setup_error,
+            pattern_completions: Vec::new(),
+            terminated_threads: Vec::new(),
+        }));
+
+        // Setup communication channels between threads
+        let (tx_worker_msg,rx_worker_msg) = mpsc::channel::<WorkerMsg>();
+        let (tx_address_match,rx_address_match) = mpsc::channel::<AddressMatch>();
+
+        // Setup worker threads (config.threads of them), which the main loop also uses to
+        // replace workers which got stuck
+        let spawn_worker = {
+            let tx_worker_msg = tx_worker_msg.clone();
+            let state = state.clone();
+            let keep_alive = keep_alive.clone();
+            move |thread_id: usize| {
+
+                let tx_worker_msg_clone = tx_worker_msg.clone();
+                let state_clone = state.clone();
+                let keep_alive_clone = keep_alive.clone();
+                let worker_options_clone = worker_options.clone();
+
+                thread::spawn(move || {
+                    // A panicking worker is replaced by a fresh one, keeping the number of threads
+                    let work = || thread_worker(thread_id, tx_worker_msg_clone.clone(), state_clone.clone(), keep_alive_clone.clone(), worker_options_clone.clone());
+                    supervise(&keep_alive_clone, work, || {
+                        if let Ok(mut s) = state_clone.lock() { s.worker_restarts += 1 }
+                    });
+                    terminated(&state_clone, format!("worker {thread_id}"));
+                })
+            }
+        };
+        let mut thread_handles:Vec<_> = (0..config.threads).map(&spawn_worker).collect();
+
+        // Setup threads reporting periodic statistics to the embedder and the status file, which
+        // report once more when the main loop drops its ends of their channels
+        let mut stats_consumers: Vec<(Duration, StatsCallback)> = stats_callback.into_iter().collect();
+        if let Some(path) = config.status_file.clone() {
+            let mut failing = false;
+            stats_consumers.push((config.status_interval, Box::new(move |stats: &SearchStats| {
+                // Only report the first of consecutive failures, as the stats arrive every interval
+                let result = write_status_file(&path, stats);
+                if let (Err(e), false) = (&result, failing) {
+                    eprintln!("Error: Unable to write status file {path}: {e}");
+                }
+                failing = result.is_err();
+            })));
+        }
+        let mut tx_done = Vec::new();
+        for (interval, callback) in stats_consumers {
+            let (tx, rx_done) = mpsc::channel::<()>();
+            tx_done.push(tx);
+            let state_clone = state.clone();
+            thread_handles.push(thread::spawn(move||{
+                thread_stats(state_clone.clone(), rx_done, interval, callback);
+                terminated(&state_clone, String::from("stats"));
+            }));
+        }
+
+        // Setup main loop thread
+        let keep_alive_clone = keep_alive.clone();
+        let state_clone = state.clone();
+        let loop_options = LoopOptions {
+            find_only_once: config.once,
+            candidates: config.candidates,
+            time_limit: config.duration,
+            warm_up: config.warm_up,
+            attempt_limit: config.attempts,
+            scaler,
+            active_threads,
+            min_quality: config.min_quality,
+            unique_words: config.unique_words,
+            ladder: config.ladder,
+            reverse: config.reverse,
+            record_discovery: config.record_discovery,
+            rate_floor: config.min_rate.map(|floor| RateFloor::new(floor, config.min_rate_grace)),
+            match_rate_cap: config.max_match_rate,
+            worker_timeout,
+        };
+        thread_handles.push(thread::spawn(move||{
+            // Replacements are not joined, as the main loop runs until every worker has stopped
+            let respawn = move |thread_id| drop(spawn_worker(thread_id));
+            thread_main_loop(rx_worker_msg, tx_address_match, state_clone.clone(), loop_options, keep_alive_clone, respawn);
+            drop(tx_done);
+            terminated(&state_clone, String::from("main loop"));
+        }));
+
+        // Setup socket thread streaming saved matches to local consumers
+        let mut tx_streams: Vec<_> = tx_stream.into_iter().collect();
+        if let Some(path) = config.socket.clone() {
+            let (tx_socket, rx_socket) = mpsc::channel();
+            tx_streams.push(tx_socket);
+            let state_socket = state.clone();
+            thread_handles.push(thread::spawn(move||{
+                #[cfg(unix)]
+                if let Err(e) = crate::socket::thread_socket_sink(path.clone(), rx_socket) {
+                    eprintln!("Error: Unable to stream matches to socket {path}: {e}");
+                }
+                #[cfg(not(unix))]
+                {
+                    drop(rx_socket);
+                    eprintln!("Error: Streaming matches to socket {path} is only supported on unix");
+                }
+                terminated(&state_socket, String::from("socket"));
+            }));
+        }
+
+        // Setup file handler thread
+        let keep_alive_clone = keep_alive.clone();
+        let state_clone = state.clone();
+        let file_options = FileOptions {
+            path: config.save_path.clone(),
+            match_log: config.match_log.clone(),
+            shards: config.shards,
+            fields: config.fields.clone(),
+            rotate: config.rotate,
+            format: config.format,
+            events_file: config.events_file.clone(),
+            save_every: config.save_every,
+            save_interval: config.save_interval,
+            wipe_secrets: config.wipe_secrets,
+        };
+        let completion_file = config.completion_file.clone();
+        thread_handles.push(thread::spawn(move||{
+            match thread_file_handler(rx_address_match, file_options, tx_streams, state_clone.clone(), keep_alive_clone.clone()) {
+                Err(e) => {
+                    keep_alive_clone.store(false,Ordering::Relaxed);
+                    eprintln!("Error: Unable to save vanity addresses to file: {}",e);
+                },
+                // Signal completion only once every match has been saved
+                Ok(()) => if let (Some(path), Ok(s)) = (completion_file, state_clone.lock()) {
+                    if let Some(completion) = s.completion {
+                        if let Err(e) = write_completion_file(&path, &s, completion) {
+                            eprintln!("Error: Unable to write completion file {path}: {e}");
+                        }
+                    }
+                },
+            }
+            terminated(&state_clone, String::from("file handler"));
+        }));
+
+        SearchHandle { state, keep_alive, thread_handles }
+    }
+}
+
+/// Find a single address matching the pattern, using all available threads, for the common
+/// case of embedding the search. The pattern is not case sensitive, and the match is also saved
+/// to [`DEFAULT_PATH`] in the working directory. See [`VanitySearcher::find_one`] for more control.
+///
+/// This blocks until a match is found, which can take a very long time for hard patterns, see
+/// [`crate::difficulty::expected_attempts`]. Fails right away for patterns with characters
+/// which can not appear in an address.
+///
+/// ```no_run
+/// use algo_vanity_rs::{PlacementMode, find_one_vanity};
+///
+/// let found = find_one_vanity("algo", PlacementMode::Start.into()).unwrap();
+/// assert!(found.public().starts_with("ALGO"));
+/// ```
+pub fn find_one_vanity(pattern: &str, placement: SearchPlacement) -> io::Result<AddressMatch> {
+    let pattern = pattern.to_uppercase();
+    if let Some(c) = pattern.chars().find(|c| !ADDRESS_CHARS.contains(*c)) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("'{c}' can not appear in an address")));
+    }
+
+    VanitySearcher::new(SearchConfig { vanities: vec![pattern], placement, ..SearchConfig::default() }).find_one()
+}
+
+/// Handle to a running vanity search
+pub struct SearchHandle {
+    state: Arc<Mutex<GlobalState>>,
+    keep_alive: Arc<AtomicBool>,
+    thread_handles: Vec<JoinHandle<()>>,
+}
+
+impl SearchHandle {
+    /// Shared state of the search, updated by the main loop
+    pub fn state(&self) -> &Arc<Mutex<GlobalState>> {
+        &self.state
+    }
+
+    /// Flag keeping the search threads alive
+    pub fn keep_alive(&self) -> &Arc<AtomicBool> {
+        &self.keep_alive
+    }
+
+    /// Whether the search is still running
+    pub fn is_running(&self) -> bool {
+        self.keep_alive.load(Ordering::Relaxed)
+    }
+
+    /// Stop searching for a pattern, without affecting the others. Returns whether the
+    /// pattern was being searched for. The search stops once no patterns remain.
+    pub fn cancel_pattern(&self, pattern: &str) -> bool {
+        cancel_pattern(&self.state, &self.keep_alive, pattern)
+    }
+
+    /// Signal all search threads to stop
+    pub fn stop(&self) {
+        self.keep_alive.store(false, Ordering::Relaxed);
+    }
+
+    /// Wait for all search threads to finish
+    pub fn join(self) {
+        for handle in self.thread_handles {
+            _ = handle.join();
+        }
+    }
+
+    /// Wait up to `timeout` for all search threads to finish. Returns `false` if some
+    /// thread is still running once the timeout has passed, in which case it is detached.
+    pub fn join_timeout(self, timeout: Duration) -> bool {
+        join_timeout(self.thread_handles, timeout)
+    }
+}
+
+/// Join the threads which finish within `timeout`, returning whether all of them did
+pub(crate) fn join_timeout(handles: Vec<JoinHandle<()>>, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while handles.iter().any(|h| !h.is_finished()) {
+        if Instant::now() >= deadline { return false }
+        thread::sleep(JOIN_POLL_INTERVAL);
+    }
+    for handle in handles {
+        _ = handle.join();
+    }
+    true
+}
+
+/// Record that a thread of the search has finished, for the embedder to report
+fn terminated(state: &Mutex<GlobalState>, name: String) {
+    if let Ok(mut s) = state.lock() { s.terminated_threads.push(name) }
+}
+
+/// Capacity of the seed pool and number of accounts per batch of each worker thread, reduced
+/// to fit within `max_thread_memory`
+fn buffer_lens(config: &SearchConfig) -> (Option<usize>, usize) {
+    let fixed = thread_memory(&config.vanities);
+    let seed_pool = config.seed_pool.map(|len| capped_batch_len(SeedPool::SEED_SIZE, len, fixed, config.max_thread_memory));
+    let batch = capped_batch_len(std::mem::size_of::<Account>(), COUNT_PER_LOOP * COUNT_PER_LOOP, fixed, config.max_thread_memory);
+    (seed_pool, batch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_timeout_gives_up_on_slow_threads() {
+        let slow = thread::spawn(|| thread::sleep(Duration::from_secs(2)));
+        let started = Instant::now();
+        assert!(!join_timeout(vec![slow], Duration::from_millis(100)));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn join_timeout_joins_finished_threads() {
+        let handles = (0..3).map(|_| thread::spawn(|| thread::sleep(Duration::from_millis(20)))).collect();
+        assert!(join_timeout(handles, Duration::from_secs(5)));
+        assert!(join_timeout(Vec::new(), Duration::ZERO));
+    }
+
+    #[test]
+    fn memory_cap_shrinks_buffers() {
+        let config = SearchConfig { vanities: vec![String::from("ALGO")], seed_pool: Some(64), ..SearchConfig::default() };
+        assert_eq!(buffer_lens(&config), (Some(64), COUNT_PER_LOOP * COUNT_PER_LOOP));
+
+        let fixed = thread_memory(&config.vanities);
+        let capped = SearchConfig { max_thread_memory: Some(fixed + 16 * SeedPool::SEED_SIZE), ..config };
+        let (seed_pool, batch) = buffer_lens(&capped);
+        assert_eq!(seed_pool, Some(16));
+        assert!(batch < COUNT_PER_LOOP * COUNT_PER_LOOP);
+
+        let minimal = SearchConfig { max_thread_memory: Some(fixed), ..capped };
+        assert_eq!(buffer_lens(&minimal), (Some(1), 1));
+    }
+
+    /// Worker restarts once a worker ran into `fault`, and whether the search went on after it
+    fn recover_from(fault: WorkerFault) -> (usize, bool) {
+        let save_path = std::env::temp_dir().join(format!("algo-vanity-fault-{fault:?}-{}.json", std::process::id()));
+        let vanities = vec![String::from("ALGOALGOALGO")];
+
+        // Small batches keep the workers reporting well within the timeout
+        let max_thread_memory = thread_memory(&vanities) + 100 * std::mem::size_of::<Account>();
+        let handle = VanitySearcher::new(SearchConfig {
+            vanities,
+            threads: 1,
+            save_path: save_path.to_string_lossy().into_owned(),
+            max_thread_memory: Some(max_thread_memory),
+            ..SearchConfig::default()
+        }).inject_fault(fault, Duration::from_millis(500)).start();
+
+        let started = Instant::now();
+        let progress = || { let s = handle.state().lock().unwrap(); (s.worker_restarts, s.total_count) };
+        while progress().0 == 0 && started.elapsed() < Duration::from_secs(10) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let (restarts, count) = progress();
+        while progress().1 == count && started.elapsed() < Duration::from_secs(10) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let recovered = progress().1 > count && handle.state().lock().unwrap().search_rate > 0.0;
+
+        handle.stop();
+        handle.join();
+        _ = std::fs::remove_file(&save_path);
+        (restarts, recovered)
+    }
+
+    #[test]
+    fn panicking_worker_is_restarted() {
+        let (restarts, recovered) = recover_from(WorkerFault::Panic);
+        assert_eq!(restarts, 1);
+        assert!(recovered);
+    }
+
+    #[test]
+    fn stuck_worker_is_replaced() {
+        let (restarts, recovered) = recover_from(WorkerFault::Stall);
+        assert_eq!(restarts, 1);
+        assert!(recovered);
+    }
+}
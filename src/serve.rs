@@ -0,0 +1,142 @@
+//! Minimal HTTP API for running one search at a time as a backend service.
+//!
+//! - `POST /search` with `{"patterns": ["algo"], "placement": "start"}` starts a search, with
+//!   the placement named as for `--placement`
+//! - `GET /search` reports the progress and matches of the current search
+//! - `DELETE /search` stops the current search
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use crate::{PlacementMode, SearchConfig, SearchHandle, SearchPlacement, VanitySearcher, ADDRESS_CHARS};
+
+/// Time to wait for a stopped search to finish before starting the next
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time to wait for a client to send its request before giving up on the connection
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest request body accepted, well above any list of patterns
+const MAX_BODY_LEN: usize = 8 * 1024;
+
+/// Serve the API on the listener, starting searches from the `base` configuration. Connections
+/// which fail to be accepted are skipped, so this only returns if the listener stops.
+pub fn serve(listener: TcpListener, base: SearchConfig) -> io::Result<()> {
+    let mut search: Option<SearchHandle> = None;
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                println!("Error: Unable to accept connection: {e}");
+                continue;
+            },
+        };
+        if let Err(e) = handle_connection(stream, &base, &mut search) {
+            println!("Error: Unable to handle request: {e}");
+        }
+    }
+    Ok(())
+}
+
+/// Answer a single request on the connection
+fn handle_connection(mut stream: TcpStream, base: &SearchConfig, search: &mut Option<SearchHandle>) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+
+    // Request line, followed by headers of which only the content length matters
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() { break }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    // The body is only read once its length is known to be reasonable
+    let mut body = Vec::new();
+    if content_length <= MAX_BODY_LEN {
+        body.resize(content_length, 0);
+        reader.read_exact(&mut body)?;
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, response) = match (parts.next(), parts.next()) {
+        _ if content_length > MAX_BODY_LEN => ("413 Payload Too Large", json!({ "error": format!("request body exceeds {MAX_BODY_LEN} bytes") })),
+        (Some("POST"), Some("/search")) => start_search(&body, base, search),
+        (Some("GET"), Some("/search")) => poll_search(search),
+        (Some("DELETE"), Some("/search")) => {
+            search.as_ref().inspect(|s| s.stop());
+            ("200 OK", json!({ "stopped": search.is_some() }))
+        },
+        _ => ("404 Not Found", json!({ "error": "unknown endpoint" })),
+    };
+
+    let body = response.to_string();
+    write!(stream, "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len())?;
+    stream.flush()
+}
+
+/// Start a search for the requested patterns, unless one is already running
+fn start_search(body: &[u8], base: &SearchConfig, search: &mut Option<SearchHandle>) -> (&'static str, Value) {
+    if search.as_ref().is_some_and(|s| s.is_running()) {
+        return ("409 Conflict", json!({ "error": "a search is already running" }));
+    }
+
+    let request: Value = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return ("400 Bad Request", json!({ "error": format!("invalid json: {e}") })),
+    };
+
+    let patterns: Vec<String> = match request["patterns"].as_array() {
+        Some(patterns) => patterns.iter().filter_map(|p| p.as_str()).map(|p| p.to_uppercase()).collect(),
+        None => return ("400 Bad Request", json!({ "error": "expected a list of patterns" })),
+    };
+    if patterns.is_empty() || patterns.iter().any(|p| p.is_empty() || !p.chars().all(|c| ADDRESS_CHARS.contains(c))) {
+        return ("400 Bad Request", json!({ "error": "patterns must be non-empty and only use address characters" }));
+    }
+
+    let placement: SearchPlacement = match PlacementMode::from_str(request["placement"].as_str().unwrap_or("start"), false) {
+        Ok(mode) => mode.into(),
+        Err(e) => return ("400 Bad Request", json!({ "error": e })),
+    };
+
+    // The previous search has stopped, but its threads may still be finishing
+    if let Some(previous) = search.take() {
+        previous.join_timeout(STOP_TIMEOUT);
+    }
+
+    *search = Some(VanitySearcher::new(SearchConfig { vanities: patterns, placement, ..base.clone() }).start());
+    ("202 Accepted", json!({ "started": true }))
+}
+
+/// Progress and matches of the current search
+fn poll_search(search: &Option<SearchHandle>) -> (&'static str, Value) {
+    let Some(handle) = search else {
+        return ("404 Not Found", json!({ "error": "no search has been started" }));
+    };
+    let Ok(s) = handle.state().lock() else {
+        return ("500 Internal Server Error", json!({ "error": "search state is unavailable" }));
+    };
+    ("200 OK", json!({
+        "running": handle.is_running(),
+        "patterns": s.vanities,
+        "placement": s.placement.to_string(),
+        "total_count": s.total_count,
+        "search_rate": s.search_rate,
+        "warming_up": s.warming_up,
+        "run_time_secs": s.run_time.as_secs_f64(),
+        "match_count": s.match_count,
+        "worker_restarts": s.worker_restarts,
+        "matches": s.matches,
+    }))
+}
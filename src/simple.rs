@@ -0,0 +1,89 @@
+//! Plain progress output for consoles which can not run the terminal ui, using nothing but
+//! printing and carriage returns, which work in cmd.exe, PowerShell and legacy consoles alike,
+//! and for logs and services, printing nothing but whole lines
+
+use std::{
+    io::{self, IsTerminal, Write, stdout},
+    sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use algo_vanity_rs::{GlobalState, locale::NumberLocale, stats::SearchStats};
+
+/// Whether the terminal ui is likely to misbehave here, such that the simple output is used
+/// instead. This is the case on Windows consoles other than Windows Terminal and terminals
+/// announcing themselves through `TERM`.
+pub fn terminal_ui_unsupported() -> bool {
+    cfg!(windows)
+        && std::env::var_os("WT_SESSION").is_none()
+        && std::env::var_os("TERM").is_none()
+}
+
+/// Whether the output is not a terminal at all, such as a log file or a pipe, such that the
+/// headless output is used instead
+pub fn output_is_not_terminal() -> bool {
+    !stdout().is_terminal()
+}
+
+/// Print new matches on lines of their own, and overwrite a single progress line in between,
+/// until the search stops
+pub fn main(state: &Arc<Mutex<GlobalState>>, keep_alive: Arc<AtomicBool>, refresh_interval: Duration, locale: NumberLocale) -> io::Result<()> {
+    let mut out = stdout();
+    let mut printed = state.lock().map_or(0, |s| s.matches.len());
+    let mut line_width = 0;
+
+    loop {
+        let stopped = !keep_alive.load(Ordering::Relaxed);
+        let (stats, new_matches) = match state.lock() {
+            Ok(s) => (SearchStats::snapshot(&s), s.matches[printed.min(s.matches.len())..].iter().map(|m| m.summary()).collect::<Vec<_>>()),
+            Err(_) => break,
+        };
+        printed += new_matches.len();
+
+        // Blank out the progress line before printing over it, as carriage returns do not clear
+        let line = stats.progress_line(locale);
+        write!(out, "\r{}\r", " ".repeat(line_width))?;
+        for summary in new_matches {
+            writeln!(out, "Found {summary}")?;
+        }
+        write!(out, "{line}")?;
+        out.flush()?;
+        line_width = line.chars().count();
+
+        if stopped { break }
+        thread::sleep(refresh_interval);
+    }
+    writeln!(out)
+}
+
+/// Print new matches on lines of their own as they are found, and a progress line every
+/// `progress_interval`, until the search stops. Every line is complete on its own, without any
+/// carriage returns or escape sequences, such that logs of services and pipes stay readable.
+pub fn headless(state: &Arc<Mutex<GlobalState>>, keep_alive: Arc<AtomicBool>, refresh_interval: Duration, progress_interval: Duration, locale: NumberLocale) -> io::Result<()> {
+    let mut out = stdout();
+    let mut printed = state.lock().map_or(0, |s| s.matches.len());
+    let mut last_progress = Instant::now();
+
+    loop {
+        let stopped = !keep_alive.load(Ordering::Relaxed);
+        let (stats, new_matches) = match state.lock() {
+            Ok(s) => (SearchStats::snapshot(&s), s.matches[printed.min(s.matches.len())..].iter().map(|m| m.summary()).collect::<Vec<_>>()),
+            Err(_) => break,
+        };
+        printed += new_matches.len();
+
+        for summary in new_matches {
+            writeln!(out, "Found {summary}")?;
+        }
+        if stopped || last_progress.elapsed() >= progress_interval {
+            writeln!(out, "{}", stats.progress_line(locale))?;
+            last_progress = Instant::now();
+        }
+        out.flush()?;
+
+        if stopped { break }
+        thread::sleep(refresh_interval);
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! Streaming matches as json lines to local consumers over a Unix domain socket
+
+use std::{
+    io::{self, Write},
+    os::unix::{fs::FileTypeExt, net::{UnixListener, UnixStream}},
+    sync::mpsc,
+    time::Duration,
+};
+
+use crate::AddressMatch;
+
+/// Interval for accepting new consumers while waiting for matches
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time a consumer has to take in a match before it is dropped for falling behind
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Listen on a socket at `path`, sending every received match to all connected consumers as
+/// a line of json. Consumers first receive the matches sent before they connected, and are
+/// dropped once they disconnect or fall behind by more than `WRITE_TIMEOUT`, such that they
+/// never hold up the others. The socket is removed when the search ends.
+pub(crate) fn thread_socket_sink(path: String, rx_address_match: mpsc::Receiver<AddressMatch>) -> io::Result<()> {
+    remove_stale_socket(&path)?;
+    let listener = UnixListener::bind(&path)?;
+    listener.set_nonblocking(true)?;
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut consumers: Vec<UnixStream> = Vec::new();
+    loop {
+        // Catch new consumers up on the matches so far
+        while let Ok((mut stream, _)) = listener.accept() {
+            let ready = stream.set_nonblocking(false).is_ok() && stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_ok();
+            if ready && lines.iter().all(|line| stream.write_all(line.as_bytes()).is_ok()) {
+                consumers.push(stream);
+            }
+        }
+
+        match rx_address_match.recv_timeout(ACCEPT_INTERVAL) {
+            Ok(address_match) => {
+                let line = serde_json::to_string(&address_match)? + "\n";
+                consumers.retain_mut(|stream| stream.write_all(line.as_bytes()).is_ok());
+                lines.push(line);
+            },
+            Err(mpsc::RecvTimeoutError::Timeout) => {},
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    std::fs::remove_file(&path)
+}
+
+/// Remove the socket left at `path` by an earlier search which did not end cleanly. Sockets
+/// which are still being listened on, and files other than sockets, are left alone for
+/// binding to fail on.
+fn remove_stale_socket(path: &str) -> io::Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() && UnixStream::connect(path).is_err() => std::fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,158 @@
+//! Periodic statistics of a running search, for rendering progress outside the terminal ui
+
+use std::{
+    io,
+    sync::{Arc, Mutex, mpsc},
+    time::{Duration, Instant},
+};
+
+use crate::{GlobalState, RateFilter, locale::NumberLocale};
+
+/// Snapshot of the statistics of a running search
+#[derive(Clone,Debug)]
+pub struct SearchStats {
+    /// When the snapshot was taken
+    pub taken_at: Instant,
+    /// Time since the search started
+    pub run_time: Duration,
+    /// Filtered number of addresses generated per second, across all threads
+    pub search_rate: f32,
+    /// Whether the rate is still warming up, see [`crate::SearchConfig::warm_up`]
+    pub warming_up: bool,
+    /// Number of addresses generated so far
+    pub total_count: usize,
+    /// Number of matches found so far, including those below `min_quality`
+    pub match_count: usize,
+    /// Number of matches kept, including those carried forward from a previous search
+    pub matches: usize,
+    /// Patterns still being searched for
+    pub remaining: Vec<String>,
+}
+
+impl SearchStats {
+    /// Take a snapshot of the statistics of the search
+    pub fn snapshot(state: &GlobalState) -> Self {
+        SearchStats {
+            taken_at: Instant::now(),
+            run_time: state.start_time.elapsed(),
+            search_rate: state.search_rate,
+            warming_up: state.warming_up,
+            total_count: state.total_count,
+            match_count: state.match_count,
+            matches: state.matches.len(),
+            remaining: state.vanities.clone(),
+        }
+    }
+
+    /// Single line of plain text summarizing the progress, without any escape sequences, such
+    /// as for consoles which can not run the terminal ui
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use algo_vanity_rs::{locale::NumberLocale, stats::SearchStats};
+    ///
+    /// let stats = SearchStats {
+    ///     taken_at: Instant::now(),
+    ///     run_time: Duration::from_secs(3725),
+    ///     search_rate: 123456.7,
+    ///     warming_up: false,
+    ///     total_count: 450_000_000,
+    ///     match_count: 3,
+    ///     matches: 3,
+    ///     remaining: vec![String::from("ALGO")],
+    /// };
+    /// assert_eq!(stats.progress_line(NumberLocale::En), "1h:02m:05s | 123,457 a/s | 450 million addresses | 3 matches | 1 pattern left");
+    /// ```
+    pub fn progress_line(&self, locale: NumberLocale) -> String {
+        let secs = self.run_time.as_secs();
+        let counted = |n: usize, one: &str, many: &str| format!("{n} {}", if n == 1 { one } else { many });
+        format!("{}h:{:02}m:{:02}s | {} a/s{} | {} addresses | {} | {} left",
+            secs / 3600, secs / 60 % 60, secs % 60,
+            locale.group(self.search_rate.round() as u64),
+            if self.warming_up { " (warming up)" } else { "" },
+            locale.count(self.total_count),
+            counted(self.match_count, "match", "matches"),
+            counted(self.remaining.len(), "pattern", "patterns"),
+        )
+    }
+}
+
+/// Aggregates the rates reported by each worker thread into the filtered rate of the search
+///
+/// ```
+/// use std::time::Duration;
+/// use algo_vanity_rs::stats::RateAggregator;
+///
+/// let mut rates = RateAggregator::new(2);
+/// rates.observe(0, 1000, Duration::from_secs(1), true);
+/// assert_eq!(rates.observe(1, 1000, Duration::from_millis(500), true), 3000.0);
+/// assert_eq!(rates.rate_of(1), 1000.0);
+///
+/// rates.forget_from(1);
+/// assert_eq!(rates.rate_of(2), 1000.0);
+/// ```
+pub struct RateAggregator {
+    rates: Vec<f32>,
+    filter: RateFilter,
+}
+
+impl RateAggregator {
+    /// Aggregate the rates of `threads` worker threads
+    pub fn new(threads: usize) -> Self {
+        RateAggregator { rates: vec![0.0; threads], filter: RateFilter::default() }
+    }
+
+    /// Register that worker thread `id` generated `count` addresses in `duration`, returning
+    /// the filtered rate of all threads
+    pub fn observe(&mut self, id: usize, count: usize, duration: Duration, warming_up: bool) -> f32 {
+        if let Some(rate) = self.rates.get_mut(id) {
+            *rate = count as f32 / duration.as_secs_f32();
+        }
+        self.filter.observe(self.rates.iter().sum(), warming_up)
+    }
+
+    /// Unfiltered rate of the first `threads` worker threads
+    pub fn rate_of(&self, threads: usize) -> f32 {
+        self.rates.iter().take(threads).sum()
+    }
+
+    /// Forget the rates of the worker threads from `threads` on, such as once they are paused
+    pub fn forget_from(&mut self, threads: usize) {
+        self.rates.iter_mut().skip(threads).for_each(|r| *r = 0.0);
+    }
+}
+
+/// Callback receiving periodic statistics of a search
+pub(crate) type StatsCallback = Box<dyn FnMut(&SearchStats) + Send>;
+
+/// Thread calling the callback with a fresh snapshot every `interval`, and once more when
+/// `done` disconnects after the main loop has handled its last message. The state is only
+/// locked while taking the snapshot.
+pub(crate) fn thread_stats(state: Arc<Mutex<GlobalState>>, done: mpsc::Receiver<()>, interval: Duration, mut callback: StatsCallback) {
+    let mut next = Instant::now() + interval;
+    loop {
+        let stopped = matches!(done.recv_timeout(next.saturating_duration_since(Instant::now())), Err(mpsc::RecvTimeoutError::Disconnected));
+        next += interval;
+        let Ok(stats) = state.lock().map(|s| SearchStats::snapshot(&s)) else { return };
+        callback(&stats);
+        if stopped { return }
+    }
+}
+
+/// Atomically overwrite the status file at `path` with a snapshot of the statistics, writing a
+/// temporary file next to it first, such that readers never see a partially written file
+pub(crate) fn write_status_file(path: &str, stats: &SearchStats) -> io::Result<()> {
+    let status = serde_json::json!({
+        "updated_at": chrono::Local::now().to_rfc3339(),
+        "run_time_secs": stats.run_time.as_secs_f64(),
+        "search_rate": stats.search_rate,
+        "warming_up": stats.warming_up,
+        "total_count": stats.total_count,
+        "match_count": stats.match_count,
+        "matches": stats.matches,
+        "remaining": stats.remaining,
+    });
+    let temporary = format!("{path}.tmp");
+    std::fs::write(&temporary, serde_json::to_string_pretty(&status)?)?;
+    std::fs::rename(&temporary, path)
+}
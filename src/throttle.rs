@@ -0,0 +1,69 @@
+//! Rate limiting of the match list of the terminal ui
+
+use std::time::{Duration, Instant};
+
+/// Default time between two updates of the visible match list
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Limits how often the visible match list catches up with the matches found, so patterns
+/// matching faster than the list can be read do not churn it. Matches which arrive in
+/// between are only held back from display, never from the counters or the results file.
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use algo_vanity_rs::throttle::DisplayThrottle;
+///
+/// let start = Instant::now();
+/// let mut throttle = DisplayThrottle::new(Duration::from_millis(250));
+/// assert_eq!(throttle.update(1, start), 1);
+///
+/// // Matches arriving shortly after are held back until the interval has passed
+/// assert_eq!(throttle.update(40, start + Duration::from_millis(100)), 1);
+/// assert_eq!(throttle.pending(40), 39);
+/// assert_eq!(throttle.update(90, start + Duration::from_millis(250)), 90);
+///
+/// // Removed matches disappear right away
+/// assert_eq!(throttle.update(10, start + Duration::from_millis(300)), 10);
+/// ```
+#[derive(Clone,Debug)]
+pub struct DisplayThrottle {
+    interval: Duration,
+    shown: usize,
+    updated: Option<Instant>,
+}
+
+impl Default for DisplayThrottle {
+    fn default() -> Self {
+        DisplayThrottle::new(DEFAULT_INTERVAL)
+    }
+}
+
+impl DisplayThrottle {
+    /// Update the visible list at most once per `interval`
+    pub fn new(interval: Duration) -> Self {
+        DisplayThrottle { interval, shown: 0, updated: None }
+    }
+
+    /// Register the number of matches available at `now`, returning how many of them to show.
+    /// The list catches up with every available match once `interval` has passed since its
+    /// last update.
+    pub fn update(&mut self, available: usize, now: Instant) -> usize {
+        if available < self.shown {
+            self.shown = available;
+        } else if available > self.shown && self.updated.is_none_or(|t| now.duration_since(t) >= self.interval) {
+            self.shown = available;
+            self.updated = Some(now);
+        }
+        self.shown
+    }
+
+    /// Number of matches to show, as of the last update
+    pub fn shown(&self) -> usize {
+        self.shown
+    }
+
+    /// Number of available matches held back from display
+    pub fn pending(&self, available: usize) -> usize {
+        available.saturating_sub(self.shown)
+    }
+}
@@ -1,27 +1,180 @@
-use std::{io::{self, stdout}, sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex}};
+use std::{io::{self, stdout}, sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex}, time::{Duration, Instant}};
 use crossterm::{
     event::{self, Event, KeyCode},
     ExecutableCommand,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen}
 };
 use ratatui::{prelude::*, widgets::*};
-use thousands::Separable;
+use serde::Serialize;
 
-use crate::{GlobalState, AddressMatch};
+use algo_vanity_rs::{GlobalState, AddressMatch, Placement, ADDRESS_CHARS, cancel_pattern, difficulty::{self, DifficultyModel}, format, interesting::InterestingRule, locale::NumberLocale, offset_histogram, serialize_opt_secs, serialize_secs, throttle::DisplayThrottle};
+
+/// Settings of the terminal ui
+#[derive(Serialize)]
+pub struct TuiConfig {
+    /// Interval between redraws and polls for key presses
+    #[serde(rename = "refresh_interval_secs", serialize_with = "serialize_secs")]
+    pub refresh_interval: Duration,
+    /// Highlight colors of the matched pattern for each placement
+    #[serde(skip)]
+    pub highlight: PlacementColors,
+    /// Render with colors and text styles, or as plain text
+    pub color: bool,
+    /// Matches satisfying any of these rules are marked with a star
+    pub interesting: Vec<InterestingRule>,
+    /// Separators and magnitude words of displayed numbers
+    pub locale: NumberLocale,
+    /// Model behind the estimates of the pattern calculator and of the first match, the same
+    /// as that of the search
+    #[serde(skip)]
+    pub difficulty: DifficultyModel,
+    /// Pack several matches per row when the terminal is wide enough
+    pub multi_column: bool,
+    /// Ask for confirmation before quitting while matches are not saved yet
+    pub confirm_quit: bool,
+    /// Separate the displayed addresses into groups of this many characters
+    pub group_display: Option<usize>,
+    /// Mask revealed mnemonics, such as while screen sharing
+    #[serde(rename = "redact")]
+    pub redact_mnemonics: bool,
+    /// Mask the middle of addresses outside the matched pattern
+    pub redact_addresses: bool,
+    /// Set the characters derived from the checksum apart from the rest of each address
+    pub show_checksum: bool,
+    /// Show each address lowercased next to its canonical form
+    pub show_lowercase: bool,
+    /// Print plain progress lines in place of the terminal ui
+    pub simple: bool,
+    /// Print whole progress lines this often in place of the terminal ui, for logs and services
+    #[serde(rename = "headless_secs", serialize_with = "serialize_opt_secs")]
+    pub headless: Option<Duration>,
+}
+
+/// Colors to highlight matches with, depending on their placement
+pub struct PlacementColors {
+    pub start: Color,
+    pub anywhere: Color,
+    pub end: Color,
+}
+
+impl PlacementColors {
+    fn get(&self, placement: &Placement) -> Color {
+        match placement {
+            Placement::Start | Placement::Near(_) => self.start,
+            Placement::Anywhere(_) | Placement::Window { .. } => self.anywhere,
+            Placement::End => self.end,
+        }
+    }
+}
+
+/// Number of patterns to show the progress of at most
+const MAX_PROGRESS_LINES: usize = 6;
+
+/// Width of the progress bar of each pattern, in characters
+const PROGRESS_BAR_WIDTH: usize = 10;
+
+/// Height of the histogram of anywhere match offsets, including its border
+const HISTOGRAM_HEIGHT: u16 = 6;
+
+/// Time the outcome of an export stays visible
+const STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time a revealed mnemonic stays visible before it is hidden again
+const REVEAL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Width of a match in the multi-column layout, an address with room for markers around it
+const MATCH_CELL_WIDTH: usize = difficulty::ADDRESS_LENGTH + 4;
+
+/// Width of the rarity score following each match, such as "  42 bits"
+const RARITY_WIDTH: usize = 9;
+
+/// Spaces between the columns of matches
+const COLUMN_GAP: usize = 3;
+
+/// Characters left unmasked at either end of a redacted address
+const REDACT_VISIBLE_CHARS: usize = 4;
+
+/// State of the terminal ui which is not part of the search itself
+#[derive(Default)]
+struct UiState {
+    /// Draft pattern of the difficulty calculator, if it is open
+    calculator: Option<String>,
+    /// Index of the selected match
+    selected: Option<usize>,
+    /// Time at which the mnemonic of the selected match was revealed
+    revealed: Option<Instant>,
+    /// Draft file path to export the selected match to, if the prompt is open
+    export: Option<String>,
+    /// Draft pattern to cancel, if the prompt is open
+    cancel: Option<String>,
+    /// Outcome of the last export, shown until `STATUS_TIMEOUT` has passed
+    status: Option<(String, Instant)>,
+    /// Whether to ask for confirmation before quitting with unsaved matches
+    confirm_quit: bool,
+    /// Limits how often the visible matches catch up with the matches found
+    display: DisplayThrottle,
+}
+
+impl UiState {
+    /// Move the selection by `step` matches, starting from the newest match. Steps beyond the
+    /// oldest or newest match stop at it, such as `isize::MIN` to jump to the oldest match.
+    fn select(&mut self, step: isize, match_count: usize) {
+        if match_count == 0 { return }
+        let current = self.selected.unwrap_or(match_count) as isize;
+        self.selected = Some(current.saturating_add(step).clamp(0, match_count as isize - 1) as usize);
+        self.revealed = None;
+    }
+
+    /// Hide the mnemonic once it has been revealed for longer than `REVEAL_TIMEOUT`
+    fn expire_reveal(&mut self, now: Instant) {
+        if self.revealed.is_some_and(|t| now.duration_since(t) >= REVEAL_TIMEOUT) {
+            self.revealed = None;
+        }
+        if self.status.as_ref().is_some_and(|(_, t)| now.duration_since(*t) >= STATUS_TIMEOUT) {
+            self.status = None;
+        }
+    }
+
+    /// Write the selected match to its own file at the draft path
+    fn export_selected(&mut self, state: &Arc<Mutex<GlobalState>>) {
+        let (Some(path), Some(selected)) = (self.export.take(), self.selected) else { return };
+        let selected_match = state.lock().ok().and_then(|s| s.matches.get(selected).cloned());
+        let message = match selected_match.map(|m| format::write_new_matches(&path, &[m])) {
+            Some(Ok(())) => format!("Exported match to {path}"),
+            Some(Err(e)) => format!("Unable to export match to {path}: {e}"),
+            None => String::from("Unable to export match, it is no longer available"),
+        };
+        self.status = Some((message, Instant::now()));
+    }
+}
+
+/// Highest refresh rate of the terminal ui, in frames per second
+pub const MAX_FPS: u32 = 60;
+
+/// Interval between redraws and polls for key presses at `fps` frames per second, unless the
+/// rate is outside 1 to `MAX_FPS`
+pub fn refresh_interval(fps: u32) -> Option<Duration> {
+    (1..=MAX_FPS).contains(&fps).then(|| Duration::from_secs(1) / fps)
+}
 
 pub fn main(
     state: &Arc<Mutex<GlobalState>>,
-    keep_alive: Arc<AtomicBool>
+    keep_alive: Arc<AtomicBool>,
+    config: &TuiConfig,
 ) -> io::Result<()> {
 
     // Setup terminal
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    let mut ui = UiState::default();
 
     while keep_alive.load(Ordering::Relaxed) {
-        terminal.draw(|frame|ui_function(frame, state))?;
-        handle_events(&keep_alive)?;
+        let now = Instant::now();
+        ui.expire_reveal(now);
+        ui.display.update(state.lock().map_or(0, |s| s.matches.len()), now);
+        terminal.draw(|frame|ui_function(frame, state, &ui, config))?;
+        handle_events(&keep_alive, state, &mut ui, config)?;
     }
 
     // Tear down terminal
@@ -30,59 +183,172 @@ pub fn main(
     Ok(())
 }
 
-fn handle_events(keep_alive: &Arc<AtomicBool>) -> io::Result<()> {
-    if event::poll(std::time::Duration::from_millis(100))? {
+fn handle_events(keep_alive: &Arc<AtomicBool>, state: &Arc<Mutex<GlobalState>>, ui: &mut UiState, config: &TuiConfig) -> io::Result<()> {
+    let quit = || {
+        keep_alive.store(false, Ordering::Relaxed);
+        std::io::stdout().execute(LeaveAlternateScreen).unwrap();
+    };
+
+    if event::poll(config.refresh_interval)? {
         if let Event::Key(key) = event::read()? {
-            if key.kind == event::KeyEventKind::Press && key.code == KeyCode::Char('q') {
-                keep_alive.store(false, Ordering::Relaxed);
-                std::io::stdout().execute(LeaveAlternateScreen).unwrap();
+            if key.kind != event::KeyEventKind::Press { return Ok(()) }
+
+            // Keys answering whether to quit with unsaved matches
+            if ui.confirm_quit {
+                match key.code {
+                    KeyCode::Char('y') => quit(),
+                    KeyCode::Char('n') | KeyCode::Esc => ui.confirm_quit = false,
+                    _ => {}
+                }
+                return Ok(())
+            }
+
+            // Keys typed into the open cancel prompt
+            if let Some(draft) = ui.cancel.as_mut() {
+                match key.code {
+                    KeyCode::Esc => ui.cancel = None,
+                    KeyCode::Enter => {
+                        let pattern = ui.cancel.take().unwrap_or_default();
+                        let message = match cancel_pattern(state, keep_alive, &pattern) {
+                            true => format!("Stopped searching for {pattern}"),
+                            false => format!("{pattern} is not being searched for"),
+                        };
+                        ui.status = Some((message, Instant::now()));
+                    },
+                    KeyCode::Backspace => { draft.pop(); },
+                    KeyCode::Char(c) if ADDRESS_CHARS.contains(c.to_ascii_uppercase()) => {
+                        draft.push(c.to_ascii_uppercase())
+                    },
+                    _ => {}
+                }
+                return Ok(())
+            }
+
+            // Keys typed into the open export prompt
+            if let Some(path) = ui.export.as_mut() {
+                match key.code {
+                    KeyCode::Esc => ui.export = None,
+                    KeyCode::Enter => ui.export_selected(state),
+                    KeyCode::Backspace => { path.pop(); },
+                    KeyCode::Char(c) => path.push(c),
+                    _ => {}
+                }
+                return Ok(())
+            }
+
+            // Keys typed into the open difficulty calculator
+            if let Some(draft) = ui.calculator.as_mut() {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Enter => ui.calculator = None,
+                    KeyCode::Backspace => { draft.pop(); },
+                    KeyCode::Char(c) if ADDRESS_CHARS.contains(c.to_ascii_uppercase()) => {
+                        draft.push(c.to_ascii_uppercase())
+                    },
+                    _ => {}
+                }
+                return Ok(())
+            }
+
+            match key.code {
+                KeyCode::Char('q') => match config.confirm_quit && state.lock().is_ok_and(|s| s.unsaved > 0) {
+                    true => ui.confirm_quit = true,
+                    false => quit(),
+                },
+                KeyCode::Char('c') => ui.calculator = Some(String::new()),
+                KeyCode::Char('p') => ui.cancel = Some(String::new()),
+                KeyCode::Up => ui.select(-1, ui.display.shown()),
+                KeyCode::Down => ui.select(1, ui.display.shown()),
+                KeyCode::Home | KeyCode::Char('g') => ui.select(isize::MIN, ui.display.shown()),
+                KeyCode::End | KeyCode::Char('G') => ui.select(isize::MAX, ui.display.shown()),
+                KeyCode::Char('x') if ui.selected.is_some() => ui.export = Some(String::new()),
+                KeyCode::Char('m') if ui.selected.is_some() => {
+                    ui.revealed = match ui.revealed {
+                        Some(_) => None,
+                        None => Some(Instant::now()),
+                    }
+                },
+                _ => {}
             }
        }
     }
     Ok(())
 }
 
-fn ui_function(frame: &mut Frame, state: &Arc<Mutex<GlobalState>>) {
+fn ui_function(frame: &mut Frame, state: &Arc<Mutex<GlobalState>>, ui: &UiState, config: &TuiConfig) {
     if let Ok(s) = state.lock() {
 
-        let areas = Layout::new()
-            .direction(Direction::Vertical)
-            .constraints(
-                [
-                    Constraint::Max(8),
-                    Constraint::Percentage(0),
-                    Constraint::Max(1)    
-                ])
-            .vertical_margin(1)
-            .horizontal_margin(2)
-            .split(frame.size());
-
         let sec = s.run_time.as_secs() % 60;
         let min = (s.run_time.as_secs() / 60) % 60;
         let hrs = (s.run_time.as_secs() / 60) / 60;
 
-        let count_message = if s.total_count > 1_000_000_000 {
-            format!("Total: {:.2} billion", s.total_count as f32 / 1e9)
-        } else {
-            format!("Total: {:.2} million", s.total_count as f32 / 1e6)
-        };
+        let count_message = format!("Total: {}", config.locale.count(s.total_count));
 
-        let stats_lines = vec![
+        let mut stats_lines = vec![
             Line::raw(format!("Timer: {}h:{:02}m:{:02}s", hrs, min, sec)),
-            Line::raw(format!("Speed: {} a/s", (s.search_rate as usize).separate_with_commas())),
+            Line::raw(format!("Speed: {} a/s{}", config.locale.group(s.search_rate as u64), if s.warming_up { " (warming up)" } else { "" })),
             Line::raw(count_message),
             Line::raw(format!("Found: {} matches", s.match_count)),
         ];
+        if s.worker_restarts > 0 {
+            stats_lines.push(Line::styled(format!("Restarted workers: {}", s.worker_restarts), Style::default().fg(Color::Yellow)));
+        }
 
-        let config_lines = vec![
-            Line::raw(format!("Threads:   {}", s.threads)),
+        let mut config_lines = vec![
+            Line::raw(match s.active_threads {
+                active if active < s.threads => format!("Threads:   {active} of {}", s.threads),
+                _ => format!("Threads:   {}", s.threads),
+            }),
             Line::raw(format!("Patterns:  {}", s.vanities.join(", "))),
             Line::raw(format!("Saves to:  {}", s.save_path)),
             Line::raw(format!("Placement: {}", s.placement)),
             // Add more configuration details here...
         ];
 
-        let matches = matches_to_text(&s.matches, (areas[1].height.saturating_sub(4)).into());
+        // Patterns retired once found, such as with --once, after the remaining ones
+        let mut found: Vec<&String> = Vec::new();
+        for m in &s.matches {
+            if !s.vanities.contains(&m.target) && s.expected_attempts.contains_key(&m.target) && !found.contains(&&m.target) {
+                found.push(&m.target);
+            }
+        }
+        let patterns: Vec<&String> = s.vanities.iter().chain(found.iter().copied()).collect();
+
+        // Progress towards the expected number of attempts of each remaining pattern, if it has
+        // an estimate, with the time it is expected to take at the current rate
+        let width = patterns.iter().take(MAX_PROGRESS_LINES).map(|v| v.len()).max().unwrap_or(0);
+        config_lines.extend(patterns.iter().take(MAX_PROGRESS_LINES).map(|vanity| {
+            if found.contains(vanity) {
+                return Line::from(vec![Span::raw(format!("{vanity:width$} ")), Span::styled("found", Style::default().fg(Color::Green))]);
+            }
+            let Some(expected) = s.expected_attempts.get(*vanity) else { return Line::raw(vanity.as_str()) };
+            let progress = s.total_count as f64 / expected;
+            let filled = difficulty::bar_fill(progress, PROGRESS_BAR_WIDTH);
+            let color = if progress > 1.0 { Color::Red } else { Color::Green };
+            Line::from(vec![
+                Span::raw(format!("{vanity:width$} ")),
+                Span::styled("█".repeat(filled), Style::default().fg(color)),
+                Span::styled("░".repeat(PROGRESS_BAR_WIDTH - filled), Style::default().add_modifier(Modifier::DIM)),
+                Span::raw(format!(" {:.0}% of {}, {}", progress * 100.0, config.locale.attempts(*expected), difficulty::eta(*expected, s.search_rate as f64))),
+            ])
+        }));
+        if patterns.len() > MAX_PROGRESS_LINES {
+            config_lines.push(Line::raw(format!("… and {} more", patterns.len() - MAX_PROGRESS_LINES)));
+        }
+
+        let areas = Layout::new()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Max(config_lines.len().max(stats_lines.len()) as u16 + 4),
+                    Constraint::Percentage(0),
+                    Constraint::Max(if s.placement.anywhere { HISTOGRAM_HEIGHT } else { 0 }),
+                    Constraint::Max(1)    
+                ])
+            .vertical_margin(1)
+            .horizontal_margin(2)
+            .split(frame.size());
+
+        let (matches, shown_matches) = matches_to_text(&s, areas[1].height.saturating_sub(4).into(), areas[1].width.saturating_sub(8).into(), ui, config);
 
         let areas_top = Layout::default()
             .direction(Direction::Horizontal)
@@ -101,7 +367,10 @@ fn ui_function(frame: &mut Frame, state: &Arc<Mutex<GlobalState>>) {
 
         let widget_config = Paragraph::new(Text::from(config_lines))
             .block(Block::default()
-                .title(" Configuration ")
+                .title(match &s.run_label {
+                    Some(label) => format!(" Configuration (run {label}) "),
+                    None => String::from(" Configuration "),
+                })
                 .padding(Padding::new(3,3,1,1))
                 .title_style(Style::default().bold())
                 .title_position(block::Position::Top)
@@ -109,10 +378,21 @@ fn ui_function(frame: &mut Frame, state: &Arc<Mutex<GlobalState>>) {
                 .borders(Borders::ALL)
             );
 
-        let title_matches = match matches.lines.len() {
+        let mut title_matches = match shown_matches {
             0 => String::from(" Matches will appear here "),
-            1 => format!(" Last match "),
-            _ => format!(" Last {} matches ", matches.lines.len())
+            1 => String::from(" Last match "),
+            _ => format!(" Last {} matches ", shown_matches)
+        };
+        let pending = ui.display.pending(s.matches.len());
+        if pending > 0 {
+            title_matches += &format!("(+{pending} arriving) ");
+        }
+
+        // Reassure that the search is working until the first match arrives
+        let matches = match s.matches.is_empty() {
+            true => Text::from(difficulty::empty_state(config.difficulty, &s.vanities, &s.placement, s.search_rate as f64, config.locale)
+                .into_iter().map(|line| Line::styled(line, Style::default().add_modifier(Modifier::DIM))).collect::<Vec<_>>()),
+            false => matches,
         };
 
         let widget_matches = Paragraph::new(matches)
@@ -124,49 +404,578 @@ fn ui_function(frame: &mut Frame, state: &Arc<Mutex<GlobalState>>) {
                 .title_alignment(Alignment::Center)
             ).alignment(Alignment::Center);
 
-        let exit_message = Paragraph::new(Text::raw(" Press 'q' to exit "))
-            .add_modifier(Modifier::DIM);
+        let exit_message = match (&s.save_error, &ui.status) {
+            (Some(e), _) => Paragraph::new(Text::raw(format!(" Unable to save matches: {e} ")))
+                .style(Style::default().fg(Color::Red)),
+            (None, Some((status, _))) => Paragraph::new(Text::raw(format!(" {status} "))),
+            (None, None) => Paragraph::new(Text::raw(" Press 'q' to exit, 'c' to open the difficulty calculator, arrows to select a match, 'm' to show its mnemonic, 'x' to export it and 'p' to cancel a pattern "))
+                .add_modifier(Modifier::DIM),
+        };
 
         frame.render_widget(widget_stats, areas_top[0]);
         frame.render_widget(widget_config, areas_top[1]);
         frame.render_widget(widget_matches, areas[1]);
-        frame.render_widget(exit_message, areas[2]);
+        frame.render_widget(exit_message, areas[3]);
+        if s.placement.anywhere {
+            let data = histogram_data(&s.anywhere_offsets, areas[2]);
+            frame.render_widget(histogram_widget(&data), areas[2]);
+        }
+
+        if let Some(draft) = &ui.calculator {
+            let area = centered_rect(frame.size(), 60, 9);
+            frame.render_widget(Clear, area);
+            frame.render_widget(calculator_widget(draft, &s, config), area);
+        }
+
+        if let Some(path) = &ui.export {
+            let area = centered_rect(frame.size(), 60, 5);
+            frame.render_widget(Clear, area);
+            frame.render_widget(prompt_widget(" Export selected match ", "Path: ", path, " Enter to export, Esc to close "), area);
+        }
+
+        if let Some(draft) = &ui.cancel {
+            let area = centered_rect(frame.size(), 60, 5);
+            frame.render_widget(Clear, area);
+            frame.render_widget(prompt_widget(" Cancel pattern ", "Pattern: ", draft, " Enter to cancel it, Esc to close "), area);
+        }
+
+        if ui.confirm_quit {
+            let question = format!("{} matches unsaved, quit anyway? (y/n)", s.unsaved);
+            let area = centered_rect(frame.size(), 60, 5);
+            frame.render_widget(Clear, area);
+            frame.render_widget(prompt_widget(" Quit ", &question, "", " y to quit, n or Esc to keep searching "), area);
+        }
+
+        // Plain text only keeps the layout, dropping every color and style
+        if !config.color {
+            let area = frame.size();
+            frame.buffer_mut().set_style(area, Style::reset());
+        }
     }
 }
 
+/// Bars of the distribution of anywhere match offsets across the address, one per column
+fn histogram_data(offsets: &[usize], area: Rect) -> Vec<u64> {
+    let columns = area.width.saturating_sub(2).min(offsets.len() as u16);
+    offset_histogram(offsets, columns.into()).into_iter().map(|c| c as u64).collect()
+}
 
-fn match_to_line(m: &AddressMatch) -> Line {
-    // Calculate the start and end of the match
-    let (a, b) = match m.placement {
-        crate::Placement::Start => (0, m.target.len()),
-        crate::Placement::Anywhere(position) => (position, position + m.target.len()),
-        crate::Placement::End => (m.public.len() - m.target.len(), m.public.len()),
-    };
+fn histogram_widget(data: &[u64]) -> Sparkline<'_> {
+    Sparkline::default()
+        .data(data)
+        .style(Style::default().fg(Color::Yellow))
+        .block(Block::default()
+            .title(" Match offsets (start to end of address) ")
+            .title_style(Style::default().bold())
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+        )
+}
 
-    // Construct a span with the given text, color and modifier
-    let styled_span = |text: &str, color: Color, modifier: Modifier| {
-        Span::styled(text.to_owned(), Style::default().fg(color).add_modifier(modifier))
+/// Difficulty and time estimate of the draft pattern, recomputed on every frame
+fn calculator_widget<'a>(draft: &'a str, s: &GlobalState, config: &TuiConfig) -> Paragraph<'a> {
+    let expected = config.difficulty.expected_attempts(draft, &s.placement);
+    let eta = if s.search_rate > 0.0 {
+        difficulty::format_duration(expected / s.search_rate as f64)
+    } else {
+        String::from("estimating…")
     };
 
-    // Construct the line from the spans
-    let spans = vec![
-        styled_span(&m.public[ ..a], Color::Gray, Modifier::DIM),
-        styled_span(&m.public[a..b], Color::Green, Modifier::BOLD),
-        styled_span(&m.public[b.. ], Color::Gray, Modifier::DIM),
+    let lines = vec![
+        Line::from(vec![Span::raw("Pattern:   "), Span::styled(draft, Style::default().fg(Color::Green).bold())]),
+        Line::raw(format!("Placement: {}", s.placement)),
+        Line::raw(format!("Expected:  {} attempts", config.locale.attempts(expected))),
+        Line::raw(format!("Time:      {eta}")),
     ];
 
+    Paragraph::new(Text::from(lines))
+        .block(Block::default()
+            .title(" Difficulty calculator ")
+            .title(block::Title::from(" Esc to close ").position(block::Position::Bottom))
+            .padding(Padding::new(3,3,1,1))
+            .title_style(Style::default().bold())
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+        )
+}
+
+/// Popup prompting for a line of text
+fn prompt_widget<'a>(title: &'a str, label: &'a str, draft: &'a str, help: &'a str) -> Paragraph<'a> {
+    Paragraph::new(Line::from(vec![Span::raw(label), Span::styled(draft, Style::default().bold())]))
+        .block(Block::default()
+            .title(title)
+            .title(block::Title::from(help).position(block::Position::Bottom))
+            .padding(Padding::new(3,3,1,1))
+            .title_style(Style::default().bold())
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+        )
+}
+
+/// Rectangle of at most the given size, centered within the area
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+
+fn match_to_line<'a>(m: &'a AddressMatch, config: &TuiConfig) -> Line<'a> {
+    // Highlight the match, and set the checksum apart from the rest of the address if enabled
+    let matched = m.matched_range();
+    let style_of = |i: usize| if matched.contains(&i) {
+        Style::default().fg(config.highlight.get(&m.placement)).add_modifier(Modifier::BOLD)
+    } else if config.show_checksum && difficulty::is_checksum_derived(i) {
+        Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM | Modifier::ITALIC)
+    } else {
+        Style::default().fg(Color::Gray).add_modifier(Modifier::DIM)
+    };
+
+    // Add characters to the last span while they share its style, masking them outside the
+    // match except for the first and last few characters if redacted. Groups of characters
+    // are separated in the style of the characters around the separator, such that only
+    // separators within the match are highlighted.
+    let mut spans: Vec<Span> = Vec::new();
+    let mut push = |c: char, style: Style| match spans.last_mut() {
+        Some(span) if span.style == style => span.content.to_mut().push(c),
+        _ => spans.push(Span::styled(c.to_string(), style)),
+    };
+    let visible = |i: usize| !config.redact_addresses || i < REDACT_VISIBLE_CHARS || i + REDACT_VISIBLE_CHARS >= m.public.len();
+    // The lowercased form follows the canonical one if enabled, styled the same way
+    let forms = if config.show_lowercase { &[false, true][..] } else { &[false] };
+    for &lowercase in forms {
+        if lowercase {
+            push(' ', Style::default());
+            push(' ', Style::default());
+        }
+        for (i, c) in m.public.chars().enumerate() {
+            if config.group_display.is_some_and(|n| i > 0 && i.is_multiple_of(n)) {
+                push(' ', if matched.contains(&i) { style_of(i - 1) } else { style_of(i) });
+            }
+            let c = if lowercase { c.to_ascii_lowercase() } else { c };
+            push(if matched.contains(&i) || visible(i) { c } else { '*' }, style_of(i));
+        }
+    }
+
+    // Rank the match by how rare it is
+    spans.push(Span::styled(format!("  {:>2.0} bits", m.rarity()), Style::default().add_modifier(Modifier::DIM)));
+
     Line::from(spans)
 }
 
-fn matches_to_text(matches: &Vec<AddressMatch>, lines: usize) -> Text {
+fn matches_to_text<'a>(s: &'a GlobalState, lines: usize, width: usize, ui: &UiState, config: &TuiConfig) -> (Text<'a>, usize) {
+    // Matches arriving faster than the list updates are held back from it
+    let matches = &s.matches[..ui.display.shown().min(s.matches.len())];
+
+    // A revealed mnemonic takes up a line of its own
+    let revealed = ui.selected.filter(|_| ui.revealed.is_some());
+    let lines = lines.saturating_sub(revealed.is_some() as usize);
+
+    // Pack as many matches per row as fit the width, if enabled
+    let address_width = difficulty::ADDRESS_LENGTH + config.group_display.map_or(0, |n| (difficulty::ADDRESS_LENGTH - 1) / n);
+    let cell_width = MATCH_CELL_WIDTH + RARITY_WIDTH + address_width - difficulty::ADDRESS_LENGTH + if config.show_lowercase { address_width + 2 } else { 0 };
+    let columns = match config.multi_column {
+        true => ((width + COLUMN_GAP) / (cell_width + COLUMN_GAP)).max(1),
+        false => 1,
+    };
+    let capacity = lines * columns;
+
+    // If there are more matches than fit, only draw the last `capacity` matches,
+    // scrolling back far enough to include the selected match
+    let mut first = matches.len().saturating_sub(capacity);
+    if let Some(selected) = ui.selected {
+        first = first.min(selected);
+    }
+    let matches_to_draw = &matches[first..matches.len().min(first + capacity)];
+
+    // Iterate over rows of matches and render them as lines of text
+    let mut text = Vec::new();
+    for (row_index, row) in matches_to_draw.chunks(columns).enumerate() {
+        let mut row_line = Line::default();
+        let mut mnemonic = None;
+        for (column, m) in row.iter().enumerate() {
+            let index = first + row_index * columns + column;
+            let line = styled_match_line(m, index, s, ui, config);
+
+            // Align the columns by padding every cell to the same width
+            let padding = match columns {
+                1 => 0,
+                _ => cell_width.saturating_sub(line.width()),
+            };
+            if column > 0 {
+                row_line.spans.push(Span::raw(" ".repeat(COLUMN_GAP)));
+            }
+            row_line.spans.extend(line.spans);
+            if padding > 0 {
+                row_line.spans.push(Span::raw(" ".repeat(padding)));
+            }
+            if revealed == Some(index) {
+                mnemonic = Some(m.mnemonic.as_str());
+            }
+        }
+        text.push(row_line);
+        match mnemonic {
+            Some("") => text.push(Line::styled("(mnemonic not saved)", Style::default().add_modifier(Modifier::DIM))),
+            Some(mnemonic) if config.redact_mnemonics => {
+                let masked: Vec<_> = mnemonic.split_whitespace().map(|_| "*****").collect();
+                text.push(Line::styled(format!("{} (redacted)", masked.join(" ")), Style::default().fg(Color::Red)));
+            },
+            Some(mnemonic) => text.push(Line::styled(mnemonic, Style::default().fg(Color::Red))),
+            None => {},
+        }
+    }
+    (text.into(), matches_to_draw.len())
+}
+
+/// Line of a match, marked when it is a milestone, a double vanity, interesting or selected
+fn styled_match_line<'a>(m: &'a AddressMatch, index: usize, s: &GlobalState, ui: &UiState, config: &TuiConfig) -> Line<'a> {
+    let mut line = match_to_line(m, config);
+    if s.milestones.binary_search(&index).is_ok() {
+        line.spans.insert(0, Span::styled("▲ ", Style::default().fg(Color::LightCyan).bold()));
+    }
+    if s.double_vanities.contains(&m.public) {
+        line.spans.insert(0, Span::styled("◆ ", Style::default().fg(Color::LightYellow).bold()));
+    }
+    if config.interesting.iter().any(|rule| rule.matches(m)) {
+        line.spans.insert(0, Span::styled("★ ", Style::default().fg(Color::LightMagenta).bold()));
+        line.patch_style(Style::default().add_modifier(Modifier::UNDERLINED));
+    }
+    if ui.selected == Some(index) {
+        line.patch_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        // Without styles the selection is marked by arrows instead
+        if !config.color {
+            line.spans.insert(0, Span::raw("> "));
+            line.spans.push(Span::raw(" <"));
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::{HashMap, HashSet}, time::Instant};
 
-    // If there are more matches than lines, only draw the last `lines` matches
-    let matches_to_draw = &matches[matches.len().saturating_sub(lines)..];
+    use ratatui::{backend::TestBackend, buffer::{Buffer, Cell}};
 
-    // Iterate over the matches and render them as lines of text
-    matches_to_draw
-        .iter()
-        .map(match_to_line)
-        .collect::<Vec<Line>>()
-        .into()
-}    
\ No newline at end of file
+    use algo_vanity_rs::SearchPlacement;
+    use super::*;
+
+    /// Address of the test matches, which only need to match their pattern
+    const ADDRESS: &str = "ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZE";
+
+    const MNEMONIC: &str = "abandon ability able about above absent absorb abstract absurd abuse access accident account accuse achieve acid acoustic acquire across act action actor actress actual adapt";
+
+    fn config() -> TuiConfig {
+        TuiConfig {
+            refresh_interval: Duration::from_millis(100),
+            highlight: PlacementColors { start: Color::Green, anywhere: Color::Yellow, end: Color::Cyan },
+            color: true,
+            interesting: Vec::new(),
+            locale: NumberLocale::default(),
+            difficulty: DifficultyModel::default(),
+            multi_column: false,
+            confirm_quit: false,
+            group_display: None,
+            redact_mnemonics: false,
+            redact_addresses: false,
+            show_checksum: false,
+            show_lowercase: false,
+            simple: false,
+            headless: None,
+        }
+    }
+
+    /// Address distinct for each `n` below the size of the alphabet
+    fn address(n: usize) -> String {
+        let mut address = ADDRESS.to_string();
+        address.replace_range(4..5, &ADDRESS_CHARS[n..n + 1]);
+        address
+    }
+
+    fn state(matches: Vec<AddressMatch>) -> Arc<Mutex<GlobalState>> {
+        Arc::new(Mutex::new(GlobalState {
+            vanities: vec![String::from("ALGO")],
+            labels: HashMap::new(),
+            threads: 1,
+            active_threads: 1,
+            placement: SearchPlacement { start: true, anywhere: false, end: false },
+            matches,
+            search_rate: 1000.0,
+            total_count: 0,
+            match_count: 0,
+            worker_restarts: 0,
+            unsaved: 0,
+            start_time: Instant::now(),
+            run_time: Duration::ZERO,
+            save_path: String::from("vanities.json"),
+            run_label: None,
+            save_error: None,
+            anywhere_offsets: Vec::new(),
+            warming_up: false,
+            best_prefix: HashMap::new(),
+            expected_attempts: HashMap::new(),
+            milestones: Vec::new(),
+            double_vanities: HashSet::new(),
+            completion: None,
+            aborted: None,
+            pattern_completions: Vec::new(),
+            terminated_threads: Vec::new(),
+        }))
+    }
+
+    /// Ui state showing every match of the state
+    fn ui_showing(state: &Arc<Mutex<GlobalState>>) -> UiState {
+        let mut ui = UiState::default();
+        ui.display.update(state.lock().unwrap().matches.len(), Instant::now());
+        ui
+    }
+
+    fn render(state: &Arc<Mutex<GlobalState>>, ui: &UiState, config: &TuiConfig, width: u16, height: u16) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| ui_function(frame, state, ui, config)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    /// Rows of the rendered text
+    fn rows(buffer: &Buffer) -> Vec<String> {
+        (0..buffer.area.height).map(|y| (0..buffer.area.width).map(|x| buffer.get(x, y).symbol.as_str()).collect()).collect()
+    }
+
+    /// Position of the first character of every rendered occurrence of the text, row by row
+    fn positions(buffer: &Buffer, text: &str) -> Vec<(u16, u16)> {
+        rows(buffer).iter().enumerate()
+            .filter_map(|(y, row)| row.find(text).map(|i| (row[..i].chars().count() as u16, y as u16)))
+            .collect()
+    }
+
+    /// Text of all lines, one per row
+    fn text(text: &Text) -> String {
+        text.lines.iter().map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect::<String>() + "\n").collect()
+    }
+
+    /// Text of the spans of the line in the style, in order
+    fn styled_text(line: &Line, style: Style) -> String {
+        line.spans.iter().filter(|span| span.style == style).map(|span| span.content.as_ref()).collect()
+    }
+
+    /// Rendered text from the label up to the border of its block
+    fn row_with(buffer: &Buffer, label: &str) -> String {
+        rows(buffer).into_iter()
+            .find_map(|row| row.find(label).map(|i| row[i..].split('│').next().unwrap().trim_end().to_string()))
+            .unwrap_or_else(|| panic!("no row with {label}"))
+    }
+
+    #[test]
+    fn refresh_interval_follows_fps() {
+        assert_eq!(refresh_interval(10), Some(Duration::from_millis(100)));
+        assert_eq!(refresh_interval(1), Some(Duration::from_secs(1)));
+        assert_eq!(refresh_interval(MAX_FPS), Some(Duration::from_secs(1) / MAX_FPS));
+        assert_eq!(refresh_interval(0), None);
+        assert_eq!(refresh_interval(MAX_FPS + 1), None);
+    }
+
+    #[test]
+    fn calculator_follows_the_draft() {
+        let state = state(Vec::new());
+        let (config, mut ui) = (config(), UiState::default());
+        let expected_of = |ui: &UiState| row_with(&render(&state, ui, &config, 120, 40), "Expected:");
+
+        ui.calculator = Some(String::from("A"));
+        let short = expected_of(&ui);
+        ui.calculator = Some(String::from("ALG"));
+        let buffer = render(&state, &ui, &config, 120, 40);
+        assert_eq!(row_with(&buffer, "Pattern:"), "Pattern:   ALG");
+        assert_eq!(row_with(&buffer, "Expected:"), format!("Expected:  {} attempts", difficulty::format_attempts(32f64.powi(3))));
+        assert_ne!(short, expected_of(&ui));
+        assert_eq!(short, format!("Expected:  {} attempts", difficulty::format_attempts(32.0)));
+    }
+
+    #[test]
+    fn matches_are_highlighted_by_placement() {
+        let state = state(vec![
+            AddressMatch::new("ALG", ADDRESS, "", Placement::Start),
+            AddressMatch::new("VUDU", ADDRESS, "", Placement::Anywhere(8)),
+            AddressMatch::new("ZAZE", ADDRESS, "", Placement::End),
+        ]);
+        let buffer = render(&state, &ui_showing(&state), &config(), 120, 40);
+
+        let found = positions(&buffer, ADDRESS);
+        assert_eq!(found.len(), 3);
+        for ((x, y), (range, color)) in found.into_iter().zip([(0..3, Color::Green), (8..12, Color::Yellow), (54..58, Color::Cyan)]) {
+            for i in 0..ADDRESS.len() as u16 {
+                let cell = buffer.get(x + i, y);
+                match range.contains(&i) {
+                    true => assert_eq!((cell.fg, cell.modifier.contains(Modifier::BOLD)), (color, true), "{i}"),
+                    false => assert_eq!(cell.fg, Color::Gray, "{i}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn revealed_mnemonic_hides_after_timeout() {
+        let state = state(vec![AddressMatch::new("ALG", ADDRESS, MNEMONIC, Placement::Start)]);
+        let config = config();
+        let mut ui = ui_showing(&state);
+        ui.select(-1, 1);
+        let revealed = Instant::now();
+        ui.revealed = Some(revealed);
+        ui.status = Some((String::from("Exported match"), revealed));
+        assert_eq!(positions(&render(&state, &ui, &config, 250, 40), MNEMONIC).len(), 1);
+
+        // The status outlives the export by less than the reveal
+        ui.expire_reveal(revealed + STATUS_TIMEOUT);
+        assert_eq!((ui.revealed, ui.status.is_none()), (Some(revealed), true));
+        ui.expire_reveal(revealed + REVEAL_TIMEOUT - Duration::from_millis(1));
+        assert_eq!(ui.revealed, Some(revealed));
+        ui.expire_reveal(revealed + REVEAL_TIMEOUT);
+        assert_eq!(ui.revealed, None);
+        assert!(positions(&render(&state, &ui, &config, 250, 40), MNEMONIC).is_empty());
+    }
+
+    #[test]
+    fn no_color_renders_plain_text() {
+        let state = state(vec![AddressMatch::new("ALG", ADDRESS, "", Placement::Start)]);
+        let mut ui = ui_showing(&state);
+        ui.select(-1, 1);
+        let styled = |cell: &Cell| cell.fg != Color::Reset || cell.bg != Color::Reset || !cell.modifier.is_empty();
+
+        let colored = render(&state, &ui, &config(), 120, 40);
+        assert!(colored.content.iter().any(styled));
+
+        let plain = render(&state, &ui, &TuiConfig { color: false, ..config() }, 120, 40);
+        assert!(!plain.content.iter().any(styled));
+        assert_eq!(rows(&plain).iter().filter(|row| row.contains(&format!("> {ADDRESS}"))).count(), 1);
+    }
+
+    #[test]
+    fn wide_terminal_packs_matches_into_columns() {
+        let state = state((0..6).map(|n| AddressMatch::new("ALGO", address(n), "", Placement::Start)).collect());
+        let ui = ui_showing(&state);
+        let per_row = |config: &TuiConfig| -> Vec<usize> {
+            rows(&render(&state, &ui, config, 250, 40)).iter()
+                .map(|row| (0..6).filter(|n| row.contains(&address(*n))).count())
+                .filter(|count| *count > 0)
+                .collect()
+        };
+
+        assert_eq!(per_row(&TuiConfig { multi_column: true, ..config() }), [3, 3]);
+        assert_eq!(per_row(&config()), [1; 6]);
+
+        // Narrow terminals keep a single column
+        let narrow = rows(&render(&state, &ui, &TuiConfig { multi_column: true, ..config() }, 100, 40));
+        assert!(narrow.iter().all(|row| (0..6).filter(|n| row.contains(&address(*n))).count() <= 1));
+    }
+
+    #[test]
+    fn redaction_masks_the_display_only() {
+        let state = state(vec![AddressMatch::new("ALGO", ADDRESS, MNEMONIC, Placement::Start)]);
+        let mut ui = ui_showing(&state);
+        ui.select(-1, 1);
+        ui.revealed = Some(Instant::now());
+        let config = TuiConfig { redact_mnemonics: true, redact_addresses: true, ..config() };
+        let buffer = render(&state, &ui, &config, 250, 40);
+
+        let masked_words = vec!["*****"; MNEMONIC.split_whitespace().count()].join(" ");
+        assert_eq!(positions(&buffer, &format!("{masked_words} (redacted)")).len(), 1);
+        assert!(positions(&buffer, MNEMONIC).is_empty() && positions(&buffer, "abandon").is_empty());
+
+        // Only the matched pattern and the last few characters of the address stay visible
+        let masked_address = format!("ALGO{}{}", "*".repeat(ADDRESS.len() - 8), &ADDRESS[ADDRESS.len() - 4..]);
+        assert_eq!(positions(&buffer, &masked_address).len(), 1);
+        assert!(positions(&buffer, ADDRESS).is_empty());
+
+        // The match itself keeps its secrets
+        let s = state.lock().unwrap();
+        assert_eq!((s.matches[0].public.as_str(), s.matches[0].mnemonic.as_str()), (ADDRESS, MNEMONIC));
+    }
+
+    #[test]
+    fn grouped_addresses_keep_their_highlight() {
+        let grouped: Vec<&str> = ADDRESS.as_bytes().chunks(4).map(|c| std::str::from_utf8(c).unwrap()).collect();
+        let grouped = grouped.join(" ");
+        let grouping = TuiConfig { group_display: Some(4), ..config() };
+        let highlight = Style::default().fg(Color::Green).add_modifier(Modifier::BOLD);
+
+        // Separators within the match keep its highlight, the ones around it do not
+        let m = AddressMatch::new("ALGOAA", ADDRESS, "", Placement::Start);
+        let line = match_to_line(&m, &grouping);
+        assert!(line.spans.iter().map(|span| span.content.as_ref()).collect::<String>().starts_with(&grouped));
+        assert_eq!(styled_text(&line, highlight), "ALGO AA");
+
+        let m = AddressMatch::new("VUDU", ADDRESS, "", Placement::Anywhere(8));
+        let line = match_to_line(&m, &TuiConfig { highlight: PlacementColors { start: Color::Red, anywhere: Color::Green, end: Color::Red }, ..grouping });
+        assert_eq!(styled_text(&line, highlight), "VUDU");
+
+        let state = state(vec![m]);
+        let buffer = render(&state, &ui_showing(&state), &TuiConfig { group_display: Some(4), ..config() }, 120, 40);
+        let (x, y) = positions(&buffer, &grouped)[0];
+        assert!((0..grouped.len() as u16).all(|i| (buffer.get(x + i, y).fg == Color::Yellow) == (10..14).contains(&i)));
+    }
+
+    #[test]
+    fn checksum_characters_are_set_apart() {
+        let m = AddressMatch::new("ALGO", ADDRESS, "", Placement::Start);
+        let checksum = Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM | Modifier::ITALIC);
+        let tail = &ADDRESS[difficulty::PAYLOAD_LENGTH..];
+
+        assert_eq!(styled_text(&match_to_line(&m, &TuiConfig { show_checksum: true, ..config() }), checksum), tail);
+        assert_eq!(styled_text(&match_to_line(&m, &config()), checksum), "");
+
+        // Matched characters within the checksum keep their highlight
+        let end = AddressMatch::new("ZAZE", ADDRESS, "", Placement::End);
+        assert_eq!(styled_text(&match_to_line(&end, &TuiConfig { show_checksum: true, ..config() }), checksum), &tail[..tail.len() - 4]);
+
+        let state = state(vec![m]);
+        let buffer = render(&state, &ui_showing(&state), &TuiConfig { show_checksum: true, ..config() }, 120, 40);
+        let (x, y) = positions(&buffer, ADDRESS)[0];
+        for i in 0..ADDRESS.len() {
+            let italic = buffer.get(x + i as u16, y).modifier.contains(Modifier::ITALIC);
+            assert_eq!(italic, difficulty::is_checksum_derived(i), "{i}");
+        }
+    }
+
+    #[test]
+    fn selection_stops_at_the_oldest_and_newest_match() {
+        let mut ui = UiState::default();
+        ui.select(-1, 0);
+        assert_eq!(ui.selected, None);
+
+        // Selection starts from the newest match
+        ui.select(-1, 5);
+        assert_eq!(ui.selected, Some(4));
+        ui.select(1, 5);
+        assert_eq!(ui.selected, Some(4));
+
+        // Home and End jump to either bound, hiding a revealed mnemonic
+        ui.revealed = Some(Instant::now());
+        ui.select(isize::MIN, 5);
+        assert_eq!((ui.selected, ui.revealed), (Some(0), None));
+        ui.select(-1, 5);
+        assert_eq!(ui.selected, Some(0));
+        ui.select(isize::MAX, 5);
+        assert_eq!(ui.selected, Some(4));
+    }
+
+    #[test]
+    fn selecting_the_oldest_match_scrolls_to_it() {
+        let state = state((0..20).map(|n| AddressMatch::new("ALGO", address(n), "", Placement::Start)).collect());
+        let mut ui = ui_showing(&state);
+        let config = config();
+        let s = state.lock().unwrap();
+        let drawn = |ui: &UiState| text(&matches_to_text(&s, 5, 200, ui, &config).0);
+
+        // Only the newest matches fit, until the oldest is selected
+        assert!(drawn(&ui).contains(&address(19)) && !drawn(&ui).contains(&address(0)));
+        ui.select(isize::MIN, 20);
+        assert!(drawn(&ui).contains(&address(0)) && !drawn(&ui).contains(&address(19)));
+        ui.select(isize::MAX, 20);
+        assert!(drawn(&ui).contains(&address(19)) && !drawn(&ui).contains(&address(0)));
+    }
+}
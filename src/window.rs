@@ -0,0 +1,76 @@
+//! Patterns which may only appear within a window of character positions
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::{ADDRESS_CHARS, difficulty::ADDRESS_LENGTH};
+
+/// Pattern searched for only within the characters `start..end` of the address, which
+/// generalizes the other placements, such as `0:4:ALGO` matching at the start.
+///
+/// ```
+/// use algo_vanity_rs::window::PatternWindow;
+///
+/// let address = "XXALGOXXXXRANDXXXX";
+///
+/// // Interior windows
+/// let algo: PatternWindow = "1:8:algo".parse().unwrap();
+/// assert_eq!(algo.find(address), Some(2));
+/// assert_eq!("3:8:ALGO".parse::<PatternWindow>().unwrap().find(address), None);
+/// assert_eq!("1:5:ALGO".parse::<PatternWindow>().unwrap().find(address), None);
+///
+/// // Windows touching the start and end of the address
+/// assert_eq!("0:6:ALGO".parse::<PatternWindow>().unwrap().find(address), Some(2));
+/// assert_eq!("0:4:XXAL".parse::<PatternWindow>().unwrap().find(address), Some(0));
+/// assert_eq!("54:58:XXXX".parse::<PatternWindow>().unwrap().find(&"X".repeat(58)), Some(54));
+///
+/// // Windows which do not fit the pattern or an address are rejected
+/// assert!("0:59:ALGO".parse::<PatternWindow>().is_err());
+/// assert!("4:7:ALGO".parse::<PatternWindow>().is_err());
+/// assert!("8:4:ALGO".parse::<PatternWindow>().is_err());
+/// assert!("0:8:AL0O".parse::<PatternWindow>().is_err());
+/// ```
+#[derive(Clone,Debug,PartialEq,Eq,Serialize)]
+pub struct PatternWindow {
+    /// First character of the window
+    pub start: usize,
+    /// Character just past the window
+    pub end: usize,
+    /// Upper-case pattern to find within the window
+    pub pattern: String,
+}
+
+impl PatternWindow {
+    /// Index of the first occurrence of the pattern within the window of the address
+    pub fn find(&self, address: &str) -> Option<usize> {
+        address.get(self.start..self.end)?.find(&self.pattern).map(|index| self.start + index)
+    }
+}
+
+impl FromStr for PatternWindow {
+    type Err = String;
+
+    /// Parse `START:END:PATTERN`, where the window is the characters `START..END`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, ':');
+        let (Some(start), Some(end), Some(pattern)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(format!("expected START:END:PATTERN, got '{s}'"));
+        };
+        let start: usize = start.parse().map_err(|_| format!("invalid window start '{start}'"))?;
+        let end: usize = end.parse().map_err(|_| format!("invalid window end '{end}'"))?;
+        if start >= end || end > ADDRESS_LENGTH {
+            return Err(format!("window {start}:{end} must satisfy START < END <= {ADDRESS_LENGTH}"));
+        }
+
+        let pattern = pattern.to_uppercase();
+        if pattern.is_empty() || pattern.len() > end - start {
+            return Err(format!("pattern '{pattern}' does not fit in the window {start}:{end}"));
+        }
+        if let Some(c) = pattern.chars().find(|c| !ADDRESS_CHARS.contains(*c)) {
+            return Err(format!("pattern character '{c}' can not exist in an Algorand Address"));
+        }
+
+        Ok(PatternWindow { start, end, pattern })
+    }
+}
@@ -0,0 +1,32 @@
+//! Searches which only send the addresses of matches
+
+use algo_vanity_rs::{Placement, SearchConfig, SearchPlacement, VanitySearcher};
+
+#[test]
+fn light_path_matches_correctly() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-address-only-{}.json", std::process::id()));
+
+    // Single character patterns are found almost immediately
+    let mut searcher = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A"), String::from("Q")],
+        threads: 1,
+        placement: SearchPlacement { start: true, anywhere: false, end: true },
+        save_path: save_path.to_string_lossy().into_owned(),
+        once: true,
+        address_only: true,
+        seed: Some(0),
+        ..SearchConfig::default()
+    });
+    let matches = searcher.stream_matches();
+    searcher.start().join();
+    let matches: Vec<_> = matches.iter().collect();
+    _ = std::fs::remove_file(&save_path);
+
+    assert_eq!(matches.len(), 2);
+    for m in &matches {
+        assert!(m.placement.matches(&m.public, &m.target), "{} does not match {}", m.public, m.target);
+        assert!(matches!(m.placement, Placement::Start | Placement::End));
+        assert!(m.mnemonic.is_empty());
+        assert!(m.seed.is_none());
+    }
+}
@@ -0,0 +1,28 @@
+//! Results files listing only the addresses of the matches
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher, format::ResultsFormat};
+
+#[test]
+fn addresses_format_lists_exactly_the_addresses() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-addresses-{}.txt", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A"), String::from("Q"), String::from("Z")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        format: Some(ResultsFormat::Addresses),
+        address_only: true,
+        once: true,
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    let contents = std::fs::read_to_string(&save_path).unwrap();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    let expected: String = s.matches.iter().map(|m| format!("{}\n", m.public())).collect();
+    assert_eq!(s.matches.len(), 3);
+    assert_eq!(contents, expected);
+}
@@ -0,0 +1,30 @@
+//! Searches bounded by a number of attempts
+
+use algo_vanity_rs::{COUNT_PER_LOOP, SearchConfig, SearchPlacement, VanitySearcher};
+
+#[test]
+fn search_stops_near_attempt_budget() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-attempts-{}.json", std::process::id()));
+    let threads = 2;
+    let budget = 3 * COUNT_PER_LOOP * COUNT_PER_LOOP;
+
+    // The digit 0 is not part of the address alphabet, so the pattern never matches
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("0")],
+        threads,
+        placement: SearchPlacement { start: false, anywhere: true, end: false },
+        save_path: save_path.to_string_lossy().into_owned(),
+        attempts: Some(budget),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    // Each thread may finish one more batch after the budget is reached
+    let total = state.lock().unwrap().total_count;
+    assert!(total >= budget, "stopped after {total} attempts, before the budget of {budget}");
+    assert!(total <= budget + threads * COUNT_PER_LOOP * COUNT_PER_LOOP, "ran {total} attempts, far beyond the budget of {budget}");
+}
@@ -0,0 +1,44 @@
+//! Cancelling individual patterns of a running search
+
+use std::{thread, time::{Duration, Instant}};
+
+use algo_vanity_rs::{COUNT_PER_LOOP, SearchConfig, VanitySearcher};
+
+#[test]
+fn cancelled_pattern_stops_matching() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-cancel-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AA"), String::from("BB")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let count = || handle.state().lock().unwrap().total_count;
+    let matches_of = |target: &str| handle.state().lock().unwrap().matches.iter().filter(|m| m.target == target).count();
+    let wait_for_batches = |batches: usize| {
+        let until = count() + batches * COUNT_PER_LOOP * COUNT_PER_LOOP;
+        let deadline = Instant::now() + Duration::from_secs(120);
+        while count() < until && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+    };
+
+    assert!(handle.cancel_pattern("AA"));
+    assert!(!handle.cancel_pattern("AA"));
+
+    // The batch which was running when cancelling may still report the pattern
+    wait_for_batches(2);
+    let matches_after_cancel = matches_of("AA");
+    let other_matches = matches_of("BB");
+    wait_for_batches(2);
+
+    assert_eq!(matches_of("AA"), matches_after_cancel);
+    assert!(matches_of("BB") > other_matches);
+    assert_eq!(handle.state().lock().unwrap().vanities, ["BB"]);
+
+    handle.stop();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+}
@@ -0,0 +1,29 @@
+//! Finding several candidate matches of each pattern before it is done
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher, load_matches};
+
+#[test]
+fn pattern_is_retired_after_candidates() {
+    let candidates = 3;
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-candidates-{}.json", std::process::id())).to_string_lossy().into_owned();
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AB")],
+        threads: 1,
+        save_path: save_path.clone(),
+        once: true,
+        candidates,
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    let saved = load_matches(&save_path).unwrap();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    assert!(s.vanities.is_empty());
+    assert_eq!(s.match_count, candidates);
+    assert_eq!(saved.len(), candidates);
+    assert!(saved.iter().all(|m| m.target() == "AB"));
+}
@@ -0,0 +1,69 @@
+//! Checksum-aware difficulty estimates, compared with the uniform model
+
+use algo_vanity_rs::{PlacementMode, SearchPlacement, difficulty::{ADDRESS_LENGTH, DifficultyModel}};
+
+const ANYWHERE: SearchPlacement = SearchPlacement { start: false, anywhere: true, end: false };
+
+#[test]
+fn patterns_outside_the_final_character_are_estimated_alike() {
+    let (uniform, aware) = (DifficultyModel::Uniform, DifficultyModel::ChecksumAware);
+    for pattern in ["A", "ALGO", "ZZZZZZ", "RAND7"] {
+        for mode in [PlacementMode::Start, PlacementMode::End, PlacementMode::StartEnd] {
+            let placement = mode.into();
+            let (u, a) = (uniform.probability(pattern, &placement), aware.probability(pattern, &placement));
+            assert!((u - a).abs() <= u * 1e-12, "{pattern} {placement}: {u} vs {a}");
+        }
+    }
+
+    // Too long to reach the final character anywhere but at the end
+    let long = "B".repeat(ADDRESS_LENGTH - 1);
+    let start = PlacementMode::Start.into();
+    assert_eq!(aware.probability(&long, &start), uniform.probability(&long, &start));
+}
+
+#[test]
+fn anywhere_estimates_account_for_the_final_character() {
+    let (uniform, aware) = (DifficultyModel::Uniform, DifficultyModel::ChecksumAware);
+
+    // The final character can never be 'B', leaving one position fewer
+    let positions = (ADDRESS_LENGTH - 2 + 1) as f64;
+    let ratio = aware.probability("QB", &ANYWHERE) / uniform.probability("QB", &ANYWHERE);
+    assert!((ratio - (positions - 1.0) / positions).abs() < 1e-12);
+
+    // The final character is 'Q' four times as often as any one character elsewhere
+    let ratio = aware.probability("BQ", &ANYWHERE) / uniform.probability("BQ", &ANYWHERE);
+    assert!((ratio - (positions + 3.0) / positions).abs() < 1e-12);
+
+    // Only the last character of a pattern can land on the final character of the address
+    assert!(aware.expected_attempts("QB", &ANYWHERE) > aware.expected_attempts("BQ", &ANYWHERE));
+    assert_eq!(uniform.expected_attempts("QB", &ANYWHERE), uniform.expected_attempts("BQ", &ANYWHERE));
+}
+
+#[test]
+fn full_length_patterns_follow_the_final_character() {
+    let aware = DifficultyModel::ChecksumAware;
+    let anywhere_end = "A".repeat(ADDRESS_LENGTH);
+    let never_ends = "B".repeat(ADDRESS_LENGTH);
+
+    assert!(aware.probability(&anywhere_end, &ANYWHERE) > 0.0);
+    assert_eq!(aware.probability(&never_ends, &ANYWHERE), 0.0);
+    assert!(aware.is_impractical(&never_ends, &PlacementMode::Start.into()));
+}
+
+#[test]
+fn plan_uses_the_checksum_aware_model_on_request() {
+    let plan = |extra: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_algo-vanity-rs"))
+            .args(["plan", "qqqb", "--placement", "anywhere"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let uniform = plan(&[]);
+    let aware = plan(&["--include-checksum-aware"]);
+    assert!(uniform.contains("Expected attempts: 19,066"), "{uniform}");
+    assert!(aware.contains("Expected attempts: 19,419"), "{aware}");
+}
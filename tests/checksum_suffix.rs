@@ -0,0 +1,40 @@
+//! Matches required to also end their checksum characters with a suffix
+
+use algo_vanity_rs::{PlacementMode, SearchConfig, VanitySearcher, checksum::ChecksumConstraint, difficulty::{self, DifficultyModel}, verify_match};
+
+#[test]
+fn matches_satisfy_prefix_and_checksum_suffix() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-checksum-{}.json", std::process::id()));
+    let checksum: ChecksumConstraint = "qe".parse().unwrap();
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        checksum: Some(checksum.clone()),
+        once: true,
+        candidates: 3,
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let matches = state.lock().unwrap().matches.clone();
+    assert_eq!(matches.len(), 3);
+    for m in &matches {
+        assert!(m.public.starts_with('A'), "{}", m.public);
+        assert!(difficulty::checksum_region(&m.public).ends_with("QE"), "{}", m.public);
+        assert!(checksum.satisfied(&m.public));
+        assert!(verify_match(m).is_ok());
+    }
+}
+
+#[test]
+fn checksum_suffix_compounds_difficulty() {
+    let checksum: ChecksumConstraint = "QE".parse().unwrap();
+    let model = DifficultyModel::Uniform;
+    let prefix = model.probability("ALGO", &PlacementMode::Start.into());
+    assert_eq!(checksum.factor(model), 32.0 * 8.0);
+    assert_eq!((prefix * checksum.probability(model)).recip(), 32f64.powi(4) * 256.0);
+}
@@ -0,0 +1,66 @@
+//! Completion marker file for orchestrators
+
+use std::time::Duration;
+
+use algo_vanity_rs::{COUNT_PER_LOOP, SearchConfig, VanitySearcher};
+
+fn config(name: &str) -> (SearchConfig, std::path::PathBuf, std::path::PathBuf) {
+    let dir = std::env::temp_dir();
+    let save_path = dir.join(format!("algo-vanity-completion-{name}-{}.json", std::process::id()));
+    let marker = dir.join(format!("algo-vanity-completion-{name}-{}.done", std::process::id()));
+    let config = SearchConfig {
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        completion_file: Some(marker.to_string_lossy().into_owned()),
+        seed: Some(0),
+        ..SearchConfig::default()
+    };
+    (config, save_path, marker)
+}
+
+#[test]
+fn marker_written_once_all_patterns_are_found() {
+    let (config, save_path, marker) = config("found");
+    VanitySearcher::new(SearchConfig { vanities: vec![String::from("A"), String::from("B")], once: true, ..config }).start().join();
+
+    let contents = std::fs::read_to_string(&marker);
+    _ = std::fs::remove_file(&save_path);
+    _ = std::fs::remove_file(&marker);
+
+    let json: serde_json::Value = serde_json::from_str(&contents.unwrap()).unwrap();
+    assert_eq!(json["status"], "all_found");
+    assert_eq!(json["matches"], 2);
+    assert_eq!(json["results"], save_path.to_string_lossy().as_ref());
+}
+
+#[test]
+fn marker_written_once_attempt_budget_is_reached() {
+    let (config, save_path, marker) = config("budget");
+    VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AAAAAAAA")],
+        attempts: Some(COUNT_PER_LOOP * COUNT_PER_LOOP),
+        ..config
+    }).start().join();
+
+    let contents = std::fs::read_to_string(&marker);
+    _ = std::fs::remove_file(&save_path);
+    _ = std::fs::remove_file(&marker);
+
+    let json: serde_json::Value = serde_json::from_str(&contents.unwrap()).unwrap();
+    assert_eq!(json["status"], "attempt_budget");
+    assert_eq!(json["attempts"], COUNT_PER_LOOP * COUNT_PER_LOOP);
+}
+
+#[test]
+fn no_marker_when_interrupted() {
+    let (config, save_path, marker) = config("interrupted");
+    let handle = VanitySearcher::new(SearchConfig { vanities: vec![String::from("AAAAAAAA")], once: true, ..config }).start();
+    std::thread::sleep(Duration::from_millis(200));
+    handle.stop();
+    handle.join();
+
+    let exists = marker.exists();
+    _ = std::fs::remove_file(&save_path);
+    _ = std::fs::remove_file(&marker);
+    assert!(!exists);
+}
@@ -0,0 +1,52 @@
+//! Attempts and time into the run at which matches were found
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher};
+
+#[test]
+fn attempt_index_increases_across_matches() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-discovery-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AB")],
+        record_discovery: true,
+        attempts: Some(50_000),
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    let saved = std::fs::read_to_string(&save_path);
+    _ = std::fs::remove_file(&save_path);
+
+    let matches = state.lock().unwrap().matches.clone();
+    assert!(matches.len() > 1);
+    let attempts: Vec<usize> = matches.iter().map(|m| m.found_after_attempts.unwrap()).collect();
+    assert!(attempts.windows(2).all(|pair| pair[0] <= pair[1]), "{attempts:?}");
+    assert!(attempts.first() < attempts.last(), "{attempts:?}");
+
+    let secs: Vec<f64> = matches.iter().map(|m| m.found_after_secs.unwrap()).collect();
+    assert!(secs.windows(2).all(|pair| pair[0] <= pair[1]), "{secs:?}");
+
+    let saved = saved.unwrap();
+    assert!(saved.contains("\"found_after_attempts\""));
+    assert!(saved.contains("\"found_after_secs\""));
+}
+
+#[test]
+fn discovery_left_out_unless_recorded() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-no-discovery-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        once: true,
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    handle.join();
+    let saved = std::fs::read_to_string(&save_path);
+    _ = std::fs::remove_file(&save_path);
+
+    assert!(!saved.unwrap().contains("found_after"));
+}
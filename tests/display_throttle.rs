@@ -0,0 +1,30 @@
+//! Rate limiting of the visible match list under a flood of matches
+
+use std::time::{Duration, Instant};
+
+use algo_vanity_rs::throttle::DisplayThrottle;
+
+#[test]
+fn flood_of_matches_updates_list_at_most_once_per_interval() {
+    let start = Instant::now();
+    let mut throttle = DisplayThrottle::new(Duration::from_millis(250));
+
+    // A match arrives every millisecond for a second, with the list redrawn every 10 ms
+    let mut updates = 0;
+    let mut shown = 0;
+    for ms in (0..1000).step_by(10) {
+        let available = ms + 1;
+        let now = start + Duration::from_millis(ms as u64);
+        let visible = throttle.update(available, now);
+        if visible != shown {
+            updates += 1;
+            shown = visible;
+        }
+        assert!(visible <= available);
+    }
+    assert_eq!(updates, 4);
+
+    // Every match is shown once the flood stops
+    assert_eq!(throttle.update(1000, start + Duration::from_secs(2)), 1000);
+    assert_eq!(throttle.pending(1000), 0);
+}
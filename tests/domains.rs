@@ -0,0 +1,46 @@
+//! Domain separation of the accounts generated by each worker thread
+
+use std::collections::HashSet;
+
+use rand::{SeedableRng, rngs::StdRng};
+use algo_vanity_rs::{Accounts, COUNT_PER_LOOP, Partition, keys::KeyType};
+
+/// Enough accounts to span more than one seed batch
+const N: usize = COUNT_PER_LOOP * COUNT_PER_LOOP + COUNT_PER_LOOP;
+
+/// Seeds of the first `n` accounts generated in a domain
+fn seeds(accounts: Accounts, domain: u16, n: usize) -> Vec<[u8; 32]> {
+    accounts.with_domain(domain).take(n).map(|acc| acc.seed()).collect()
+}
+
+#[test]
+fn threads_draw_from_disjoint_streams() {
+    // Both threads get the very same generator, the worst case for overlap
+    let from_same_rng = |domain| seeds(Accounts::new(StdRng::seed_from_u64(0), KeyType::default()), domain, N);
+    let (first, second): (HashSet<_>, HashSet<_>) = (from_same_rng(0).into_iter().collect(), from_same_rng(1).into_iter().collect());
+
+    assert_eq!(first.len(), N);
+    assert_eq!(second.len(), N);
+    assert!(first.is_disjoint(&second));
+}
+
+#[test]
+fn threads_of_a_partition_draw_from_disjoint_streams() {
+    let partitioned = |domain| -> HashSet<_> {
+        seeds(Accounts::partitioned(0, Partition::default(), KeyType::default()), domain, N).into_iter().collect()
+    };
+    assert!(partitioned(0).is_disjoint(&partitioned(1)));
+    assert_eq!(partitioned(1), partitioned(1));
+}
+
+#[test]
+fn seeds_carry_no_trace_of_their_domain() {
+    // The leading bytes of the seeds of a domain are as random as the rest, rather than fixed
+    let batch_starts = |domain| -> HashSet<[u8; 2]> {
+        seeds(Accounts::new(StdRng::seed_from_u64(0), KeyType::default()), domain, 3 * COUNT_PER_LOOP * COUNT_PER_LOOP)
+            .iter().step_by(COUNT_PER_LOOP * COUNT_PER_LOOP).map(|seed| [seed[0], seed[1]]).collect()
+    };
+    for domain in [1, 7] {
+        assert_eq!(batch_starts(domain).len(), 3);
+    }
+}
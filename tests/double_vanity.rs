@@ -0,0 +1,35 @@
+//! Flagging addresses which match several different patterns
+
+use std::collections::{HashMap, HashSet};
+
+use algo_vanity_rs::{COUNT_PER_LOOP, PlacementMode, SearchConfig, VanitySearcher};
+
+#[test]
+fn addresses_matching_two_patterns_are_flagged() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-double-vanity-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AB"), String::from("CD")],
+        threads: 1,
+        placement: PlacementMode::Anywhere.into(),
+        save_path: save_path.to_string_lossy().into_owned(),
+        attempts: Some(COUNT_PER_LOOP * COUNT_PER_LOOP),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let state = state.lock().unwrap();
+    let mut targets: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for m in &state.matches {
+        targets.entry(m.public()).or_default().insert(m.target());
+    }
+
+    assert!(!state.double_vanities.is_empty());
+    assert!(state.double_vanities.len() < targets.len());
+    for (public, targets) in targets {
+        assert_eq!(state.double_vanities.contains(public), targets.len() >= 2, "{public} matched {targets:?}");
+    }
+}
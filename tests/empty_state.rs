@@ -0,0 +1,29 @@
+//! Lines of the terminal ui before the first match
+
+use algo_vanity_rs::{PlacementMode, difficulty::{DifficultyModel, empty_state}, locale::NumberLocale};
+
+fn lines(vanities: &[&str], placement: PlacementMode, rate: f64) -> Vec<String> {
+    let vanities: Vec<String> = vanities.iter().map(|v| v.to_string()).collect();
+    empty_state(DifficultyModel::Uniform, &vanities, &placement.into(), rate, NumberLocale::En)
+}
+
+#[test]
+fn hard_pattern_gets_a_tip() {
+    assert_eq!(lines(&["ALGO", "RANDOM"], PlacementMode::Start, 100_000.0), [
+        "No matches yet, the search is running",
+        "Expected first match in about 10s (1,047,553 attempts)",
+        "Tip: the 6-character prefix RANDOM alone may take 2h 58m, every character less makes it 32 times faster",
+    ]);
+}
+
+#[test]
+fn easy_patterns_get_no_tip() {
+    let lines = lines(&["ALGA"], PlacementMode::End, 100_000.0);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].starts_with("Expected first match in about "));
+}
+
+#[test]
+fn no_patterns_left() {
+    assert_eq!(lines(&[], PlacementMode::Start, 100_000.0), ["No patterns left to search for"]);
+}
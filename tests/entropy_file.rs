@@ -0,0 +1,50 @@
+//! Seeding the workers from a user-provided entropy file
+
+use algo_vanity_rs::{ENTROPY_PER_THREAD, SearchConfig, VanitySearcher};
+
+/// Addresses of the first matches of a search seeded from an entropy file of the given bytes
+fn search(name: &str, entropy: &[u8], threads: usize) -> (Vec<String>, Option<String>) {
+    let dir = std::env::temp_dir();
+    let save_path = dir.join(format!("algo-vanity-entropy-{name}-{}.json", std::process::id()));
+    let entropy_path = dir.join(format!("algo-vanity-entropy-{name}-{}.bin", std::process::id()));
+    std::fs::write(&entropy_path, entropy).unwrap();
+
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A"), String::from("B"), String::from("C")],
+        once: true,
+        threads,
+        save_path: save_path.to_string_lossy().into_owned(),
+        entropy_file: Some(entropy_path.to_string_lossy().into_owned()),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+    _ = std::fs::remove_file(&entropy_path);
+
+    let state = state.lock().unwrap();
+    let mut addresses: Vec<String> = state.matches.iter().map(|m| m.public.clone()).collect();
+    addresses.sort();
+    (addresses, state.aborted.clone())
+}
+
+#[test]
+fn same_entropy_generates_same_accounts() {
+    let entropy: Vec<u8> = (0..ENTROPY_PER_THREAD as u8).collect();
+    let (first, aborted) = search("first", &entropy, 1);
+    let (second, _) = search("second", &entropy, 1);
+    assert_eq!(aborted, None);
+    assert_eq!(first.len(), 3);
+    assert_eq!(first, second);
+
+    let other: Vec<u8> = entropy.iter().map(|b| b ^ 0xff).collect();
+    let (third, _) = search("third", &other, 1);
+    assert_ne!(first, third);
+}
+
+#[test]
+fn too_little_entropy_aborts_the_search() {
+    let (matches, aborted) = search("short", &[0; ENTROPY_PER_THREAD], 2);
+    assert!(matches.is_empty());
+    assert!(aborted.unwrap().contains("2 threads need 64 bytes"));
+}
@@ -0,0 +1,48 @@
+//! Expected attempts to find each pattern, estimated before searching
+
+use std::process::Command;
+
+use algo_vanity_rs::{PlacementMode, SearchConfig, VanitySearcher, checksum::ChecksumConstraint};
+
+#[test]
+fn state_holds_the_expected_attempts_of_each_pattern() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-estimates-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("ALGO"), String::from("RAND")],
+        placement: PlacementMode::StartEnd.into(),
+        windows: vec!["10:20:XYZ".parse().unwrap()],
+        checksum: Some("Q".parse::<ChecksumConstraint>().unwrap()),
+        attempts: Some(1),
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    // Neither pattern can end an address, and the checksum suffix makes every match 8 times rarer
+    let s = state.lock().unwrap();
+    assert_eq!(s.expected_attempts["ALGO"], 32f64.powi(4) * 8.0);
+    assert_eq!(s.expected_attempts["RAND"], 32f64.powi(4) * 8.0);
+    // The window holds the pattern at 8 places
+    assert_eq!(s.expected_attempts["XYZ"], 32f64.powi(3) / 8.0 * 8.0);
+}
+
+#[test]
+fn startup_prints_the_expected_attempts_table() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-estimates-table-{}.json", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_algo-vanity-rs"))
+        .args(["--headless", "--attempts", "1", "--threads", "1", "--seed", "0", "--path"])
+        .arg(&save_path)
+        .args(["A", "algo"])
+        .output()
+        .unwrap();
+    _ = std::fs::remove_file(&save_path);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let table: Vec<&str> = stdout.lines().skip_while(|line| !line.starts_with("Pattern")).take(3).collect();
+    assert_eq!(table, ["Pattern  Expected attempts", "A                       32", "ALGO             1,048,576"], "{stdout}");
+}
@@ -0,0 +1,30 @@
+//! Saving only selected fields of each match
+
+use algo_vanity_rs::{COUNT_PER_LOOP, SearchConfig, VanitySearcher, format::MatchField};
+
+#[test]
+fn output_has_requested_fields_in_order() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-fields-{}.jsonl", std::process::id())).to_string_lossy().into_owned();
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        threads: 1,
+        save_path: save_path.clone(),
+        fields: vec![MatchField::Public, MatchField::Placement, MatchField::Target],
+        attempts: Some(COUNT_PER_LOOP * COUNT_PER_LOOP),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+    handle.join();
+
+    let contents = std::fs::read_to_string(&save_path).unwrap();
+    _ = std::fs::remove_file(&save_path);
+
+    assert!(contents.lines().count() > 0);
+    for line in contents.lines() {
+        let record: serde_json::Map<String, serde_json::Value> = serde_json::from_str(line).unwrap();
+        assert_eq!(record.len(), 3);
+        let positions: Vec<_> = ["\"public\":", "\"placement\":", "\"target\":"].iter().map(|key| line.find(key).unwrap()).collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "unexpected field order: {line}");
+        assert_eq!(record["target"], "A");
+    }
+}
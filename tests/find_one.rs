@@ -0,0 +1,42 @@
+//! One-shot search for a single match
+
+use algo_vanity_rs::{DEFAULT_PATH, Placement, PlacementMode, SearchConfig, VanitySearcher, find_one_vanity, verify_match};
+
+#[test]
+fn finds_a_valid_match_of_a_trivial_pattern() {
+    // The match is saved to the default results file, so keep it out of the repository
+    let dir = std::env::temp_dir().join(format!("algo-vanity-find-one-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+
+    let found = find_one_vanity("a", PlacementMode::Start.into()).unwrap();
+    assert!(dir.join(DEFAULT_PATH).exists());
+    _ = std::fs::remove_dir_all(&dir);
+
+    assert_eq!(found.target(), "A");
+    assert_eq!(found.placement(), Placement::Start);
+    assert!(found.public().starts_with('A'));
+    assert!(verify_match(&found).is_ok());
+}
+
+#[test]
+fn rejects_patterns_which_can_not_appear() {
+    let result = find_one_vanity("algo0", PlacementMode::Start.into());
+    assert!(result.is_err_and(|e| e.kind() == std::io::ErrorKind::InvalidInput));
+}
+
+#[test]
+fn fails_when_the_search_stops_without_a_match() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-find-one-{}.json", std::process::id()));
+    let result = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AAAAAAAAAA")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        attempts: Some(1),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).find_one();
+    _ = std::fs::remove_file(&save_path);
+
+    assert!(result.is_err());
+}
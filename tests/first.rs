@@ -0,0 +1,40 @@
+//! One-shot `--first` mode of the command line tool
+
+use std::process::Command;
+
+use algo_rust_sdk::account::Account;
+
+fn save_path(name: &str) -> String {
+    std::env::temp_dir().join(format!("algo-vanity-first-{name}-{}.json", std::process::id())).to_string_lossy().into_owned()
+}
+
+#[test]
+fn prints_one_account_and_succeeds() {
+    let path = save_path("found");
+    let output = Command::new(env!("CARGO_BIN_EXE_algo-vanity-rs"))
+        .args(["--first", "--threads", "1", "--path", &path, "a"])
+        .output()
+        .unwrap();
+    _ = std::fs::remove_file(&path);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "unexpected output: {stdout}");
+
+    let account = Account::from_mnemonic(lines[0]).unwrap();
+    assert!(account.address().encode_string().starts_with('A'));
+}
+
+#[test]
+fn fails_without_match() {
+    let path = save_path("timeout");
+    let output = Command::new(env!("CARGO_BIN_EXE_algo-vanity-rs"))
+        .args(["--first", "--threads", "1", "--duration", "1s", "--path", &path, "aaaaaaaa"])
+        .output()
+        .unwrap();
+    _ = std::fs::remove_file(&path);
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
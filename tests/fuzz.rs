@@ -0,0 +1,130 @@
+//! Randomized checks of the pattern matchers against simple reference implementations,
+//! with random addresses and patterns of any length, including ones which do not fit
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use algo_vanity_rs::{
+    ADDRESS_CHARS, AddressMatch, Placement, SearchPlacement, difficulty, find_anywhere, find_matches, hamming_distance,
+    interesting::InterestingRule, region::RegionConstraint, window::PatternWindow,
+};
+
+/// Random cases checked by each test
+const CASES: usize = 20_000;
+
+/// Random string of up to `max_len` characters, mostly from a few address characters such
+/// that patterns are found often
+fn random_string(rng: &mut StdRng, max_len: usize) -> String {
+    let len = rng.gen_range(0..=max_len);
+    let chars: Vec<char> = if rng.gen_bool(0.8) { "ABC".chars().collect() } else { ADDRESS_CHARS.chars().collect() };
+    (0..len).map(|_| chars[rng.gen_range(0..chars.len())]).collect()
+}
+
+fn random_placement(rng: &mut StdRng) -> SearchPlacement {
+    SearchPlacement { start: rng.gen(), anywhere: rng.gen(), end: rng.gen() }
+}
+
+/// Straightforward reimplementation of `find_matches`, comparing characters one by one
+fn reference_matches(address: &str, targets: &[String], placement: &SearchPlacement, first_only: bool) -> Vec<(String, Placement)> {
+    let address: Vec<char> = address.chars().collect();
+    let at = |index: usize, target: &[char]| index + target.len() <= address.len() && address[index..index + target.len()] == *target;
+
+    let mut matches = Vec::new();
+    for target in targets {
+        let chars: Vec<char> = target.chars().collect();
+        let start = placement.start && at(0, &chars);
+        let end = placement.end && chars.len() <= address.len() && at(address.len() - chars.len(), &chars);
+        if start { matches.push((target.clone(), Placement::Start)) }
+        if end { matches.push((target.clone(), Placement::End)) }
+        if !start && !end && placement.anywhere {
+            if let Some(index) = (0..=address.len()).find(|i| at(*i, &chars)) {
+                matches.push((target.clone(), Placement::Anywhere(index)));
+            }
+        }
+        if first_only && !matches.is_empty() {
+            matches.truncate(1);
+            break
+        }
+    }
+    matches
+}
+
+#[test]
+fn find_matches_agrees_with_reference() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..CASES {
+        let address = random_string(&mut rng, 12);
+        let targets: Vec<_> = (0..rng.gen_range(0..4)).map(|_| random_string(&mut rng, 5)).collect();
+        let placement = random_placement(&mut rng);
+        let first_only = rng.gen();
+
+        let found: Vec<_> = find_matches(&address, &targets, &placement, first_only).into_iter().map(|(t, p)| (t.clone(), p)).collect();
+        assert_eq!(found, reference_matches(&address, &targets, &placement, first_only), "{address} {targets:?} {placement}");
+        for (target, placement) in &found {
+            assert!(placement.matches(&address, target));
+        }
+    }
+}
+
+#[test]
+fn windows_agree_with_reference() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..CASES {
+        let address = random_string(&mut rng, difficulty::ADDRESS_LENGTH);
+        let (start, end) = (rng.gen_range(0..difficulty::ADDRESS_LENGTH), rng.gen_range(0..=difficulty::ADDRESS_LENGTH + 2));
+        let pattern = random_string(&mut rng, 4);
+        let Ok(window) = format!("{start}:{end}:{pattern}").parse::<PatternWindow>() else { continue };
+
+        let expected = match address.get(start..end) {
+            Some(_) => find_anywhere(&address[start..], &pattern).map(|i| start + i).filter(|i| i + pattern.len() <= end),
+            None => None,
+        };
+        assert_eq!(window.find(&address), expected, "{address} {start}:{end}:{pattern}");
+        if let Some(index) = expected {
+            assert!(Placement::Window { start, end, index }.matches(&address, &pattern));
+        }
+    }
+}
+
+#[test]
+fn hamming_distance_agrees_with_reference() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for _ in 0..CASES {
+        let (a, b) = (random_string(&mut rng, 6), random_string(&mut rng, 6));
+        let expected = (a.len() == b.len()).then(|| (0..a.len()).filter(|i| a.as_bytes()[*i] != b.as_bytes()[*i]).count());
+        assert_eq!(hamming_distance(&a, &b), expected);
+        assert_eq!(hamming_distance(&a, &a), Some(0));
+    }
+}
+
+/// Matches whose recorded placement does not fit the address, such as from a corrupted
+/// results file, must not slice out of bounds
+#[test]
+fn placements_beyond_address_do_not_panic() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let placements = |rng: &mut StdRng| {
+        let index = rng.gen_range(0..70);
+        [Placement::Start, Placement::End, Placement::Anywhere(index), Placement::Near(rng.gen_range(0..4)),
+            Placement::Window { start: index.saturating_sub(3), end: index + 3, index }]
+    };
+    let region: RegionConstraint = "digits:3".parse().unwrap();
+    let rules: Vec<InterestingRule> = ["repeat:3", "contains:AB", "quality:2"].iter().map(|r| r.parse().unwrap()).collect();
+
+    let check = |address: &str, target: &str, placement: Placement| {
+        _ = placement.matches(address, target);
+        _ = region.satisfied(address, target, &placement);
+        let m = AddressMatch::new(target, address, "", placement);
+        _ = difficulty::quality(&m);
+        rules.iter().for_each(|rule| _ = rule.matches(&m));
+    };
+
+    // Edge cases of slicing past the end of the address
+    check("ALGO", "LONGERPATTERN", Placement::End);
+    check("ALGO", "GO", Placement::Anywhere(60));
+    check("", "A", Placement::Window { start: 50, end: 58, index: 55 });
+
+    for _ in 0..CASES {
+        let (address, target) = (random_string(&mut rng, 10), random_string(&mut rng, 12));
+        for placement in placements(&mut rng) {
+            check(&address, &target, placement);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! Headless output of the command line tool, for services and logs
+
+use std::process::Command;
+
+#[test]
+fn headless_output_is_plain_lines() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-headless-{}.json", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_algo-vanity-rs"))
+        .args(["--headless", "--progress-interval", "0.1s", "--once", "--threads", "1", "--seed", "0", "--path"])
+        .arg(&save_path)
+        .arg("AB")
+        .output()
+        .unwrap();
+    let saved = std::fs::read_to_string(&save_path);
+    _ = std::fs::remove_file(&save_path);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains(['\r', '\x1b']), "{stdout:?}");
+    assert_eq!(stdout.lines().filter(|line| line.starts_with("Found AB")).count(), 1, "{stdout}");
+    assert!(stdout.lines().any(|line| line.ends_with("0 patterns left")), "{stdout}");
+    assert!(saved.unwrap().contains("\"target\": \"AB\""));
+}
@@ -0,0 +1,18 @@
+//! Refusal of patterns which are practically impossible to find
+
+use std::process::Command;
+
+#[test]
+fn end_pattern_with_impossible_last_character_is_refused_without_force() {
+    let path = std::env::temp_dir().join(format!("algo-vanity-impractical-{}.json", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_algo-vanity-rs"))
+        .args(["--placement", "end", "--threads", "1", "--path", &path.to_string_lossy(), "algob"])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Warning: Pattern ALGOB can not end an address"), "{stderr}");
+    assert!(stdout.contains("Error: Exiting due to impractical pattern(s)"), "{stdout}");
+    assert!(!path.exists());
+}
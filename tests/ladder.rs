@@ -0,0 +1,33 @@
+//! Best effort search climbing the prefix ladder of a pattern
+
+use algo_vanity_rs::{COUNT_PER_LOOP, SearchConfig, VanitySearcher, ladder_best};
+
+#[test]
+fn longer_prefixes_supersede_shorter_ones() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-ladder-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AAAAAAAA")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        ladder: true,
+        seed: Some(0),
+        attempts: Some(10 * COUNT_PER_LOOP * COUNT_PER_LOOP),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    let best = ladder_best("AAAAAAAA", &s.matches).unwrap();
+    assert!(best.target().len() >= 3, "reached only {}", best.target());
+
+    // Each match climbs higher than every match before it
+    let lengths: Vec<_> = s.matches.iter().map(|m| m.target().len()).collect();
+    assert!(lengths.windows(2).all(|pair| pair[0] < pair[1]), "{lengths:?}");
+
+    // Only the rungs above the best one are left to search for
+    assert!(s.vanities.iter().all(|rung| rung.len() > best.target().len()));
+    assert_eq!(s.vanities.len(), 8 - best.target().len());
+}
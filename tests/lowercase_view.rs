@@ -0,0 +1,30 @@
+//! Lowercased view of addresses, shown next to their canonical form
+
+use algo_vanity_rs::{AddressMatch, Placement, SearchPlacement, find_matches, render_matches};
+
+const ADDRESS: &str = "ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZE";
+
+#[test]
+fn both_forms_are_rendered() {
+    let matches = vec![AddressMatch::new("ALGO", ADDRESS, "secret words", Placement::Start)];
+    let lowercase = ADDRESS.to_lowercase();
+
+    for table in [false, true] {
+        let rendered = render_matches(&matches, table, false, true);
+        assert!(rendered.contains(ADDRESS), "{rendered}");
+        assert!(rendered.contains(&lowercase), "{rendered}");
+
+        let canonical_only = render_matches(&matches, table, false, false);
+        assert!(!canonical_only.contains(&lowercase), "{canonical_only}");
+    }
+}
+
+#[test]
+fn matching_stays_canonical() {
+    let placement = SearchPlacement { start: true, anywhere: false, end: false };
+    let targets = vec![String::from("ALGO"), String::from("algo")];
+
+    let found: Vec<_> = find_matches(ADDRESS, &targets, &placement, false).into_iter().map(|(t, _)| t.as_str()).collect();
+    assert_eq!(found, ["ALGO"]);
+    assert!(find_matches(&ADDRESS.to_lowercase(), &targets[..1], &placement, false).is_empty());
+}
@@ -0,0 +1,31 @@
+//! Human-readable log of every match found
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher, load_matches};
+
+#[test]
+fn logs_a_line_per_match() {
+    let dir = std::env::temp_dir();
+    let save_path = dir.join(format!("algo-vanity-match-log-{}.json", std::process::id()));
+    let log_path = dir.join(format!("algo-vanity-match-log-{}.log", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A"), String::from("B"), String::from("C")],
+        once: true,
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        match_log: Some(log_path.to_string_lossy().into_owned()),
+        ..SearchConfig::default()
+    }).start();
+    handle.join();
+
+    let log = std::fs::read_to_string(&log_path).unwrap();
+    let saved = load_matches(&save_path.to_string_lossy()).unwrap();
+    _ = std::fs::remove_file(&save_path);
+    _ = std::fs::remove_file(&log_path);
+
+    assert_eq!(saved.len(), 3);
+    assert_eq!(log.lines().count(), saved.len());
+    for (line, m) in log.lines().zip(&saved) {
+        assert!(line.contains(&m.public), "{line}");
+    }
+}
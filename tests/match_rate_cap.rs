@@ -0,0 +1,35 @@
+//! Capping the rate of kept matches
+
+use std::time::Duration;
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher};
+
+#[test]
+fn kept_matches_respect_the_cap() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-match-rate-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        threads: 1,
+        seed: Some(0),
+        duration: Some(Duration::from_secs(3)),
+        max_match_rate: Some(2.0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    let saved = std::fs::read_to_string(&save_path);
+    _ = std::fs::remove_file(&save_path);
+
+    // A burst of one second worth of matches, then the capped rate for the rest of the run,
+    // with a second of slack for the run time being updated only between batches
+    let s = state.lock().unwrap();
+    let allowed = 2.0 + 2.0 * (s.run_time.as_secs_f64() + 1.0);
+    assert!(!s.matches.is_empty());
+    assert!(s.matches.len() as f64 <= allowed, "{} matches kept in {:?}", s.matches.len(), s.run_time);
+
+    // Dropped matches are still counted, and only kept ones are saved
+    assert!(s.match_count > s.matches.len());
+    let saved: serde_json::Value = serde_json::from_str(&saved.unwrap()).unwrap();
+    assert_eq!(saved.as_array().unwrap().len(), s.matches.len());
+}
@@ -0,0 +1,37 @@
+//! Milestones of matches extending the longest matched prefix of their pattern
+
+use std::collections::HashMap;
+
+use algo_vanity_rs::{COUNT_PER_LOOP, SearchConfig, VanitySearcher, matched_prefix};
+
+#[test]
+fn only_prefix_extending_matches_are_milestones() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-milestones-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AAAAAA")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        attempts: Some(2 * COUNT_PER_LOOP * COUNT_PER_LOOP),
+        max_distance: Some(4),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let state = state.lock().unwrap();
+    let mut best = HashMap::new();
+    let expected: Vec<_> = state.matches.iter().enumerate().filter(|(_, m)| {
+        let prefix = matched_prefix(m);
+        let record = best.get(m.target()).is_none_or(|b| prefix > *b);
+        if record { best.insert(m.target().to_string(), prefix); }
+        record
+    }).map(|(index, _)| index).collect();
+
+    assert!(expected.len() >= 2, "too few milestones to compare: {expected:?}");
+    assert!(expected.len() < state.matches.len());
+    assert_eq!(state.milestones, expected);
+    assert_eq!(state.best_prefix, best);
+}
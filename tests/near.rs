@@ -0,0 +1,29 @@
+//! Matching addresses within a Hamming distance of a pattern
+
+use algo_vanity_rs::{COUNT_PER_LOOP, Placement, SearchConfig, VanitySearcher, verify_match};
+
+#[test]
+fn near_matches_record_their_distance() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-near-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AAA")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        attempts: Some(2 * COUNT_PER_LOOP * COUNT_PER_LOOP),
+        max_distance: Some(1),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let state = state.lock().unwrap();
+    let near: Vec<_> = state.matches.iter().filter(|m| matches!(m.placement(), Placement::Near(_))).collect();
+    assert!(!near.is_empty());
+    for m in near {
+        assert_eq!(m.placement(), Placement::Near(1));
+        assert!(verify_match(m).is_ok());
+    }
+}
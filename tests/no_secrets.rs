@@ -0,0 +1,37 @@
+//! Searches which only save addresses
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher, load_matches};
+
+#[test]
+fn matches_have_no_mnemonic() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-no-secrets-{}.json", std::process::id()));
+    let save_path = save_path.to_string_lossy().into_owned();
+
+    // A single character pattern is found almost immediately
+    let mut searcher = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        threads: 1,
+        save_path: save_path.clone(),
+        once: true,
+        no_secrets: true,
+        record_seed: true,
+        seed: Some(0),
+        ..SearchConfig::default()
+    });
+    let matches = searcher.stream_matches();
+    searcher.start().join();
+
+    let streamed: Vec<_> = matches.iter().collect();
+    let saved = load_matches(&save_path).unwrap();
+    let contents = std::fs::read_to_string(&save_path).unwrap();
+    _ = std::fs::remove_file(&save_path);
+
+    assert_eq!(streamed.len(), 1);
+    assert_eq!(saved.len(), 1);
+    for m in streamed.iter().chain(&saved) {
+        assert!(m.public.starts_with('A'));
+        assert!(m.mnemonic.is_empty());
+        assert!(m.seed.is_none());
+    }
+    assert!(!contents.contains("mnemonic"));
+}
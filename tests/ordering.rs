@@ -0,0 +1,44 @@
+//! Matches required to sort before or after a reference address
+
+use std::cmp::Ordering;
+
+use algo_vanity_rs::{AddressMatch, SearchConfig, VanitySearcher, ordering::{OrderConstraint, reference_address}, verify_match};
+
+const REFERENCE: &str = "KMHX7V7PQ3M2NUZMDSHYBVYVPXK3TFDQPX6NT33YDHCUOSADKMYZSGPYIE";
+
+fn ordered_search(name: &str, vanity: &str, ordering: Ordering) -> Vec<AddressMatch> {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-ordering-{name}-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from(vanity)],
+        order: Some(OrderConstraint { ordering, reference: reference_address(REFERENCE).unwrap() }),
+        once: true,
+        candidates: 5,
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let matches = state.lock().unwrap().matches.clone();
+    assert_eq!(matches.len(), 5);
+    matches
+}
+
+#[test]
+fn matches_sort_before_the_reference() {
+    for m in ordered_search("less", "", Ordering::Less) {
+        assert!(m.public.as_str() < REFERENCE, "{}", m.public);
+        assert!(verify_match(&m).is_ok());
+    }
+}
+
+#[test]
+fn matches_with_a_prefix_sort_after_the_reference() {
+    for m in ordered_search("greater", "KM", Ordering::Greater) {
+        assert!(m.public.starts_with("KM") && m.public.as_str() > REFERENCE, "{}", m.public);
+        assert!(verify_match(&m).is_ok());
+    }
+}
@@ -0,0 +1,22 @@
+//! Splitting the deterministic seed space of a search across machines
+
+use std::collections::HashSet;
+
+use algo_vanity_rs::{Accounts, COUNT_PER_LOOP, Partition, keys::KeyType};
+
+/// Seeds of the first `batches` batches of accounts generated within a partition
+fn seeds(offset: u64, stride: u64, batches: usize) -> HashSet<[u8; 32]> {
+    Accounts::partitioned(0, Partition::new(offset, stride).unwrap(), KeyType::default())
+        .take(batches * COUNT_PER_LOOP * COUNT_PER_LOOP)
+        .map(|acc| acc.seed())
+        .collect()
+}
+
+#[test]
+fn partitions_generate_disjoint_seeds() {
+    let first = seeds(0, 2, 2);
+    let second = seeds(1, 2, 2);
+    assert_eq!(first.len(), 2 * COUNT_PER_LOOP * COUNT_PER_LOOP);
+    assert_eq!(second.len(), 2 * COUNT_PER_LOOP * COUNT_PER_LOOP);
+    assert!(first.is_disjoint(&second));
+}
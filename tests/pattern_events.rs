@@ -0,0 +1,45 @@
+//! Per-pattern completion events of searches with `once`
+
+use std::collections::HashSet;
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher};
+
+#[test]
+fn event_written_for_each_completed_pattern() {
+    let dir = std::env::temp_dir();
+    let save_path = dir.join(format!("algo-vanity-events-{}.json", std::process::id()));
+    let events_path = dir.join(format!("algo-vanity-events-{}.jsonl", std::process::id()));
+
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A"), String::from("BC")],
+        once: true,
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        events_file: Some(events_path.to_string_lossy().into_owned()),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+
+    let contents = std::fs::read_to_string(&events_path);
+    _ = std::fs::remove_file(&save_path);
+    _ = std::fs::remove_file(&events_path);
+
+    let events: Vec<serde_json::Value> = contents.unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(events.len(), 2);
+    let patterns: HashSet<&str> = events.iter().map(|e| e["pattern"].as_str().unwrap()).collect();
+    assert_eq!(patterns, HashSet::from(["A", "BC"]));
+
+    let state = state.lock().unwrap();
+    for event in &events {
+        assert_eq!(event["event"], "pattern_completed");
+        let pattern = event["pattern"].as_str().unwrap();
+        let address = event["address"].as_str().unwrap();
+        assert!(address.starts_with(pattern));
+        assert!(state.matches.iter().any(|m| m.public == address));
+        assert!(chrono::DateTime::parse_from_rfc3339(event["timestamp"].as_str().unwrap()).is_ok());
+        assert!(event["time_to_find_secs"].as_f64().unwrap() >= 0.0);
+        assert!(event["attempts_to_find"].as_u64().unwrap() <= state.total_count as u64);
+    }
+}
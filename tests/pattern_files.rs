@@ -0,0 +1,90 @@
+//! Structured pattern files with settings per pattern
+
+use std::process::Command;
+
+use algo_vanity_rs::{PlacementMode, collect_pattern_entries, collect_patterns};
+
+fn pattern_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!("algo-vanity-pattern-files-{}-{name}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+const YAML: &str = "
+patterns:
+  - algo
+  - pattern: rand
+    placement: end
+    label: Randomness
+  - pattern: moon
+    priority: 10
+  - pattern: algo
+    label: Duplicate
+";
+
+const TOML: &str = r#"
+[[patterns]]
+pattern = "rocks"
+placement = "start-end"
+priority = -1
+
+[[patterns]]
+pattern = "moon"
+label = "Later duplicate"
+"#;
+
+#[test]
+fn parses_mixed_settings_ordered_by_priority() {
+    let yaml = pattern_file("mixed.yaml", YAML);
+    let toml = pattern_file("mixed.toml", TOML);
+    let entries = collect_pattern_entries(&[yaml.clone(), String::from("sun"), toml.clone()]);
+    let patterns = collect_patterns(&[toml.clone(), yaml.clone()]);
+    _ = std::fs::remove_file(&yaml);
+    _ = std::fs::remove_file(&toml);
+
+    let entries = entries.unwrap();
+    let order: Vec<_> = entries.iter().map(|e| e.pattern.as_str()).collect();
+    assert_eq!(order, ["moon", "algo", "rand", "sun", "rocks"]);
+
+    assert_eq!(entries[0].priority, 10);
+    assert_eq!(entries[0].label, None);
+    assert_eq!(entries[1].placement, None);
+    assert_eq!(entries[1].label, None);
+    assert_eq!(entries[2].placement, Some(PlacementMode::End));
+    assert_eq!(entries[2].label.as_deref(), Some("Randomness"));
+    assert_eq!(entries[4].placement, Some(PlacementMode::StartEnd));
+    assert_eq!(entries[4].priority, -1);
+
+    // The first of duplicates is kept with its settings, so moon loses its priority here
+    assert_eq!(patterns.unwrap(), ["moon", "algo", "rand", "rocks"]);
+}
+
+#[test]
+fn rejects_malformed_files() {
+    let unknown = pattern_file("unknown.yml", "patterns:\n  - pattern: algo\n    placment: end\n");
+    let placement = pattern_file("placement.toml", "patterns = [{ pattern = \"algo\", placement = \"middle\" }]");
+    let results = [&unknown, &placement].map(|path| collect_pattern_entries(std::slice::from_ref(path)));
+    _ = std::fs::remove_file(&unknown);
+    _ = std::fs::remove_file(&placement);
+
+    for result in results {
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn placements_and_labels_reach_the_search() {
+    let yaml = pattern_file("config.yaml", YAML);
+    let output = Command::new(env!("CARGO_BIN_EXE_algo-vanity-rs"))
+        .args(["--print-config", "--placement", "anywhere"])
+        .arg(&yaml)
+        .output()
+        .unwrap();
+    _ = std::fs::remove_file(&yaml);
+
+    assert!(output.status.success());
+    let config: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(config["patterns"], serde_json::json!(["MOON", "ALGO"]));
+    assert_eq!(config["windows"], serde_json::json!([{ "start": 54, "end": 58, "pattern": "RAND" }]));
+    assert_eq!(config["labels"], serde_json::json!({ "RAND": "Randomness" }));
+}
@@ -0,0 +1,29 @@
+//! Previewing random addresses without searching
+
+use algo_rust_sdk::Address;
+use algo_vanity_rs::{ADDRESS_CHARS, difficulty::ADDRESS_LENGTH};
+
+#[test]
+fn preview_prints_valid_addresses_and_saves_nothing() {
+    let dir = std::env::temp_dir().join(format!("algo-vanity-preview-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_algo-vanity-rs"))
+        .args(["--preview", "5"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let written = std::fs::read_dir(&dir).unwrap().count();
+    _ = std::fs::remove_dir_all(&dir);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let addresses: Vec<_> = stdout.lines().collect();
+    assert_eq!(addresses.len(), 5, "{stdout}");
+    for address in addresses {
+        assert_eq!(address.len(), ADDRESS_LENGTH);
+        assert!(address.chars().all(|c| ADDRESS_CHARS.contains(c)));
+        assert!(Address::from_string(address).is_ok(), "{address} has an invalid checksum");
+    }
+    assert_eq!(written, 0);
+}
@@ -0,0 +1,28 @@
+//! Printing the resolved configuration of the command line tool
+
+use std::process::Command;
+
+#[test]
+fn printed_config_merges_arguments_files_and_environment() {
+    let patterns = std::env::temp_dir().join(format!("algo-vanity-print-config-{}.txt", std::process::id()));
+    std::fs::write(&patterns, "algo\nrand\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_algo-vanity-rs"))
+        .args(["--print-config", "--threads", "3", "--placement", "start-end", "--path", "found.jsonl", "--warm-up", "5s"])
+        .arg(&patterns)
+        .arg("moon")
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    _ = std::fs::remove_file(&patterns);
+
+    assert!(output.status.success());
+    let config: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(config["patterns"], serde_json::json!(["ALGO", "RAND", "MOON"]));
+    assert_eq!(config["threads"], 3);
+    assert_eq!(config["save_path"], "found.jsonl");
+    assert_eq!(config["format"], "json-lines");
+    assert_eq!(config["warm_up_secs"], 5.0);
+    assert_eq!(config["color"], false);
+    assert!(!std::path::Path::new("found.jsonl").exists());
+}
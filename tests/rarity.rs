@@ -0,0 +1,42 @@
+//! Rarity scores of matches, in bits like the difficulty of a proof of work
+
+use algo_vanity_rs::{AddressMatch, Placement, SearchConfig, VanitySearcher, load_matches};
+
+const ADDRESS: &str = "ALGOAAB2VUDUU2RRFBPTU25SJDQBGGQVM2NMDGPZVQ3XKYMDSDIFIGZAZE";
+
+#[test]
+fn rarer_matches_score_higher() {
+    let rarity = |target: &str, placement| AddressMatch::new(target, ADDRESS, "", placement).rarity();
+
+    // Longer patterns are rarer
+    assert!(rarity("ALGO", Placement::Start) > rarity("ALG", Placement::Start));
+    // The same pattern is rarer at a fixed place than anywhere in the address
+    assert!(rarity("VUDU", Placement::Window { start: 8, end: 12, index: 8 }) > rarity("VUDU", Placement::Anywhere(8)));
+    // Near matches are easier to find than exact ones
+    assert!(rarity("ALSO", Placement::Near(1)) < rarity("ALGO", Placement::Start));
+}
+
+#[test]
+fn rarity_is_saved_and_defaults_for_older_files() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-rarity-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A"), String::from("BC")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        once: true,
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+    handle.join();
+    let saved = load_matches(&save_path.to_string_lossy());
+    _ = std::fs::remove_file(&save_path);
+
+    let saved = saved.unwrap();
+    assert_eq!(saved.len(), 2);
+    assert!(saved.iter().all(|m| m.rarity.is_some_and(|bits| bits == m.rarity())));
+
+    // Matches saved before rarity scores existed still get one
+    let old: Vec<AddressMatch> = serde_json::from_str(&format!(r#"[{{"target":"ALGO","public":"{ADDRESS}","placement":"Start"}}]"#)).unwrap();
+    assert_eq!(old[0].rarity, None);
+    assert_eq!(old[0].rarity(), 30.0);
+}
@@ -0,0 +1,45 @@
+//! Aborting searches whose rate drops below a floor
+
+use std::time::{Duration, Instant};
+
+use algo_vanity_rs::{RateFloor, SearchConfig, VanitySearcher};
+
+#[test]
+fn sustained_rate_drop_triggers_abort() {
+    let start = Instant::now();
+    let mut floor = RateFloor::new(10_000.0, Duration::from_secs(60));
+
+    // A healthy rate, and a dip which recovers within the grace period
+    let mut aborted_at = None;
+    let rates = [50_000.0, 48_000.0, 9_000.0, 51_000.0];
+    let drop = std::iter::repeat_n(2_000.0, 20);
+    for (i, rate) in rates.into_iter().chain(drop).enumerate() {
+        if floor.observe(rate, start + Duration::from_secs(10 * i as u64)) {
+            aborted_at = Some(i);
+            break
+        }
+    }
+    // The drop starts at 40 s, and is sustained for 60 s by 100 s
+    assert_eq!(aborted_at, Some(10));
+}
+
+#[test]
+fn search_below_floor_aborts_without_completing() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-rate-floor-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AAAAAAAAAA")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        min_rate: Some(f32::MAX),
+        min_rate_grace: Duration::ZERO,
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    assert!(s.aborted.as_deref().is_some_and(|reason| reason.contains("below the floor")));
+    assert_eq!(s.completion, None);
+}
@@ -0,0 +1,37 @@
+//! Seeds recorded with each match of a search
+
+use algo_rust_sdk::account::Account;
+use algo_vanity_rs::{SearchConfig, VanitySearcher, load_matches};
+
+/// Decode the hex encoded seed of a match
+fn seed_from_hex(hex: &str) -> [u8; 32] {
+    let mut seed = [0; 32];
+    for (byte, pair) in seed.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap();
+    }
+    seed
+}
+
+#[test]
+fn recorded_seed_derives_the_account() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-record-seed-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A"), String::from("B"), String::from("C")],
+        once: true,
+        threads: 1,
+        seed: Some(0),
+        record_seed: true,
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    handle.join();
+    let matches = load_matches(&save_path.to_string_lossy()).unwrap();
+    _ = std::fs::remove_file(&save_path);
+
+    assert_eq!(matches.len(), 3);
+    for m in &matches {
+        let hex = m.seed.as_deref().unwrap();
+        assert_eq!(hex.len(), 64);
+        assert_eq!(Account::from_seed(seed_from_hex(hex)).address().encode_string(), m.public);
+    }
+}
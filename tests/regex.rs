@@ -0,0 +1,74 @@
+//! Patterns matched as regular expressions
+
+use algo_vanity_rs::{AddressMatch, Placement, PlacementMode, SearchConfig, SearchPlacement, VanitySearcher, regex_pattern::RegexPattern, verify_match};
+
+fn regex_search(name: &str, vanity: &str, placement: PlacementMode) -> (Vec<AddressMatch>, Option<String>) {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-regex-{name}-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from(vanity)],
+        placement: placement.into(),
+        regex: true,
+        once: true,
+        candidates: 3,
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    (s.matches.clone(), s.aborted.clone())
+}
+
+#[test]
+fn regex_matches_record_the_matched_text() {
+    let (matches, aborted) = regex_search("start", "a[2-7]", PlacementMode::Start);
+    assert_eq!(aborted, None);
+    assert_eq!(matches.len(), 3);
+    for m in &matches {
+        assert_eq!(m.target, "a[2-7]");
+        assert_eq!(m.placement, Placement::Start);
+        let matched = m.matched.as_deref().unwrap();
+        assert!(m.public.starts_with(matched), "{}", m.public);
+        assert!(matched.starts_with('A') && "234567".contains(&matched[1..]), "{matched}");
+        assert!(verify_match(m).is_ok());
+    }
+}
+
+#[test]
+fn anywhere_regex_matches_record_their_start() {
+    let (matches, _) = regex_search("anywhere", "Q[2-7]Q", PlacementMode::Anywhere);
+    assert_eq!(matches.len(), 3);
+    for m in &matches {
+        let Placement::Anywhere(index) = m.placement else { panic!("{}", m.placement) };
+        let matched = m.matched.as_deref().unwrap();
+        assert_eq!(matched.len(), 3);
+        assert_eq!(&m.public[index..index + 3], matched);
+        assert_eq!(m.matched_range(), index..index + 3);
+        assert!(verify_match(m).is_ok());
+    }
+}
+
+#[test]
+fn invalid_regex_aborts_the_search() {
+    let (matches, aborted) = regex_search("invalid", "ALGO[0-9]", PlacementMode::Start);
+    assert!(matches.is_empty());
+    assert!(aborted.unwrap().contains("ALGO[0-9]"));
+}
+
+#[test]
+fn overlapping_alternatives_match_the_leftmost_longest() {
+    let start = SearchPlacement { start: true, anywhere: false, end: false };
+    let anywhere = SearchPlacement { start: false, anywhere: true, end: false };
+
+    let pattern: RegexPattern = "A|AB".parse().unwrap();
+    assert_eq!(pattern.find("ABC", &start), [(Placement::Start, String::from("AB"))]);
+    assert_eq!(pattern.find("CABC", &anywhere), [(Placement::Anywhere(1), String::from("AB"))]);
+
+    // Leftmost comes before longest, as for literal patterns
+    let pattern: RegexPattern = "BCD|ABC|A".parse().unwrap();
+    assert_eq!(pattern.find("XABCD", &anywhere), [(Placement::Anywhere(1), String::from("ABC"))]);
+}
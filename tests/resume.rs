@@ -0,0 +1,47 @@
+//! Resuming a search from its results file
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher, load_matches};
+
+#[test]
+fn resumed_search_carries_forward_prior_matches() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-resume-{}.json", std::process::id())).to_string_lossy().into_owned();
+    let first = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        once: true,
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.clone(),
+        ..SearchConfig::default()
+    }).start();
+    first.join();
+    let prior: Vec<String> = load_matches(&save_path).unwrap().into_iter().map(|m| m.public).collect();
+    assert_eq!(prior.len(), 1);
+
+    // Only the pattern which was not found yet is searched for
+    let mut searcher = VanitySearcher::from_state(&save_path).unwrap();
+    assert_eq!(searcher.prior_matches()[0].public, prior[0]);
+    let config = searcher.config_mut();
+    assert_eq!(config.vanities, ["A"]);
+    config.vanities.push(String::from("B"));
+    config.once = true;
+    config.threads = 1;
+    config.seed = Some(1);
+    let handle = searcher.start();
+    let state = handle.state().clone();
+    assert_eq!(state.lock().unwrap().match_count, prior.len());
+    assert_eq!(state.lock().unwrap().vanities, ["B"]);
+    handle.join();
+    let saved = load_matches(&save_path).unwrap();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    assert_eq!(s.match_count, 2);
+    assert_eq!(saved.len(), 2);
+    assert_eq!(saved[0].public, prior[0]);
+    assert!(saved[1].public.starts_with('B'));
+
+    // The threads report finishing through the state rather than printing
+    for name in ["worker 0", "main loop", "file handler"] {
+        assert!(s.terminated_threads.iter().any(|t| t == name), "{:?}", s.terminated_threads);
+    }
+}
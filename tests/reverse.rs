@@ -0,0 +1,50 @@
+//! Matching patterns against the reversed address
+
+use algo_vanity_rs::{AddressMatch, Placement, PlacementMode, SearchConfig, VanitySearcher, verify_match};
+
+/// Matches of a reversed search for the patterns with the given placement
+fn search(name: &str, vanities: &[&str], placement: PlacementMode) -> Vec<AddressMatch> {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-reverse-{name}-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vanities.iter().map(|v| v.to_string()).collect(),
+        placement: placement.into(),
+        reverse: true,
+        once: true,
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let matches = state.lock().unwrap().matches.clone();
+    matches
+}
+
+#[test]
+fn reversed_prefix_matches_end_of_address() {
+    let matches = search("prefix", &["AB"], PlacementMode::Start);
+    assert_eq!(matches.len(), 1);
+
+    let m = &matches[0];
+    assert_eq!(m.target, "AB");
+    assert!(m.reversed);
+    assert!(m.public.ends_with("BA"), "{}", m.public);
+    assert_eq!(m.placement, Placement::End);
+    assert!(m.summary().ends_with("(end, reversed)"));
+    assert!(verify_match(m).is_ok());
+}
+
+#[test]
+fn highlight_maps_back_to_reversed_characters() {
+    let matches = search("anywhere", &["ABC"], PlacementMode::Anywhere);
+    assert_eq!(matches.len(), 1);
+
+    let m = &matches[0];
+    let range = m.matched_range();
+    assert!(matches!(m.placement, Placement::Anywhere(index) if index == range.start));
+    assert_eq!(&m.public[range], "CBA");
+    assert!(verify_match(m).is_ok());
+}
@@ -0,0 +1,35 @@
+//! Rotating results files by time
+
+use std::time::Duration;
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher, load_matches};
+
+#[test]
+fn crossing_interval_opens_new_file() {
+    let dir = std::env::temp_dir();
+    let save_path = dir.join(format!("algo-vanity-rotate-{}.json", std::process::id()));
+    let prefix = format!("algo-vanity-rotate-{}-", std::process::id());
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        rotate: Some(Duration::from_secs(1)),
+        duration: Some(Duration::from_millis(2500)),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+
+    let files: Vec<_> = std::fs::read_dir(&dir).unwrap()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(&prefix)))
+        .collect();
+    let saved: usize = files.iter().map(|path| load_matches(path.to_str().unwrap()).unwrap().len()).sum();
+    files.iter().for_each(|path| _ = std::fs::remove_file(path));
+
+    assert!(files.len() >= 2, "expected several results files, got {files:?}");
+    assert!(!save_path.exists());
+    assert_eq!(saved, state.lock().unwrap().match_count);
+}
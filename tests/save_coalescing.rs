@@ -0,0 +1,73 @@
+//! Coalescing saves of matches by their count and by time
+
+use std::time::{Duration, Instant};
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher, load_matches};
+
+/// Start an endless search for a rare pattern with the given save triggers, and return the
+/// number of matches saved while it was still running, once any were, along with the number
+/// of matches saved in the end
+fn saved_while_running(name: &str, save_every: Option<usize>, save_interval: Option<Duration>) -> (usize, usize) {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-save-{name}-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AB")],
+        save_every,
+        save_interval,
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+
+    let path = save_path.to_string_lossy().into_owned();
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let running = loop {
+        let saved = load_matches(&path).map_or(0, |m| m.len());
+        if saved > 0 || Instant::now() > deadline { break saved }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    assert!(handle.is_running());
+
+    handle.stop();
+    handle.join();
+    let saved = load_matches(&path).map_or(0, |m| m.len());
+    _ = std::fs::remove_file(&save_path);
+    (running, saved)
+}
+
+#[test]
+fn count_trigger_saves_without_interval() {
+    let (running, saved) = saved_while_running("count", Some(5), None);
+    assert!(running >= 5, "{running}");
+    assert!(saved >= running);
+}
+
+#[test]
+fn time_trigger_saves_below_count() {
+    let (running, saved) = saved_while_running("interval", Some(1_000_000), Some(Duration::from_millis(200)));
+    assert!((1..1_000_000).contains(&running), "{running}");
+    assert!(saved >= running);
+}
+
+#[test]
+fn pending_matches_saved_once_search_ends() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-save-end-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AB")],
+        save_every: Some(1_000_000),
+        attempts: Some(30_000),
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    let saved = load_matches(&save_path.to_string_lossy());
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    assert!(!s.matches.is_empty());
+    assert_eq!(saved.unwrap().len(), s.matches.len());
+    assert_eq!(s.unsaved, 0);
+}
@@ -0,0 +1,29 @@
+//! Matches required to also satisfy a script predicate
+#![cfg(feature = "script")]
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher, script::ScriptPredicate};
+
+#[test]
+fn matches_satisfy_script_predicate() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-script-{}.json", std::process::id()));
+    let script: ScriptPredicate = r#"address.ends_with("A") && target == "AB""#.parse().unwrap();
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AB"), String::from("C")],
+        script: Some(script),
+        attempts: Some(50_000),
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let matches = state.lock().unwrap().matches.clone();
+    assert!(!matches.is_empty());
+    for m in &matches {
+        assert_eq!(m.target, "AB");
+        assert!(m.public.starts_with("AB") && m.public.ends_with('A'), "{}", m.public);
+    }
+}
@@ -0,0 +1,68 @@
+//! Endpoints of the HTTP API, served in-process
+#![cfg(feature = "serve")]
+
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+    time::{Duration, Instant},
+};
+
+use algo_vanity_rs::{SearchConfig, serve::serve};
+use serde_json::Value;
+
+/// Send a request and return the status code and json body of the response
+fn request(addr: SocketAddr, method: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "{method} /search HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let status = response.split_whitespace().nth(1).unwrap().parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn submit_and_poll_search() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-serve-{}.json", std::process::id()));
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let config = SearchConfig { threads: 1, once: true, save_path: save_path.to_string_lossy().into_owned(), ..SearchConfig::default() };
+    thread::spawn(move || serve(listener, config));
+
+    assert_eq!(request(addr, "GET", "").0, 404);
+    assert_eq!(request(addr, "POST", r#"{"patterns": ["0"]}"#).0, 400);
+    assert_eq!(request(addr, "POST", r#"{"patterns": ["a"], "placement": "start"}"#).0, 202);
+
+    // A single character pattern is found almost immediately
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let status = loop {
+        let (code, status) = request(addr, "GET", "");
+        assert_eq!(code, 200);
+        if status["running"] == false || Instant::now() > deadline { break status }
+        thread::sleep(Duration::from_millis(50));
+    };
+    _ = std::fs::remove_file(&save_path);
+
+    assert_eq!(status["match_count"], 1);
+    assert!(status["matches"][0]["public"].as_str().unwrap().starts_with('A'));
+    assert_eq!(request(addr, "DELETE", "").0, 200);
+}
+
+#[test]
+fn oversized_body_is_refused() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || serve(listener, SearchConfig { threads: 1, ..SearchConfig::default() }));
+
+    // Refused from the header alone, without waiting for the announced body
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "POST /search HTTP/1.1\r\nHost: localhost\r\nContent-Length: 1000000000\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 413"), "{response}");
+
+    // The server keeps answering afterwards
+    assert_eq!(request(addr, "GET", "").0, 404);
+}
@@ -0,0 +1,39 @@
+//! Splitting matches across files by a hash of their pattern
+
+use std::collections::HashMap;
+
+use algo_vanity_rs::{COUNT_PER_LOOP, SearchConfig, VanitySearcher, labeled_path, load_matches, shard_of, shard_path};
+
+#[test]
+fn matches_land_in_shard_of_their_pattern() {
+    let shards = 3;
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-shards-{}.json", std::process::id())).to_string_lossy().into_owned();
+    let vanities: Vec<_> = ["A", "B", "C", "D", "E"].map(String::from).into();
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vanities.clone(),
+        threads: 1,
+        save_path: save_path.clone(),
+        shards: Some(shards),
+        attempts: Some(COUNT_PER_LOOP * COUNT_PER_LOOP),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+    handle.join();
+
+    let index: HashMap<String, String> = serde_json::from_str(&std::fs::read_to_string(labeled_path(&save_path, "index")).unwrap()).unwrap();
+    let mut saved = 0;
+    for shard in 0..shards {
+        let path = shard_path(&save_path, shard);
+        for m in load_matches(&path).unwrap() {
+            assert_eq!(shard_of(m.target(), shards), shard);
+            assert_eq!(index[m.target()], path);
+            saved += 1;
+        }
+        _ = std::fs::remove_file(&path);
+    }
+    _ = std::fs::remove_file(labeled_path(&save_path, "index"));
+
+    assert!(saved > 0);
+    assert_eq!(index.len(), vanities.len());
+    assert!(!std::path::Path::new(&save_path).exists());
+}
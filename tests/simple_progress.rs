@@ -0,0 +1,29 @@
+use std::time::{Duration, Instant};
+
+use algo_vanity_rs::{locale::NumberLocale, stats::SearchStats};
+
+fn stats(run_secs: u64, warming_up: bool, match_count: usize, remaining: &[&str]) -> SearchStats {
+    SearchStats {
+        taken_at: Instant::now(),
+        run_time: Duration::from_secs(run_secs),
+        search_rate: 9876.4,
+        warming_up,
+        total_count: 1_234,
+        match_count,
+        matches: match_count,
+        remaining: remaining.iter().map(|r| r.to_string()).collect(),
+    }
+}
+
+#[test]
+fn progress_line_is_plain_text() {
+    let line = stats(59, true, 0, &["ALGO", "RAND"]).progress_line(NumberLocale::En);
+    assert_eq!(line, "0h:00m:59s | 9,876 a/s (warming up) | 1.23 thousand addresses | 0 matches | 2 patterns left");
+    assert!(line.chars().all(|c| !c.is_control()), "{line:?}");
+}
+
+#[test]
+fn progress_line_counts_singular() {
+    let line = stats(36_000 + 61, false, 1, &["ALGO"]).progress_line(NumberLocale::En);
+    assert_eq!(line, "10h:01m:01s | 9,876 a/s | 1.23 thousand addresses | 1 match | 1 pattern left");
+}
@@ -0,0 +1,100 @@
+//! Streaming matches to consumers of a Unix domain socket
+#![cfg(unix)]
+
+use std::{io::{BufRead, BufReader}, os::unix::net::UnixStream, thread, time::{Duration, Instant}};
+
+use algo_vanity_rs::{AddressMatch, PlacementMode, SearchConfig, VanitySearcher};
+
+/// Connect to the socket once the search has bound it
+fn connect(path: &str) -> UnixStream {
+    let deadline = Instant::now() + Duration::from_secs(30);
+    loop {
+        match UnixStream::connect(path) {
+            Ok(stream) => break stream,
+            Err(e) if Instant::now() > deadline => panic!("unable to connect to socket: {e}"),
+            Err(_) => thread::sleep(Duration::from_millis(20)),
+        }
+    }
+}
+
+#[test]
+fn reader_receives_match() {
+    let path = |ext: &str| std::env::temp_dir().join(format!("algo-vanity-socket-{}.{ext}", std::process::id())).to_string_lossy().into_owned();
+    let (save_path, socket_path) = (path("json"), path("sock"));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        threads: 1,
+        save_path: save_path.clone(),
+        socket: Some(socket_path.clone()),
+        ..SearchConfig::default()
+    }).start();
+
+    let stream = connect(&socket_path);
+    stream.set_read_timeout(Some(Duration::from_secs(30))).unwrap();
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).unwrap();
+    let address_match: AddressMatch = serde_json::from_str(&line).unwrap();
+    assert!(address_match.public.starts_with('A'));
+
+    handle.stop();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+    assert!(!std::path::Path::new(&socket_path).exists());
+}
+
+#[test]
+fn stale_socket_is_replaced() {
+    let path = |ext: &str| std::env::temp_dir().join(format!("algo-vanity-socket-stale-{}.{ext}", std::process::id())).to_string_lossy().into_owned();
+    let (save_path, socket_path) = (path("json"), path("sock"));
+
+    // A listener dropped without cleaning up leaves its socket file behind
+    drop(std::os::unix::net::UnixListener::bind(&socket_path).unwrap());
+    assert!(std::path::Path::new(&socket_path).exists());
+
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        threads: 1,
+        save_path: save_path.clone(),
+        socket: Some(socket_path.clone()),
+        ..SearchConfig::default()
+    }).start();
+    let stream = connect(&socket_path);
+    stream.set_read_timeout(Some(Duration::from_secs(30))).unwrap();
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).unwrap();
+    assert!(serde_json::from_str::<AddressMatch>(&line).is_ok());
+
+    handle.stop();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+}
+
+#[test]
+fn slow_consumer_does_not_hold_up_others() {
+    let path = |ext: &str| std::env::temp_dir().join(format!("algo-vanity-socket-slow-{}.{ext}", std::process::id())).to_string_lossy().into_owned();
+    let (save_path, socket_path) = (path("json"), path("sock"));
+    // Most addresses contain the pattern somewhere, for a flood of matches
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        placement: PlacementMode::Anywhere.into(),
+        threads: 1,
+        save_path: save_path.clone(),
+        socket: Some(socket_path.clone()),
+        ..SearchConfig::default()
+    }).start();
+
+    // The first consumer never reads, so its buffer fills up after a few hundred matches
+    let _slow = connect(&socket_path);
+    let fast = connect(&socket_path);
+    fast.set_read_timeout(Some(Duration::from_secs(30))).unwrap();
+    let mut lines = BufReader::new(fast).lines();
+    for _ in 0..3000 {
+        let line = lines.next().unwrap().unwrap();
+        assert!(serde_json::from_str::<AddressMatch>(&line).is_ok());
+    }
+
+    handle.stop();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+}
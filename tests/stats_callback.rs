@@ -0,0 +1,41 @@
+//! Periodic statistics for embedders rendering progress of their own
+
+use std::{sync::mpsc, time::{Duration, Instant}};
+
+use algo_vanity_rs::{COUNT_PER_LOOP, SearchConfig, VanitySearcher};
+
+#[test]
+fn callback_fires_with_fresh_stats() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-stats-{}.json", std::process::id()));
+    let mut searcher = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("AAAAAAAA")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        seed: Some(0),
+        attempts: Some(3 * COUNT_PER_LOOP * COUNT_PER_LOOP),
+        ..SearchConfig::default()
+    });
+    let (tx, rx) = mpsc::channel();
+    searcher.on_stats(Duration::from_millis(50), move |stats| _ = tx.send((Instant::now(), stats.clone())));
+
+    let handle = searcher.start();
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let snapshots: Vec<_> = rx.try_iter().collect();
+    assert!(snapshots.len() >= 3, "only {} snapshots", snapshots.len());
+
+    // Every snapshot was taken right before the callback, and counts only ever grow
+    for (called_at, stats) in &snapshots {
+        assert!(called_at.duration_since(stats.taken_at) < Duration::from_millis(50));
+    }
+    assert!(snapshots.windows(2).all(|pair| pair[0].1.total_count <= pair[1].1.total_count));
+    assert!(snapshots.iter().any(|(_, stats)| stats.total_count > 0 && stats.search_rate > 0.0));
+
+    // The last snapshot is taken once the search has stopped, matching its final state
+    let (_, last) = snapshots.last().unwrap();
+    let s = state.lock().unwrap();
+    assert_eq!(last.total_count, s.total_count);
+    assert_eq!(last.match_count, s.match_count);
+}
@@ -0,0 +1,50 @@
+//! Live status file of a running search
+
+use std::time::{Duration, Instant};
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher};
+
+/// Wait for the status file to report something satisfying `ready`, giving up after a while
+fn wait_for_status(path: &std::path::Path, ready: impl Fn(&serde_json::Value) -> bool) -> serde_json::Value {
+    let deadline = Instant::now() + Duration::from_secs(60);
+    loop {
+        let status = std::fs::read_to_string(path).ok().and_then(|contents| serde_json::from_str(&contents).ok());
+        match status {
+            Some(status) if ready(&status) => return status,
+            _ if Instant::now() > deadline => panic!("Status file not updated in time"),
+            _ => std::thread::sleep(Duration::from_millis(20)),
+        }
+    }
+}
+
+#[test]
+fn status_file_written_and_updated() {
+    let dir = std::env::temp_dir();
+    let save_path = dir.join(format!("algo-vanity-status-{}.json", std::process::id()));
+    let status_path = dir.join(format!("algo-vanity-status-{}.status.json", std::process::id()));
+
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("ALGORAND")],
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        status_file: Some(status_path.to_string_lossy().into_owned()),
+        status_interval: Duration::from_millis(50),
+        ..SearchConfig::default()
+    }).start();
+
+    let first = wait_for_status(&status_path, |_| true);
+    assert_eq!(first["remaining"], serde_json::json!(["ALGORAND"]));
+    assert!(first["updated_at"].is_string());
+
+    let first_count = first["total_count"].as_u64().unwrap();
+    let later = wait_for_status(&status_path, |status| status["total_count"].as_u64().unwrap() > first_count);
+    assert!(later["run_time_secs"].as_f64().unwrap() > first["run_time_secs"].as_f64().unwrap());
+
+    handle.stop();
+    handle.join();
+    let exists = status_path.exists();
+    _ = std::fs::remove_file(&save_path);
+    _ = std::fs::remove_file(&status_path);
+    assert!(exists);
+}
@@ -0,0 +1,33 @@
+//! Shareable summary card of a finished search
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher, summary_card};
+
+#[test]
+fn card_lists_every_found_address_without_secrets() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-summary-card-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A"), String::from("B"), String::from("C")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        once: true,
+        seed: Some(0),
+        record_seed: true,
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    assert!(s.vanities.is_empty());
+    let card = summary_card(&s.matches, s.run_time, s.total_count);
+
+    assert_eq!(s.matches.len(), 3);
+    for m in &s.matches {
+        assert!(card.contains(m.public()), "{card}");
+        let mnemonic = m.mnemonic().unwrap();
+        assert!(mnemonic.split_whitespace().all(|word| !card.to_lowercase().contains(&format!(" {word} "))), "{card}");
+        assert!(!card.contains(m.seed().unwrap()));
+    }
+}
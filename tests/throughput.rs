@@ -0,0 +1,35 @@
+//! Guards the hot path against gross performance regressions
+
+use std::time::Duration;
+
+use algo_vanity_rs::{SearchConfig, SearchPlacement, VanitySearcher};
+
+/// Conservative lower bound of addresses per second for a single thread, low enough for
+/// slow machines and unoptimized builds
+const MIN_RATE: f64 = 500.0;
+
+#[test]
+fn single_thread_rate_exceeds_floor() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-throughput-{}.json", std::process::id()));
+
+    // The digit 0 is not part of the address alphabet, so the pattern never matches
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("0")],
+        threads: 1,
+        placement: SearchPlacement { start: false, anywhere: true, end: false },
+        save_path: save_path.to_string_lossy().into_owned(),
+        duration: Some(Duration::from_secs(3)),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    let rate = s.total_count as f64 / s.run_time.as_secs_f64();
+    assert_eq!(s.match_count, 0);
+    assert!(s.total_count > 0, "no addresses were generated");
+    assert!(rate > MIN_RATE, "searched {rate:.0} addresses per second, expected more than {MIN_RATE}");
+}
@@ -0,0 +1,33 @@
+//! Time limit on searches for patterns found only once
+
+use std::time::{Duration, Instant};
+
+use algo_vanity_rs::{Completion, SearchConfig, VanitySearcher};
+
+#[test]
+fn unreachable_once_pattern_stops_at_the_time_limit() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-time-limit-{}.json", std::process::id()));
+    let started = Instant::now();
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("ALGOALGOALGO"), String::from("A")],
+        once: true,
+        duration: Some(Duration::from_secs(2)),
+        threads: 1,
+        seed: Some(0),
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    assert!(started.elapsed() < Duration::from_secs(60));
+    assert_eq!(s.completion, Some(Completion::TimeLimit));
+    assert!(s.run_time >= Duration::from_secs(2));
+
+    // Only the reachable pattern was found and retired
+    assert_eq!(s.vanities, ["ALGOALGOALGO"]);
+    assert!(s.matches.iter().all(|m| m.target == "A"));
+    assert_eq!(s.pattern_completions.len(), 1);
+}
@@ -0,0 +1,31 @@
+//! Recommending a thread count from measured search rates
+
+use algo_vanity_rs::benchmark::{tune_thread_counts, tune_with};
+
+#[test]
+fn table_lists_each_thread_count_and_recommends_the_knee() {
+    // Rates scale up to 4 threads, and stay flat within noise beyond
+    let counts = tune_thread_counts(16);
+    let tuning = tune_with(&counts, |threads| 1000.0 * threads.min(4) as f64 + threads as f64);
+
+    let table = tuning.table();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 1 + counts.len());
+    assert_eq!(lines[0].split_whitespace().collect::<Vec<_>>(), ["Threads", "Rate", "(a/s)", "Speedup"]);
+
+    for (line, threads) in lines[1..].iter().zip(&counts) {
+        let cells: Vec<&str> = line.split_whitespace().collect();
+        assert_eq!(cells[0], threads.to_string());
+        assert!(cells[2].ends_with('x'));
+        assert_eq!(line.contains("<- recommended"), *threads == 4, "{line}");
+    }
+    assert_eq!(tuning.recommended().unwrap().threads, 4);
+}
+
+#[test]
+fn measures_each_count_once_in_order() {
+    let mut measured = Vec::new();
+    let tuning = tune_with(&[1, 2, 3], |threads| { measured.push(threads); 100.0 });
+    assert_eq!(measured, [1, 2, 3]);
+    assert_eq!(tuning.recommended().unwrap().threads, 1);
+}
@@ -0,0 +1,44 @@
+//! Keeping only matches whose mnemonic repeats no word
+
+use algo_vanity_rs::{COUNT_PER_LOOP, SearchConfig, VanitySearcher, has_unique_words};
+
+const UNIQUE: &str = "abandon ability able about above absent absorb abstract absurd abuse access accident account accuse achieve acid acoustic acquire across act action actor actress actual adapt";
+
+#[test]
+fn crafted_mnemonics() {
+    assert!(has_unique_words(UNIQUE));
+
+    // A repeated word anywhere fails the filter
+    let repeated_first = UNIQUE.replacen("ability", "abandon", 1);
+    let repeated_last = UNIQUE.replacen("adapt", "actual", 1);
+    assert!(!has_unique_words(&repeated_first));
+    assert!(!has_unique_words(&repeated_last));
+
+    // So do incomplete mnemonics, such as those which were not kept
+    assert!(!has_unique_words(""));
+    assert!(!has_unique_words(UNIQUE.rsplit_once(' ').unwrap().0));
+}
+
+#[test]
+fn search_keeps_only_unique_mnemonics() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-unique-words-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        unique_words: true,
+        seed: Some(1),
+        attempts: Some(COUNT_PER_LOOP * COUNT_PER_LOOP),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    assert!(!s.matches.is_empty());
+    assert!(s.matches.iter().all(|m| has_unique_words(m.mnemonic().unwrap())));
+    // Some matches repeated a word, and were counted without being kept
+    assert!(s.match_count > s.matches.len());
+}
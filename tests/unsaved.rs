@@ -0,0 +1,24 @@
+//! Reporting matches which have not been saved yet
+
+use algo_vanity_rs::{COUNT_PER_LOOP, SearchConfig, VanitySearcher};
+
+#[test]
+fn saved_matches_leave_no_backlog() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-unsaved-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        attempts: Some(COUNT_PER_LOOP * COUNT_PER_LOOP),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let s = state.lock().unwrap();
+    assert!(s.match_count > 0);
+    assert_eq!(s.unsaved, 0);
+}
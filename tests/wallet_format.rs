@@ -0,0 +1,52 @@
+//! Results files in the wallet import format
+
+use std::collections::BTreeSet;
+
+use algo_vanity_rs::{SearchConfig, VanitySearcher, format::{self, ResultsFormat, WalletFile, WALLET_VERSION}};
+
+#[test]
+fn wallet_format_round_trips_to_the_found_accounts() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-wallet-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A"), String::from("Q")],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        format: Some(ResultsFormat::Wallet),
+        once: true,
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    let contents = std::fs::read_to_string(&save_path).unwrap();
+    let reloaded = format::read_matches(save_path.to_str().unwrap());
+    _ = std::fs::remove_file(&save_path);
+
+    // Every account has exactly the fields of the schema
+    let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(json["version"], WALLET_VERSION);
+    for account in json["accounts"].as_array().unwrap() {
+        let keys: BTreeSet<&str> = account.as_object().unwrap().keys().map(String::as_str).collect();
+        assert_eq!(keys, BTreeSet::from(["address", "mnemonic", "name", "private_key"]));
+    }
+
+    // Every account imports as the account of its match
+    let wallet: WalletFile = serde_json::from_str(&contents).unwrap();
+    let s = state.lock().unwrap();
+    assert_eq!(s.matches.len(), 2);
+    assert_eq!(wallet.accounts.len(), 2);
+    for (account, m) in wallet.accounts.iter().zip(&s.matches) {
+        let imported = account.account().unwrap();
+        assert_eq!(imported.address().encode_string(), m.public);
+        assert_eq!(imported.mnemonic(), m.mnemonic);
+        assert!(account.address.starts_with(&account.name));
+    }
+
+    // A tampered private key no longer imports
+    let mut tampered = wallet.accounts[0].clone();
+    tampered.private_key = wallet.accounts[1].private_key.clone();
+    assert!(tampered.account().is_err());
+
+    assert!(reloaded.is_err());
+}
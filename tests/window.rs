@@ -0,0 +1,30 @@
+//! Searching for patterns only within a window of characters
+
+use algo_vanity_rs::{COUNT_PER_LOOP, Placement, SearchConfig, VanitySearcher, verify_match, window::PatternWindow};
+
+#[test]
+fn windowed_matches_lie_within_window() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-window-{}.json", std::process::id()));
+    let window: PatternWindow = "20:26:AB".parse().unwrap();
+    let handle = VanitySearcher::new(SearchConfig {
+        windows: vec![window],
+        threads: 1,
+        save_path: save_path.to_string_lossy().into_owned(),
+        attempts: Some(COUNT_PER_LOOP * COUNT_PER_LOOP),
+        seed: Some(0),
+        ..SearchConfig::default()
+    }).start();
+
+    let state = handle.state().clone();
+    handle.join();
+    _ = std::fs::remove_file(&save_path);
+
+    let state = state.lock().unwrap();
+    assert!(!state.matches.is_empty());
+    for m in &state.matches {
+        let Placement::Window { start: 20, end: 26, index } = m.placement() else { panic!("unexpected placement {}", m.placement()) };
+        assert!((20..=24).contains(&index));
+        assert_eq!(&m.public()[index..index + 2], "AB");
+        assert!(verify_match(m).is_ok());
+    }
+}
@@ -0,0 +1,55 @@
+//! Overwriting the secrets of matches with zeros once a search ends
+
+use algo_vanity_rs::{AddressMatch, Placement, SearchConfig, VanitySearcher, load_matches, wipe_secrets};
+
+/// Bytes of the whole allocation of a string, also beyond its length
+fn allocation(s: &str, capacity: usize) -> Vec<u8> {
+    // The allocation is still owned by the string, and zeroing it initializes every byte
+    unsafe { std::slice::from_raw_parts(s.as_ptr(), capacity).to_vec() }
+}
+
+#[test]
+fn wipe_zeroes_buffers_in_place() {
+    let mut m = AddressMatch::new("ALGO", "ALGOXYZ", "abandon ability able", Placement::Start);
+    let capacity = m.mnemonic.capacity();
+    let pointer = m.mnemonic.as_ptr();
+
+    m.wipe_secrets();
+    assert!(m.mnemonic.is_empty());
+    assert_eq!(m.mnemonic.as_ptr(), pointer);
+    assert!(allocation(&m.mnemonic, capacity).iter().all(|b| *b == 0));
+}
+
+#[test]
+fn search_secrets_wiped_after_saving() {
+    let save_path = std::env::temp_dir().join(format!("algo-vanity-wipe-{}.json", std::process::id()));
+    let handle = VanitySearcher::new(SearchConfig {
+        vanities: vec![String::from("A"), String::from("B")],
+        once: true,
+        wipe_secrets: true,
+        threads: 1,
+        seed: Some(0),
+        record_seed: true,
+        save_path: save_path.to_string_lossy().into_owned(),
+        ..SearchConfig::default()
+    }).start();
+    let state = handle.state().clone();
+    handle.join();
+    let saved = load_matches(&save_path.to_string_lossy());
+    _ = std::fs::remove_file(&save_path);
+
+    // Secrets are saved before they are wiped
+    let saved = saved.unwrap();
+    assert_eq!(saved.len(), 2);
+    assert!(saved.iter().all(|m| m.mnemonic.split(' ').count() == 25 && m.seed.is_some()));
+
+    let mut s = state.lock().unwrap();
+    let buffers: Vec<_> = s.matches.iter().map(|m| (m.mnemonic.as_ptr(), m.mnemonic.capacity())).collect();
+    wipe_secrets(&mut s);
+    for (m, (pointer, capacity)) in s.matches.iter().zip(buffers) {
+        assert!(m.mnemonic.is_empty() && m.seed.is_none());
+        assert_eq!(m.mnemonic.as_ptr(), pointer);
+        assert!(allocation(&m.mnemonic, capacity).iter().all(|b| *b == 0));
+        assert!(!m.public.is_empty());
+    }
+}